@@ -1,10 +1,17 @@
-#[allow(unused)]
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
 pub const CONFIG_NAME: &str = "eclipse";
 
-#[allow(unused)]
+#[derive(Deserialize)]
+#[serde(default)]
 pub struct Config {
     pub package: Package,
     pub editor: Editor,
+    pub build: Build,
+    pub registry: Registry,
+    pub dependencies: BTreeMap<String, Dependency>,
 }
 impl Default for Config {
     fn default() -> Self {
@@ -14,14 +21,59 @@ impl Default for Config {
                 version: "0.0.0-test".into(),
             },
             editor: Editor { tab_size: 4 },
+            build: Build::default(),
+            registry: Registry::default(),
+            dependencies: BTreeMap::new(),
         }
     }
 }
+impl Config {
+    pub fn file_name() -> String {
+        format!("{CONFIG_NAME}.toml")
+    }
+    pub fn parse(source: &str) -> Result<Self, String> {
+        toml::from_str(source).map_err(|error| error.message().to_string())
+    }
+}
 
-#[allow(unused)]
-#[derive(Default)]
+#[derive(Deserialize, Default)]
+#[serde(default)]
 pub struct Package {
     pub name: String,
     pub version: String,
 }
 
+#[derive(Deserialize)]
+#[serde(default)]
+pub struct Editor {
+    pub tab_size: usize,
+}
+impl Default for Editor {
+    fn default() -> Self {
+        Self { tab_size: 4 }
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct Build {
+    pub target: Option<String>,
+    pub opt_level: Option<u8>,
+    pub output: Option<String>,
+    pub libraries: Vec<String>,
+    pub library_paths: Vec<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct Registry {
+    pub path: Option<String>,
+}
+
+#[derive(Deserialize, Clone, PartialEq, Hash, Debug)]
+#[serde(untagged)]
+pub enum Dependency {
+    Version(String),
+    Path { path: String },
+    Registry { version: String },
+}
@@ -1,4 +1,4 @@
-use super::{Number, JSON};
+use super::{JSON, Number};
 
 impl From<bool> for JSON {
     fn from(value: bool) -> Self {
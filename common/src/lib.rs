@@ -1,8 +1,11 @@
 pub mod cmd;
+pub mod config;
 pub mod constants;
 pub mod exit;
 #[allow(unused)]
 pub mod json;
+pub mod lock;
+pub mod metadata;
 pub mod path;
 pub mod position;
 pub mod status;
@@ -0,0 +1,35 @@
+use serde::Deserialize;
+
+pub const REGISTRY_SOURCE: &str = "registry+";
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct Lock {
+    pub package: Vec<LockedPackage>,
+}
+impl Lock {
+    pub fn parse(source: &str) -> Result<Self, String> {
+        toml::from_str(source).map_err(|error| error.message().to_string())
+    }
+    pub fn registry_versions(&self, name: &str) -> impl Iterator<Item = &str> {
+        self.package
+            .iter()
+            .filter(move |package| {
+                package.name == name
+                    && package
+                        .source
+                        .as_ref()
+                        .is_some_and(|source| source.starts_with(REGISTRY_SOURCE))
+            })
+            .map(|package| package.version.as_str())
+    }
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+pub struct LockedPackage {
+    pub name: String,
+    pub version: String,
+    pub source: Option<String>,
+    pub dependencies: Vec<String>,
+}
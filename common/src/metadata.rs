@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+pub const METADATA_EXTENSION: &str = "meta.toml";
+pub const METADATA_ABI: &str = "eclipse";
+
+#[derive(Serialize, Deserialize, Default, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct Metadata {
+    pub name: String,
+    pub version: String,
+    pub library: String,
+    pub modules: Vec<ModuleMetadata>,
+}
+impl Metadata {
+    pub fn file_name(package: &str) -> String {
+        format!("{package}.{METADATA_EXTENSION}")
+    }
+    pub fn parse(source: &str) -> Result<Self, String> {
+        toml::from_str(source).map_err(|error| error.message().to_string())
+    }
+    pub fn to_toml(&self) -> String {
+        toml::to_string(self).expect("metadata is always serializable")
+    }
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct ModuleMetadata {
+    pub path: String,
+    pub imports: Vec<ImportMetadata>,
+    pub functions: Vec<FunctionMetadata>,
+}
+impl ModuleMetadata {
+    pub fn declarations(&self) -> String {
+        self.functions
+            .iter()
+            .map(|function| function.declaration())
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, PartialEq, Debug)]
+#[serde(default)]
+pub struct ImportMetadata {
+    pub name: String,
+    pub path: String,
+}
+
+#[derive(Serialize, Deserialize, Default, Clone, PartialEq, Debug)]
+#[serde(default, rename_all = "kebab-case")]
+pub struct FunctionMetadata {
+    pub name: String,
+    pub parameters: Vec<String>,
+    pub return_type: String,
+}
+impl FunctionMetadata {
+    pub fn declaration(&self) -> String {
+        format!(
+            "pub extern {METADATA_ABI:?} func {}({}) {} {{}}",
+            self.name,
+            self.parameters.join(", "),
+            self.return_type
+        )
+    }
+}
@@ -1,6 +1,9 @@
 use std::{
     io::Write,
-    sync::mpsc::{self, Receiver, Sender},
+    sync::{
+        Mutex,
+        mpsc::{self, Receiver, Sender},
+    },
     time::Duration,
 };
 
@@ -14,7 +17,7 @@ use std::{
 
 pub struct Status {
     sender: Sender<Option<String>>,
-    done_receiver: Receiver<()>,
+    done_receiver: Mutex<Receiver<()>>,
 }
 impl Status {
     pub fn new() -> Self {
@@ -46,7 +49,7 @@ impl Status {
 
         Self {
             sender,
-            done_receiver,
+            done_receiver: Mutex::new(done_receiver),
         }
     }
     pub fn message(&self, message: String) {
@@ -54,7 +57,9 @@ impl Status {
     }
     pub fn quit(&self) {
         let _ = self.sender.send(None);
-        let _ = self.done_receiver.recv_timeout(Duration::from_secs(2));
+        if let Ok(done_receiver) = self.done_receiver.lock() {
+            let _ = done_receiver.recv_timeout(Duration::from_secs(2));
+        }
     }
 }
 
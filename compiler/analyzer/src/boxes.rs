@@ -0,0 +1,91 @@
+use common::position::PositionRange;
+use diagnostics::{DiagnosticData, DiagnosticResult};
+use syntax::{ast, hir};
+
+use crate::Analyzer;
+
+impl Analyzer<'_> {
+    pub fn boxed(
+        &mut self,
+        mut arguments: Vec<ast::Node>,
+        expected: Option<&hir::Type>,
+        position: PositionRange,
+    ) -> DiagnosticResult<(hir::Node, hir::Type)> {
+        if arguments.len() != 1 {
+            return DiagnosticData::error()
+                .title(format!(
+                    "Function 'Box::new' expects 1 argument(s), got {}",
+                    arguments.len()
+                ))
+                .position(position)
+                .to_err();
+        }
+
+        let expected = match expected {
+            Some(hir::Type::Box(data_type)) => Some(&**data_type),
+            _ => None,
+        };
+        let (value, data_type) = self.expression(arguments.remove(0), expected)?;
+        if data_type == hir::Type::Void {
+            return DiagnosticData::error()
+                .title("Cannot box a value of type 'void'")
+                .position(position)
+                .to_err();
+        }
+        if let hir::Type::Function { .. } = data_type
+            && !matches!(
+                value,
+                hir::Node::Closure { .. } | hir::Node::Function { .. }
+            )
+        {
+            return DiagnosticData::error()
+                .title(format!(
+                    "Cannot box a value of type '{data_type}', only closures and functions can be boxed"
+                ))
+                .position(position)
+                .to_err();
+        }
+
+        let node = hir::Node::Boxed {
+            value: Box::new(value),
+            data_type: data_type.clone(),
+        };
+        Ok((node, hir::Type::Box(Box::new(data_type))))
+    }
+    pub fn deref(
+        &mut self,
+        value: ast::Node,
+        position: PositionRange,
+    ) -> DiagnosticResult<(hir::Node, hir::Type)> {
+        let (value, found) = self.place(value)?;
+        let hir::Type::Box(data_type) = found else {
+            return DiagnosticData::error()
+                .title(format!("Cannot dereference a value of type '{found}'"))
+                .position(position)
+                .to_err();
+        };
+        Ok((
+            hir::Node::Deref(Box::new(value), *data_type.clone()),
+            *data_type,
+        ))
+    }
+    fn place(&mut self, value: ast::Node) -> DiagnosticResult<(hir::Node, hir::Type)> {
+        match value.raw {
+            ast::RawNode::Identifier(name) => {
+                let (name, data_type) = self.variable(&name, value.position)?;
+                let node = hir::Node::Variable {
+                    name,
+                    data_type: data_type.clone(),
+                    position: value.position,
+                };
+                Ok((node, data_type))
+            }
+            ast::RawNode::Deref(inner) => self.deref(*inner, value.position),
+            ast::RawNode::Wrapped(Some(inner)) => self.place(*inner),
+            _ => DiagnosticData::error()
+                .title("Cannot dereference a temporary value, bind it to a variable first")
+                .position(value.position)
+                .to_err(),
+        }
+    }
+}
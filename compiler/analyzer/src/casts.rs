@@ -0,0 +1,111 @@
+use common::position::PositionRange;
+use diagnostics::{DiagnosticData, DiagnosticResult};
+use syntax::{ast, hir};
+
+use crate::Analyzer;
+
+#[derive(Clone, Copy)]
+enum Constant {
+    Integer(i128),
+    Float(f64),
+}
+
+impl Analyzer<'_> {
+    pub fn cast(
+        &mut self,
+        value: ast::Node,
+        data_type: &ast::Type,
+        position: PositionRange,
+    ) -> DiagnosticResult<(hir::Node, hir::Type)> {
+        let target = self.data_type(data_type)?;
+
+        let literal = value.raw.to_string();
+        let constant = constant(&value.raw);
+        let expected = match constant {
+            Some(Constant::Integer(value)) if i64::try_from(value).is_err() => {
+                Some(hir::Type::UInt(64))
+            }
+            Some(Constant::Integer(_)) => Some(hir::Type::Int(64)),
+            Some(Constant::Float(_)) => Some(hir::Type::Float(64)),
+            None => None,
+        };
+        let (value, from) = self.expression(value, expected.as_ref())?;
+
+        let castable = from.is_numeric() || from == hir::Type::Boolean;
+        if !castable || !target.is_numeric() {
+            return DiagnosticData::error()
+                .title(format!("Cannot cast type '{from}' to '{target}'"))
+                .position(position)
+                .to_err();
+        }
+
+        if let Some(constant) = constant
+            && let Some(result) = self.lossy(constant, &target)
+        {
+            self.diagnostics.push(
+                DiagnosticData::warning()
+                    .title(format!(
+                        "Lossy cast of constant '{literal}' to '{target}', {result}"
+                    ))
+                    .position(position),
+            );
+        }
+
+        let node = hir::Node::Cast {
+            value: Box::new(value),
+            from,
+            data_type: target.clone(),
+        };
+        Ok((node, target))
+    }
+    fn lossy(&self, constant: Constant, target: &hir::Type) -> Option<String> {
+        let bits = match target {
+            hir::Type::Int(bits) | hir::Type::UInt(bits) | hir::Type::Float(bits) => *bits,
+            _ => self.compiler.target().pointer_width(),
+        };
+
+        match (constant, target.is_float()) {
+            (Constant::Integer(value), false) => {
+                let wrapped = wrap(value, bits, target.is_signed());
+                (wrapped != value).then(|| format!("the value becomes '{wrapped}'"))
+            }
+            (Constant::Integer(value), true) => {
+                let rounded = match bits {
+                    32 => value as f32 as f64,
+                    _ => value as f64,
+                };
+                (rounded as i128 != value).then(|| format!("the value becomes '{rounded}'"))
+            }
+            (Constant::Float(value), false) => {
+                let truncated = value.trunc() as i128;
+                if value.is_nan() || wrap(truncated, bits, target.is_signed()) != truncated {
+                    return Some("the value is out of range".to_string());
+                }
+                (value.fract() != 0.0).then(|| format!("the value becomes '{truncated}'"))
+            }
+            (Constant::Float(value), true) => {
+                let narrowed = value as f32;
+                (bits == 32 && value.is_finite() && narrowed.is_infinite())
+                    .then(|| format!("the value becomes '{narrowed}'"))
+            }
+        }
+    }
+}
+
+fn constant(raw: &ast::RawNode) -> Option<Constant> {
+    match raw {
+        ast::RawNode::Integer(value) => value.parse().ok().map(Constant::Integer),
+        ast::RawNode::Float(value) => value.parse().ok().map(Constant::Float),
+        ast::RawNode::Wrapped(Some(node)) => constant(&node.raw),
+        _ => None,
+    }
+}
+
+fn wrap(value: i128, bits: u8, signed: bool) -> i128 {
+    let modulus = 1i128 << bits;
+    let wrapped = value.rem_euclid(modulus);
+    match signed && wrapped >= modulus / 2 {
+        true => wrapped - modulus,
+        false => wrapped,
+    }
+}
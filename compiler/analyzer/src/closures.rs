@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+
+use common::position::{PositionRange, Span};
+use diagnostics::{DiagnosticData, DiagnosticResult};
+use syntax::{ast, hir};
+
+use crate::Analyzer;
+
+impl Analyzer<'_> {
+    pub fn closure(
+        &mut self,
+        moves: bool,
+        parameters: Vec<(ast::Identifier, Option<ast::Type>)>,
+        body: ast::Node,
+        expected: Option<&hir::Type>,
+        position: PositionRange,
+    ) -> DiagnosticResult<(hir::Node, hir::Type)> {
+        let (expected_parameters, expected_return) = match expected {
+            Some(hir::Type::Function {
+                parameters,
+                return_type,
+            }) => (parameters.clone(), Some((**return_type).clone())),
+            _ => (Vec::new(), None),
+        };
+
+        let mut hir_parameters = Vec::with_capacity(parameters.len());
+        for (index, (name, data_type)) in parameters.into_iter().enumerate() {
+            let data_type = match (data_type, expected_parameters.get(index)) {
+                (Some(data_type), _) => self.data_type(&data_type)?,
+                (None, Some(data_type)) => data_type.clone(),
+                (None, None) => {
+                    return DiagnosticData::error()
+                        .title(format!(
+                            "Cannot infer the type of parameter '{}', add a type annotation",
+                            name.raw
+                        ))
+                        .position(name.position)
+                        .to_err();
+                }
+            };
+            hir_parameters.push(hir::Parameter {
+                mutable: false,
+                name: name.raw,
+                data_type,
+            });
+        }
+
+        self.scopes.push(
+            hir_parameters
+                .iter()
+                .map(|p| (p.name.clone(), (p.name.clone(), p.data_type.clone())))
+                .collect::<HashMap<_, _>>(),
+        );
+        for parameter in &hir_parameters {
+            let count = self.declarations.entry(parameter.name.clone()).or_default();
+            *count = (*count).max(1);
+        }
+        self.closures.push((self.scopes.len() - 1, Vec::new()));
+        let return_type = std::mem::replace(
+            &mut self.return_type,
+            expected_return.clone().unwrap_or(hir::Type::Void),
+        );
+
+        let result = self.closure_body(body, expected_return);
+
+        self.return_type = return_type;
+        self.scopes.pop();
+        let (_, captures) = self.closures.pop().expect("Expected a closure frame");
+        let (body, return_type) = result?;
+
+        let data_type = hir::Type::Function {
+            parameters: hir_parameters.iter().map(|p| p.data_type.clone()).collect(),
+            return_type: Box::new(return_type.clone()),
+        };
+        let node = hir::Node::Closure {
+            moves,
+            parameters: hir_parameters,
+            return_type,
+            captures,
+            body,
+            position,
+        };
+        Ok((node, data_type))
+    }
+    fn closure_body(
+        &mut self,
+        body: ast::Node,
+        expected: Option<hir::Type>,
+    ) -> DiagnosticResult<(Vec<hir::Node>, hir::Type)> {
+        use ast::RawNode;
+
+        let position = body.position;
+        match body.raw {
+            RawNode::Block(nodes) => {
+                let returns =
+                    matches!(nodes.last(), Some(n) if matches!(n.raw, RawNode::Return(_)));
+                if !returns && self.return_type != hir::Type::Void {
+                    self.error(
+                        DiagnosticData::error()
+                            .title(format!(
+                                "Expected closure to return a value of type: {}",
+                                self.return_type
+                            ))
+                            .position(position),
+                    );
+                }
+
+                let body = nodes
+                    .into_iter()
+                    .filter_map(|node| {
+                        let result = self.node(node);
+                        self.capture(result)
+                    })
+                    .collect();
+                Ok((body, self.return_type.clone()))
+            }
+            raw => {
+                let (value, found) =
+                    self.expression(Span::new(raw, position), expected.as_ref())?;
+                if let Some(expected) = &expected {
+                    self.expect_type(expected, &found, position)?;
+                }
+
+                let body = match found {
+                    hir::Type::Void => vec![value],
+                    _ => vec![hir::Node::Return(Some(Box::new(value)))],
+                };
+                Ok((body, found))
+            }
+        }
+    }
+    pub fn function_value(&mut self, node: ast::Node) -> DiagnosticResult<(hir::Node, hir::Type)> {
+        let (signature, self_type) = self.callee(&node)?;
+        if let Some(self_type) = self_type {
+            return DiagnosticData::error()
+                .title(format!(
+                    "Associated function '{self_type}::{}' cannot be used as a value, wrap it in a closure",
+                    signature.name
+                ))
+                .position(node.position)
+                .to_err();
+        }
+        if !signature.generics.is_empty() {
+            return DiagnosticData::error()
+                .title(format!(
+                    "Generic function '{}' cannot be used as a value",
+                    signature.name
+                ))
+                .position(node.position)
+                .to_err();
+        }
+
+        let data_type = hir::Type::Function {
+            parameters: signature.parameters.clone(),
+            return_type: Box::new(signature.return_type.clone()),
+        };
+        let node = hir::Node::Function {
+            name: signature.name,
+            symbol: signature.symbol,
+            data_type: data_type.clone(),
+        };
+        Ok((node, data_type))
+    }
+    pub fn invoke(
+        &mut self,
+        callee: ast::Node,
+        arguments: Vec<ast::Node>,
+        position: PositionRange,
+    ) -> DiagnosticResult<(hir::Node, hir::Type)> {
+        let callee_position = callee.position;
+        let (mut callee, mut data_type) = self.expression(callee, None)?;
+        if let hir::Type::Box(inner) = &data_type
+            && let hir::Type::Function { .. } = **inner
+        {
+            let inner = (**inner).clone();
+            callee = hir::Node::Deref(Box::new(callee), inner.clone());
+            data_type = inner;
+        }
+        let hir::Type::Function {
+            parameters,
+            return_type,
+        } = data_type
+        else {
+            return DiagnosticData::error()
+                .title(format!(
+                    "Expected a function, got a value of type '{data_type}'"
+                ))
+                .position(callee_position)
+                .to_err();
+        };
+
+        if arguments.len() != parameters.len() {
+            return DiagnosticData::error()
+                .title(format!(
+                    "Function of type '{}' expects {} argument(s), got {}",
+                    hir::Type::Function {
+                        parameters: parameters.clone(),
+                        return_type: return_type.clone(),
+                    },
+                    parameters.len(),
+                    arguments.len()
+                ))
+                .position(position)
+                .to_err();
+        }
+
+        let mut values = Vec::with_capacity(arguments.len());
+        for (argument, reference) in arguments.into_iter().zip(&parameters) {
+            let position = argument.position;
+            let parameter = reference.dereference();
+            let (value, found) = self.argument(argument, reference, Some(parameter))?;
+            self.expect_type(parameter, &found, position)?;
+            values.push(value);
+        }
+
+        let node = hir::Node::Invoke {
+            callee: Box::new(callee),
+            arguments: values,
+            parameters,
+            return_type: (*return_type).clone(),
+        };
+        Ok((node, *return_type))
+    }
+}
@@ -1,17 +1,80 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+
 use context::CompilerCtx;
+use diagnostics::DiagnosticData;
 use syntax::{ast, hir};
 
+mod boxes;
+mod casts;
+mod closures;
+mod queries;
+mod results;
 mod semantic;
+mod strings;
+mod structs;
+mod symbols;
+mod traits;
 mod types;
 
+pub use queries::{Signatures, StructTable, Structs, TraitTable, Traits, Typeck, Typed, resolved};
+pub use structs::recursive_structs;
+
 struct Analyzer<'ctx> {
     pub compiler: &'ctx mut CompilerCtx,
+    names: Option<ast::Names>,
+    crates: Vec<(PathBuf, String)>,
+    functions: HashMap<(PathBuf, String), hir::Signature>,
+    traits: HashMap<String, Vec<hir::Signature>>,
+    structs: HashMap<String, hir::Struct>,
+    impls: Vec<(String, hir::Type)>,
+    methods: Vec<(hir::Type, hir::Signature)>,
+    relative_path: PathBuf,
+    generics: Vec<hir::Generic>,
+    self_type: Option<hir::Type>,
+    scopes: Vec<HashMap<String, (String, hir::Type)>>,
+    declarations: HashMap<String, usize>,
+    closures: Vec<(usize, Vec<hir::Capture>)>,
+    return_type: hir::Type,
+    diagnostics: Vec<DiagnosticData>,
+}
+impl<'ctx> Analyzer<'ctx> {
+    fn new(compiler: &'ctx mut CompilerCtx) -> Self {
+        Self {
+            compiler,
+            names: None,
+            crates: Vec::new(),
+            functions: HashMap::new(),
+            traits: HashMap::new(),
+            structs: HashMap::new(),
+            impls: Vec::new(),
+            methods: Vec::new(),
+            relative_path: PathBuf::new(),
+            generics: Vec::new(),
+            self_type: None,
+            scopes: Vec::new(),
+            declarations: HashMap::new(),
+            closures: Vec::new(),
+            return_type: hir::Type::Void,
+            diagnostics: Vec::new(),
+        }
+    }
 }
 
 pub fn analyze(
     compiler: &mut CompilerCtx,
     collection: ast::ModuleCollection,
 ) -> hir::ModuleCollection {
-    let mut analyzer = Analyzer { compiler };
-    analyzer.analyze(collection)
+    analyze_changed(compiler, collection, &HashSet::new())
+}
+
+pub fn analyze_changed(
+    compiler: &mut CompilerCtx,
+    collection: ast::ModuleCollection,
+    unchanged: &HashSet<PathBuf>,
+) -> hir::ModuleCollection {
+    let mut analyzer = Analyzer::new(compiler);
+    analyzer.analyze(collection, unchanged)
 }
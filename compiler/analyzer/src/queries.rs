@@ -0,0 +1,190 @@
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use context::{CompilerCtx, Query};
+use diagnostics::DiagnosticData;
+use resolver::{Function, Parse, Resolve, Resolved, resolve_bindings};
+use syntax::{ast, hir};
+
+use crate::{Analyzer, symbols::crate_prefixes};
+
+pub fn resolved(compiler: &mut CompilerCtx) -> Arc<Resolved> {
+    let root = compiler.root();
+    compiler.query::<Resolve>(root)
+}
+
+fn module_nodes(
+    compiler: &mut CompilerCtx,
+    resolved: &Resolved,
+    relative_path: &Path,
+) -> Vec<ast::Node> {
+    if let Some(nodes) = resolved.metadata(relative_path) {
+        return nodes.clone();
+    }
+    match compiler
+        .query::<Parse>(relative_path.to_path_buf())
+        .as_ref()
+    {
+        Ok(nodes) => nodes.clone(),
+        Err(_) => Vec::new(),
+    }
+}
+
+impl<'ctx> Analyzer<'ctx> {
+    fn resolved(
+        compiler: &'ctx mut CompilerCtx,
+        resolved: &Resolved,
+        relative_path: &Path,
+        nodes: &[ast::Node],
+    ) -> (Self, Vec<DiagnosticData>) {
+        let (names, diagnostics) = resolve_bindings(resolved.items.clone(), relative_path, nodes);
+
+        let mut analyzer = Analyzer::new(compiler);
+        analyzer.names = Some(names);
+        analyzer.crates = crate_prefixes(&resolved.crates);
+        analyzer.relative_path = relative_path.to_path_buf();
+        (analyzer, diagnostics)
+    }
+}
+
+pub struct Signatures;
+impl Query for Signatures {
+    type Key = PathBuf;
+    type Value = Vec<hir::Signature>;
+
+    fn execute(compiler: &mut CompilerCtx, relative_path: &PathBuf) -> Self::Value {
+        let resolved = resolved(compiler);
+        let nodes = module_nodes(compiler, &resolved, relative_path);
+
+        let (mut analyzer, _) = Analyzer::resolved(compiler, &resolved, relative_path, &nodes);
+        nodes
+            .iter()
+            .filter_map(|node| analyzer.signature(node))
+            .collect()
+    }
+
+    fn cycle(_: &PathBuf) -> Self::Value {
+        Vec::new()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StructTable {
+    pub structs: Vec<hir::Struct>,
+    pub diagnostics: Vec<DiagnosticData>,
+}
+
+pub struct Structs;
+impl Query for Structs {
+    type Key = PathBuf;
+    type Value = StructTable;
+
+    fn execute(compiler: &mut CompilerCtx, relative_path: &PathBuf) -> Self::Value {
+        let resolved = resolved(compiler);
+        let nodes = module_nodes(compiler, &resolved, relative_path);
+
+        let (mut analyzer, _) = Analyzer::resolved(compiler, &resolved, relative_path, &nodes);
+        analyzer.struct_table(&nodes)
+    }
+
+    fn cycle(_: &PathBuf) -> Self::Value {
+        StructTable::default()
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct TraitTable {
+    pub traits: Vec<(String, Vec<hir::Signature>)>,
+    pub impls: Vec<(String, hir::Type)>,
+    pub methods: Vec<(hir::Type, hir::Signature)>,
+}
+
+pub struct Traits;
+impl Query for Traits {
+    type Key = PathBuf;
+    type Value = TraitTable;
+
+    fn execute(compiler: &mut CompilerCtx, relative_path: &PathBuf) -> Self::Value {
+        let resolved = resolved(compiler);
+        let nodes = module_nodes(compiler, &resolved, relative_path);
+
+        let (mut analyzer, _) = Analyzer::resolved(compiler, &resolved, relative_path, &nodes);
+        analyzer.trait_table(&nodes)
+    }
+
+    fn cycle(_: &PathBuf) -> Self::Value {
+        TraitTable::default()
+    }
+}
+
+#[derive(Debug)]
+pub struct Typed {
+    pub function: Option<hir::Function>,
+    pub diagnostics: Vec<DiagnosticData>,
+}
+
+pub struct Typeck;
+impl Query for Typeck {
+    type Key = (PathBuf, String);
+    type Value = Typed;
+
+    fn execute(compiler: &mut CompilerCtx, key: &Self::Key) -> Self::Value {
+        let (relative_path, name) = key;
+        let node = compiler.query::<Function>(key.clone()).as_ref().clone();
+        let node = match node {
+            Some(node) => node,
+            None => {
+                return Typed {
+                    function: None,
+                    diagnostics: vec![
+                        DiagnosticData::error()
+                            .title(format!("Function '{name}' not found in {relative_path:?}")),
+                    ],
+                };
+            }
+        };
+
+        let resolved = resolved(compiler);
+        let mut signatures = Vec::new();
+        let mut traits = Vec::new();
+        let mut structs = Vec::new();
+        for path in &resolved.modules {
+            signatures.push((path, compiler.query::<Signatures>(path.clone())));
+            traits.push(compiler.query::<Traits>(path.clone()));
+            structs.push(compiler.query::<Structs>(path.clone()));
+        }
+
+        let nodes = std::slice::from_ref(&node);
+        let (mut analyzer, mut diagnostics) =
+            Analyzer::resolved(compiler, &resolved, relative_path, nodes);
+        for (path, signatures) in signatures {
+            for signature in signatures.iter() {
+                analyzer
+                    .functions
+                    .insert((path.clone(), signature.name.clone()), signature.clone());
+            }
+        }
+        for table in traits {
+            analyzer.insert_traits(table.as_ref().clone());
+        }
+        for table in structs {
+            analyzer.insert_structs(table.structs.clone());
+        }
+
+        let function = analyzer.item(node);
+        diagnostics.extend(analyzer.diagnostics);
+        Typed {
+            function,
+            diagnostics,
+        }
+    }
+
+    fn cycle(_: &Self::Key) -> Self::Value {
+        Typed {
+            function: None,
+            diagnostics: Vec::new(),
+        }
+    }
+}
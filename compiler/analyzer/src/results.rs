@@ -0,0 +1,101 @@
+use common::position::PositionRange;
+use diagnostics::{DiagnosticData, DiagnosticResult};
+use syntax::{ast, hir};
+
+use crate::Analyzer;
+
+impl Analyzer<'_> {
+    pub fn payload_type(&self, data_type: &ast::Type) -> DiagnosticResult<hir::Type> {
+        let found = self.data_type(data_type)?;
+        let title = match &found {
+            hir::Type::Void => "Type 'void' cannot be used in a result".to_string(),
+            found if found.is_reference() => {
+                format!("Type '{found}' cannot be used in a result, it is not copyable")
+            }
+            _ => return Ok(found),
+        };
+
+        DiagnosticData::error()
+            .title(title)
+            .position(data_type.position)
+            .to_err()
+    }
+    pub fn variant(
+        &mut self,
+        variant: hir::Variant,
+        mut arguments: Vec<ast::Node>,
+        expected: Option<&hir::Type>,
+        position: PositionRange,
+    ) -> DiagnosticResult<(hir::Node, hir::Type)> {
+        if arguments.len() != 1 {
+            return DiagnosticData::error()
+                .title(format!(
+                    "Function '{variant}' expects 1 argument(s), got {}",
+                    arguments.len()
+                ))
+                .position(position)
+                .to_err();
+        }
+
+        let Some((data_type, payload)) =
+            expected.and_then(|t| Some((t.clone(), variant.payload(t)?.clone())))
+        else {
+            return DiagnosticData::error()
+                .title(format!(
+                    "Cannot infer the result type of '{variant}', annotate it with 'result<T, E>'"
+                ))
+                .position(position)
+                .to_err();
+        };
+
+        let argument = arguments.remove(0);
+        let argument_position = argument.position;
+        let (value, found) = self.expression(argument, Some(&payload))?;
+        self.expect_type(&payload, &found, argument_position)?;
+
+        let node = hir::Node::Variant {
+            variant,
+            value: Box::new(value),
+            data_type: data_type.clone(),
+        };
+        Ok((node, data_type))
+    }
+    pub fn try_result(
+        &mut self,
+        value: ast::Node,
+        position: PositionRange,
+    ) -> DiagnosticResult<(hir::Node, hir::Type)> {
+        let value_position = value.position;
+        let (value, data_type) = self.expression(value, None)?;
+        let hir::Type::Result(ok, err) = &data_type else {
+            return DiagnosticData::error()
+                .title(format!(
+                    "The '?' operator can only be applied to a result, got '{data_type}'"
+                ))
+                .position(value_position)
+                .to_err();
+        };
+
+        let title = match &self.return_type {
+            hir::Type::Result(_, expected) if expected == err => {
+                let ok = *ok.clone();
+                let node = hir::Node::Try {
+                    value: Box::new(value),
+                    data_type,
+                };
+                return Ok((node, ok));
+            }
+            hir::Type::Result(_, expected) => format!(
+                "The '?' operator cannot convert an error of type '{err}' into '{expected}'"
+            ),
+            return_type => format!(
+                "The '?' operator can only be used in a function that returns a result, found '{return_type}'"
+            ),
+        };
+
+        DiagnosticData::error()
+            .title(title)
+            .position(position)
+            .to_err()
+    }
+}
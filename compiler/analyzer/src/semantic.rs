@@ -1,16 +1,59 @@
-use std::path::PathBuf;
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
 
-use common::position::Span;
-use diagnostics::DiagnosticResult;
-use syntax::{ast, hir};
+use common::position::{PositionRange, Span};
+use diagnostics::{DiagnosticData, DiagnosticResult};
+use syntax::{
+    ast, hir, mir,
+    operators::{ArithmeticOperator, CompareOperator, EqualsOperation, Operator},
+};
 
-use crate::Analyzer;
+use crate::{Analyzer, recursive_structs, symbols::crate_prefixes};
 
 impl Analyzer<'_> {
-    pub fn analyze(&mut self, collection: ast::ModuleCollection) -> hir::ModuleCollection {
-        let modules: Vec<hir::Module> = collection
-            .modules
+    pub fn analyze(
+        &mut self,
+        collection: ast::ModuleCollection,
+        unchanged: &HashSet<PathBuf>,
+    ) -> hir::ModuleCollection {
+        let mut modules: Vec<(PathBuf, ast::Module)> = collection.modules.into_iter().collect();
+        modules.sort_by(|(a, _), (b, _)| a.cmp(b));
+        self.names = Some(collection.names);
+        self.crates = crate_prefixes(&collection.crates);
+
+        for (path, module) in &modules {
+            self.relative_path = path.clone();
+            let table = self.struct_table(&module.nodes);
+            self.insert_structs(table.structs);
+        }
+        let mut structs: Vec<hir::Struct> = self.structs.values().cloned().collect();
+        structs.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+        for (path, data) in recursive_structs(&structs) {
+            if !unchanged.contains(&path) {
+                self.compiler.diagnostics.insert(&path, data);
+            }
+        }
+
+        for (path, module) in &modules {
+            self.relative_path = path.clone();
+            let table = self.trait_table(&module.nodes);
+            self.insert_traits(table);
+        }
+        for (path, module) in &modules {
+            self.relative_path = path.clone();
+            for node in &module.nodes {
+                if let Some(signature) = self.signature(node) {
+                    self.functions
+                        .insert((path.clone(), signature.name.clone()), signature);
+                }
+            }
+        }
+
+        let modules: Vec<hir::Module> = modules
             .into_iter()
+            .filter(|(path, _)| !unchanged.contains(path))
             .map(|(path, module)| self.module(path, module))
             .collect();
 
@@ -19,46 +62,267 @@ impl Analyzer<'_> {
     fn module(&mut self, relative_path: PathBuf, module: ast::Module) -> hir::Module {
         use ast::RawNode;
 
+        self.relative_path = relative_path.clone();
+        let mut imports = Vec::new();
+        let mut structs = Vec::new();
+        let mut functions = Vec::new();
+        let mut impls = Vec::new();
+
         for node in module.nodes {
-            match node.raw {
-                RawNode::Function {
-                    name,
-                    parameters,
-                    return_type,
-                    node,
-                } => self.function(name, parameters, return_type, *node),
-                r => todo!("{r:?}"),
+            let position = node.position;
+            match node.raw.item() {
+                RawNode::Function { .. } => functions.extend(self.item(node)),
+                RawNode::Trait { .. } => self.trait_declaration(node),
+                RawNode::Struct { .. } => structs.extend(self.struct_declaration(&node)),
+                RawNode::Impl { .. } => impls.extend(self.implementation(node)),
+                RawNode::Import(name) => imports.push(name.raw.clone()),
+                RawNode::Use(_) => {}
+                raw => self.error(
+                    DiagnosticData::error()
+                        .title(format!("Expected an item at the top level, got: {raw}"))
+                        .position(position),
+                ),
             };
         }
 
-        todo!()
+        for data in std::mem::take(&mut self.diagnostics) {
+            self.compiler.diagnostics.insert(&relative_path, data);
+        }
+
+        hir::Module {
+            path: relative_path,
+            imports,
+            structs,
+            functions,
+            impls,
+        }
+    }
+    pub fn item(&mut self, node: ast::Node) -> Option<hir::Function> {
+        use ast::RawNode;
+
+        let external = is_extern(&node.raw);
+        let symbol = match node.raw.item() {
+            RawNode::Function { name, .. } => {
+                let symbol = self.symbol(&node.raw, &name.raw);
+                if external && mir::RUNTIME.contains(&symbol.as_str()) {
+                    self.error(
+                        DiagnosticData::error()
+                            .title(format!(
+                                "'{symbol}' is reserved by the runtime and cannot be declared as an extern function"
+                            ))
+                            .position(name.position),
+                    );
+                }
+                symbol
+            }
+            _ => return None,
+        };
+        let raw = match node.raw {
+            RawNode::Modifiers(_, node) => node.raw,
+            raw => raw,
+        };
+
+        match raw {
+            RawNode::Function {
+                name,
+                generics,
+                parameters,
+                return_type,
+                node,
+            } => {
+                let function =
+                    self.function(name, generics, parameters, return_type, *node, external);
+                Some(hir::Function { symbol, ..function })
+            }
+            _ => None,
+        }
     }
     fn function(
         &mut self,
         name: Span<String>,
+        generics: Vec<ast::Generic>,
         parameters: Vec<ast::Parameter>,
         return_type: ast::Type,
         body: ast::Node,
+        external: bool,
     ) -> hir::Function {
         use ast::RawNode;
 
-        todo!()
+        self.generics.clear();
+        for generic in generics {
+            let ast::RawGeneric { name, bounds } = generic.raw;
+            if self.is_generic(&name.raw) {
+                self.error(
+                    DiagnosticData::error()
+                        .title(format!(
+                            "Type parameter '{}' is defined multiple times",
+                            name.raw
+                        ))
+                        .position(name.position),
+                );
+                continue;
+            }
+
+            let bounds = bounds
+                .iter()
+                .filter_map(|bound| {
+                    let result = self.trait_name(bound);
+                    self.capture(result)
+                })
+                .collect();
+            self.generics.push(hir::Generic {
+                name: name.raw,
+                bounds,
+            });
+        }
+        if external && !self.generics.is_empty() {
+            self.error(
+                DiagnosticData::error()
+                    .title(format!("Extern function '{}' cannot be generic", name.raw))
+                    .position(name.position),
+            );
+        }
+
+        let parameters: Vec<hir::Parameter> = parameters
+            .into_iter()
+            .filter_map(|parameter| {
+                let data_type = self.capture(self.parameter_type(&parameter))?;
+                let parameter = parameter.raw;
+                Some(hir::Parameter {
+                    mutable: parameter.reference.is_none() && parameter.mutable.is_some(),
+                    name: parameter.name.raw,
+                    data_type,
+                })
+            })
+            .collect();
+        self.scopes = vec![
+            parameters
+                .iter()
+                .map(|p| {
+                    let data_type = p.data_type.dereference().clone();
+                    (p.name.clone(), (p.name.clone(), data_type))
+                })
+                .collect(),
+        ];
+        self.declarations = parameters.iter().map(|p| (p.name.clone(), 1)).collect();
+
+        self.return_type = self
+            .capture(self.data_type(&return_type))
+            .unwrap_or(hir::Type::Void);
+
+        let nodes = match body.raw {
+            RawNode::Block(nodes) => nodes,
+            raw => vec![Span::new(raw, body.position)],
+        };
+
+        if external {
+            if !nodes.is_empty() {
+                self.error(
+                    DiagnosticData::error()
+                        .title(format!("Extern function '{}' cannot have a body", name.raw))
+                        .position(body.position),
+                );
+            }
+
+            return hir::Function {
+                symbol: name.raw.clone(),
+                name: name.raw,
+                path: self.relative_path.clone(),
+                generics: std::mem::take(&mut self.generics),
+                parameters,
+                return_type: self.return_type.clone(),
+                body: Vec::new(),
+                external,
+            };
+        }
+
+        let returns = matches!(nodes.last(), Some(n) if matches!(n.raw, RawNode::Return(_)));
+        if !returns && self.return_type != hir::Type::Void {
+            self.error(
+                DiagnosticData::error()
+                    .title(format!(
+                        "Expected function '{}' to return a value of type: {}",
+                        name.raw, self.return_type
+                    ))
+                    .position(body.position),
+            );
+        }
+
+        let body = nodes
+            .into_iter()
+            .filter_map(|node| {
+                let result = self.node(node);
+                self.capture(result)
+            })
+            .collect();
+
+        hir::Function {
+            symbol: name.raw.clone(),
+            name: name.raw,
+            path: self.relative_path.clone(),
+            generics: std::mem::take(&mut self.generics),
+            parameters,
+            return_type: self.return_type.clone(),
+            body,
+            external,
+        }
     }
-    fn node(&mut self, node: ast::Node, expected: &Option<ast::Type>) -> hir::Node {
+    pub fn node(&mut self, node: ast::Node) -> DiagnosticResult<hir::Node> {
         use ast::RawNode;
 
-        match node.raw {
-            RawNode::Integer(i) => hir::Node::Integer(i),
-            RawNode::Bool(value) => hir::Node::Boolean(value),
-            RawNode::Return(expr) => match expr {
-                Some(expr) => {
-                    let expr = self.node(*expr, &None);
-                    hir::Node::Return(Some(Box::new(expr)))
-                }
-                None => hir::Node::Return(None),
-            },
+        Ok(match node.raw {
+            RawNode::Return(expr) => {
+                let expected = self.return_type.clone();
+                let value = match expr {
+                    Some(expr) => {
+                        let position = expr.position;
+                        let (value, data_type) = self.expression(*expr, Some(&expected))?;
+                        self.expect_type(&expected, &data_type, position)?;
+                        Some(Box::new(value))
+                    }
+                    None => {
+                        self.expect_type(&expected, &hir::Type::Void, node.position)?;
+                        None
+                    }
+                };
+                hir::Node::Return(value)
+            }
             RawNode::Block(body) => {
-                hir::Node::Block(body.into_iter().map(|n| self.node(n, &None)).collect())
+                self.scopes.push(HashMap::new());
+                let mut nodes = Vec::with_capacity(body.len());
+                for node in body {
+                    let result = self.node(node);
+                    if let Some(node) = self.capture(result) {
+                        nodes.push(node)
+                    }
+                }
+                self.scopes.pop();
+                hir::Node::Block(nodes)
+            }
+            RawNode::Call(callee, arguments) => {
+                self.call(*callee, arguments, None, node.position)?.0
+            }
+            RawNode::Field(receiver, field) if matches!(field.raw, RawNode::Call(..)) => {
+                let raw = RawNode::Field(receiver, field);
+                self.expression(Span::new(raw, node.position), None)?.0
+            }
+            raw @ RawNode::Try(_) => self.expression(Span::new(raw, node.position), None)?.0,
+            RawNode::SetPath {
+                path,
+                operation: EqualsOperation::Equals,
+                value,
+            } => {
+                let (name, data_type) = self.variable(&path.raw, path.position)?;
+                let position = value.position;
+                let (value, found) = self.expression(*value, Some(&data_type))?;
+                self.expect_type(&data_type, &found, position)?;
+
+                hir::Node::Assign {
+                    name,
+                    data_type,
+                    value: Box::new(value),
+                    position: node.position,
+                }
             }
             RawNode::Declare {
                 mutable,
@@ -66,27 +330,549 @@ impl Analyzer<'_> {
                 data_type,
                 node,
             } => {
-                let data_type = self.extract_data_type(&data_type, &node).unwrap();
-                let value = Box::new(self.node(*node, &None));
+                let expected = match &data_type {
+                    Some(t) => Some(self.data_type(t)?),
+                    None => None,
+                };
+
+                let position = node.position;
+                let (value, found) = self.expression(*node, expected.as_ref())?;
+                if let Some(expected) = &expected {
+                    self.expect_type(expected, &found, position)?;
+                }
+                let name = self.declare(name.raw, found.clone());
+
+                hir::Node::DeclareVariable {
+                    name,
+                    mutable: mutable.is_some(),
+                    data_type: found,
+                    value: Box::new(value),
+                }
+            }
+            raw => {
+                return DiagnosticData::error()
+                    .title(format!("Unsupported statement: {raw}"))
+                    .position(node.position)
+                    .to_err();
+            }
+        })
+    }
+    pub fn expression(
+        &mut self,
+        node: ast::Node,
+        expected: Option<&hir::Type>,
+    ) -> DiagnosticResult<(hir::Node, hir::Type)> {
+        use ast::RawNode;
+
+        Ok(match node.raw {
+            RawNode::Integer(value) => {
+                let data_type = match expected {
+                    Some(t) if t.is_integer() => t.clone(),
+                    _ => hir::Type::Int(32),
+                };
+                (hir::Node::Integer(value), data_type)
+            }
+            RawNode::Float(value) => {
+                let data_type = match expected {
+                    Some(t) if t.is_float() => t.clone(),
+                    _ => hir::Type::Float(64),
+                };
+                (hir::Node::Float(value), data_type)
+            }
+            RawNode::Bool(value) => (hir::Node::Boolean(value), hir::Type::Boolean),
+            RawNode::String(value) => (hir::Node::String(value), hir::Type::Str),
+            RawNode::Wrapped(Some(node)) => self.expression(*node, expected)?,
+            RawNode::Identifier(name) if !self.in_scope(&name) => {
+                self.function_value(Span::new(RawNode::Identifier(name), node.position))?
+            }
+            raw @ RawNode::Path(_) => self.function_value(Span::new(raw, node.position))?,
+            RawNode::Identifier(name) => {
+                let (name, data_type) = self.variable(&name, node.position)?;
+                let variable = hir::Node::Variable {
+                    name,
+                    data_type: data_type.clone(),
+                    position: node.position,
+                };
+                (variable, data_type)
+            }
+            RawNode::Call(callee, arguments) => {
+                self.call(*callee, arguments, expected, node.position)?
+            }
+            RawNode::Operation {
+                left,
+                right,
+                operator,
+            } => self.binary(*left, *right, operator, expected, node.position)?,
+            RawNode::Closure {
+                moves,
+                parameters,
+                body,
+            } => self.closure(moves.is_some(), parameters, *body, expected, node.position)?,
+            RawNode::Deref(value) => self.deref(*value, node.position)?,
+            RawNode::Try(value) => self.try_result(*value, node.position)?,
+            RawNode::Cast(value, data_type) => self.cast(*value, &data_type, node.position)?,
+            RawNode::Field(receiver, field) if matches!(field.raw, RawNode::Call(..)) => {
+                let RawNode::Call(method, arguments) = field.raw else {
+                    unreachable!()
+                };
+                self.method_call(*receiver, *method, arguments, node.position)?
+            }
+            RawNode::Field(receiver, field) => self.field(*receiver, *field)?,
+            RawNode::Construct { name, fields } => self.construct(name, fields, node.position)?,
+            raw => {
+                return DiagnosticData::error()
+                    .title(format!("Unsupported expression: {raw}"))
+                    .position(node.position)
+                    .to_err();
+            }
+        })
+    }
+    fn binary(
+        &mut self,
+        left: ast::Node,
+        right: ast::Node,
+        operator: Operator,
+        expected: Option<&hir::Type>,
+        range: PositionRange,
+    ) -> DiagnosticResult<(hir::Node, hir::Type)> {
+        let operand = match operator {
+            Operator::Arithmetic(_) => expected.filter(|t| t.is_numeric()),
+            Operator::Compare(_) => None,
+        };
+
+        let position = right.position;
+        let ((left, data_type), (right, found)) = match left.raw {
+            ast::RawNode::Integer(_) | ast::RawNode::Float(_) => {
+                let right = self.expression(right, operand)?;
+                (self.expression(left, Some(&right.1))?, right)
+            }
+            _ => {
+                let left = self.expression(left, operand)?;
+                let right = self.expression(right, Some(&left.1))?;
+                (left, right)
+            }
+        };
+        self.expect_type(&data_type, &found, position)?;
+
+        let supported = match &operator {
+            Operator::Arithmetic(
+                ArithmeticOperator::Remainder
+                | ArithmeticOperator::LeftBitshift
+                | ArithmeticOperator::RightBitshift,
+            ) => data_type.is_integer(),
+            Operator::Arithmetic(_) => data_type.is_numeric(),
+            Operator::Compare(CompareOperator::Compare | CompareOperator::NotEquals) => {
+                data_type.is_numeric() || data_type == hir::Type::Boolean
+            }
+            Operator::Compare(CompareOperator::And | CompareOperator::Or) => {
+                data_type == hir::Type::Boolean
+            }
+            Operator::Compare(_) => data_type.is_numeric(),
+        };
+        if !supported {
+            return DiagnosticData::error()
+                .title(format!(
+                    "Cannot apply operator '{operator}' to type '{data_type}'"
+                ))
+                .position(position)
+                .to_err();
+        }
+
+        let result = match operator {
+            Operator::Arithmetic(_) => data_type.clone(),
+            Operator::Compare(_) => hir::Type::Boolean,
+        };
+        let node = hir::Node::Binary {
+            left: Box::new(left),
+            right: Box::new(right),
+            operator,
+            data_type,
+            position: range,
+        };
+        Ok((node, result))
+    }
+    pub fn in_scope(&self, name: &str) -> bool {
+        self.scopes.iter().any(|scope| scope.contains_key(name))
+    }
+    pub fn declare(&mut self, name: String, data_type: hir::Type) -> String {
+        let count = self.declarations.entry(name.clone()).or_default();
+        let local = match *count {
+            0 => name.clone(),
+            count => format!("{name}${count}"),
+        };
+        *count += 1;
 
-                todo!()
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(name, (local.clone(), data_type));
+        }
+        local
+    }
+    pub fn variable(
+        &mut self,
+        name: &str,
+        position: PositionRange,
+    ) -> DiagnosticResult<(String, hir::Type)> {
+        let found = self
+            .scopes
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(index, scope)| Some((index, scope.get(name)?.clone())));
+        if let Some((index, (local, data_type))) = found {
+            for (depth, captures) in &mut self.closures {
+                if *depth > index && !captures.iter().any(|c| c.name == local) {
+                    captures.push(hir::Capture {
+                        name: local.clone(),
+                        data_type: data_type.clone(),
+                    });
+                }
             }
-            r => todo!("{r:?}"),
+            return Ok((local, data_type));
         }
+
+        let title = match self.definition(position) {
+            Some(definition) if definition.kind == ast::DefinitionKind::Function => {
+                format!("Function '{name}' cannot be used as a value")
+            }
+            _ => format!("'{name}' not found in this scope"),
+        };
+        DiagnosticData::error()
+            .title(title)
+            .position(position)
+            .to_err()
+    }
+    pub fn definition(&self, position: PositionRange) -> Option<&ast::Definition> {
+        let names = self.names.as_ref()?;
+        let id = names.binding(&self.relative_path, &position)?;
+        Some(names.definition(id))
     }
-    fn extract_data_type(
+    pub fn callee(
         &self,
-        expected: &Option<ast::Type>,
-        node: &ast::Node,
-    ) -> DiagnosticResult<hir::Type> {
+        callee: &ast::Node,
+    ) -> DiagnosticResult<(hir::Signature, Option<hir::Type>)> {
+        use ast::RawNode;
+
+        if let RawNode::Path(path) = &callee.raw
+            && let [data_type, name] = &path[..]
+            && let Some(raw) = self.type_name(data_type)
+        {
+            let data_type = self.data_type(&ast::Type::new(raw, data_type.position))?;
+            let signature = self.associated(&data_type, name)?;
+            return Ok((signature, Some(data_type)));
+        }
+
+        let name = match &callee.raw {
+            RawNode::Identifier(name) => name.clone(),
+            RawNode::Path(path) => path.last().expect("Expected a non-empty path").raw.clone(),
+            raw => {
+                return DiagnosticData::error()
+                    .title(format!("Expected a function, got: {raw}"))
+                    .position(callee.position)
+                    .to_err();
+            }
+        };
+
+        let key = match (&self.names, self.definition(callee.position)) {
+            (_, Some(definition)) if definition.kind == ast::DefinitionKind::Function => {
+                (definition.path.clone(), definition.name.clone())
+            }
+            (_, Some(_)) => {
+                return DiagnosticData::error()
+                    .title(format!("'{name}' is not a function"))
+                    .position(callee.position)
+                    .to_err();
+            }
+            (None, None) if self.scopes.iter().any(|s| s.contains_key(&name)) => {
+                return DiagnosticData::error()
+                    .title(format!("'{name}' is not a function"))
+                    .position(callee.position)
+                    .to_err();
+            }
+            (None, None) if matches!(callee.raw, RawNode::Identifier(_)) => {
+                (self.relative_path.clone(), name.clone())
+            }
+            (_, None) => {
+                return DiagnosticData::error()
+                    .title(format!("'{name}' not found in this scope"))
+                    .position(callee.position)
+                    .to_err();
+            }
+        };
+
+        match self.functions.get(&key) {
+            Some(signature) => Ok((signature.clone(), None)),
+            None => DiagnosticData::error()
+                .title(format!("'{name}' not found in this scope"))
+                .position(callee.position)
+                .to_err(),
+        }
+    }
+    fn call(
+        &mut self,
+        callee: ast::Node,
+        arguments: Vec<ast::Node>,
+        expected: Option<&hir::Type>,
+        position: PositionRange,
+    ) -> DiagnosticResult<(hir::Node, hir::Type)> {
         use ast::RawNode;
 
-        let data_type = match &node.raw {
-            RawNode::Integer(_) => hir::Type::Int(32),
-            RawNode::Bool(_) => hir::Type::Boolean,
-            t => todo!("{t:#?}"),
+        if let RawNode::Path(path) = &callee.raw
+            && let [data_type, name] = &path[..]
+            && data_type.raw == "Box"
+            && name.raw == "new"
+        {
+            return self.boxed(arguments, expected, position);
+        }
+        if let RawNode::Path(path) = &callee.raw
+            && let [data_type, name] = &path[..]
+            && data_type.raw == "String"
+        {
+            return self.string_constructor(&name.raw, arguments, position);
+        }
+        if let RawNode::Path(path) = &callee.raw
+            && let [module, name] = &path[..]
+            && module.raw == hir::INTRINSICS
+        {
+            return self.intrinsic(&name.raw, arguments, position);
+        }
+
+        if let RawNode::Identifier(name) = &callee.raw
+            && let Some(variant) = hir::Variant::constructor(name)
+            && !self.in_scope(name)
+            && self.definition(callee.position).is_none()
+        {
+            return self.variant(variant, arguments, expected, position);
+        }
+
+        let value = match &callee.raw {
+            RawNode::Identifier(name) => self.in_scope(name),
+            RawNode::Path(_) | RawNode::TypeArguments(..) => false,
+            _ => true,
+        };
+        if value {
+            return self.invoke(callee, arguments, position);
+        }
+
+        let (callee, explicit) = match callee.raw {
+            RawNode::TypeArguments(callee, types) => (*callee, Some(types)),
+            raw => (Span::new(raw, callee.position), None),
+        };
+        let (signature, self_type) = self.callee(&callee)?;
+        let name = &signature.name;
+
+        if arguments.len() != signature.parameters.len() {
+            return DiagnosticData::error()
+                .title(format!(
+                    "Function '{name}' expects {} argument(s), got {}",
+                    signature.parameters.len(),
+                    arguments.len()
+                ))
+                .position(position)
+                .to_err();
+        }
+
+        let mut substitutions = HashMap::new();
+        if let Some(types) = explicit {
+            if types.len() != signature.generics.len() {
+                return DiagnosticData::error()
+                    .title(format!(
+                        "Function '{name}' expects {} type argument(s), got {}",
+                        signature.generics.len(),
+                        types.len()
+                    ))
+                    .position(callee.position)
+                    .to_err();
+            }
+            for (generic, data_type) in signature.generics.iter().zip(types) {
+                substitutions.insert(generic.name.clone(), self.data_type(&data_type)?);
+            }
+        }
+
+        if let (Some(expected), hir::Type::Parameter(generic)) = (expected, &signature.return_type)
+            && signature.is_generic(generic)
+            && !substitutions.contains_key(generic)
+        {
+            substitutions.insert(generic.clone(), expected.clone());
+        }
+
+        let (literals, others): (Vec<_>, Vec<_>) =
+            arguments
+                .into_iter()
+                .enumerate()
+                .partition(|(_, argument)| {
+                    matches!(argument.raw, RawNode::Integer(_) | RawNode::Float(_))
+                });
+
+        let mut values: Vec<Option<hir::Node>> = vec![None; signature.parameters.len()];
+        for (index, argument) in others.into_iter().chain(literals) {
+            let reference = &signature.parameters[index];
+            let parameter = reference.dereference();
+            let expected = match parameter {
+                hir::Type::Parameter(generic) if signature.is_generic(generic) => {
+                    substitutions.get(generic).cloned()
+                }
+                data_type => Some(data_type.clone()),
+            };
+
+            let position = argument.position;
+            let (value, found) = self.argument(argument, reference, expected.as_ref())?;
+            if let hir::Type::Parameter(generic) = parameter
+                && signature.is_generic(generic)
+                && !substitutions.contains_key(generic)
+            {
+                substitutions.insert(generic.clone(), found.clone());
+            }
+
+            self.expect_type(&parameter.substitute(&substitutions), &found, position)?;
+            values[index] = Some(value);
+        }
+
+        let mut generics = Vec::with_capacity(signature.generics.len());
+        for generic in &signature.generics {
+            let data_type = match substitutions.get(&generic.name) {
+                Some(data_type) => data_type,
+                None => {
+                    return DiagnosticData::error()
+                        .title(format!(
+                            "Cannot infer type parameter '{}' of '{name}', specify it with {name}::<...>",
+                            generic.name
+                        ))
+                        .position(position)
+                        .to_err();
+                }
+            };
+            if data_type.is_owned() {
+                return DiagnosticData::error()
+                    .title(format!(
+                        "Type '{data_type}' cannot be used for '{}' of '{name}', it is not copyable",
+                        generic.name
+                    ))
+                    .position(position)
+                    .to_err();
+            }
+            for bound in &generic.bounds {
+                if !self.implements(data_type, bound) {
+                    return DiagnosticData::error()
+                        .title(format!(
+                            "Type '{data_type}' does not implement trait '{bound}', required by '{}' of '{name}'",
+                            generic.name
+                        ))
+                        .position(position)
+                        .to_err();
+                }
+            }
+            generics.push(data_type.clone());
+        }
+
+        let return_type = signature.return_type.substitute(&substitutions);
+        let arguments = values.into_iter().flatten().collect();
+        let parameters = signature
+            .parameters
+            .iter()
+            .map(|p| p.substitute(&substitutions))
+            .collect();
+
+        let node = match self_type {
+            Some(self_type) => hir::Node::MethodCall {
+                trait_name: None,
+                self_type,
+                method: signature.name.clone(),
+                arguments,
+                parameters,
+                return_type: return_type.clone(),
+            },
+            None => hir::Node::Call {
+                function: signature.name.clone(),
+                symbol: signature.symbol.clone(),
+                generics,
+                arguments,
+                parameters,
+                return_type: return_type.clone(),
+            },
+        };
+        Ok((node, return_type))
+    }
+    pub fn argument(
+        &mut self,
+        argument: ast::Node,
+        parameter: &hir::Type,
+        expected: Option<&hir::Type>,
+    ) -> DiagnosticResult<(hir::Node, hir::Type)> {
+        if *parameter == hir::Type::Str {
+            return Ok((self.str_argument(argument)?, hir::Type::Str));
+        }
+        if !parameter.is_reference() {
+            return self.expression(argument, expected);
+        }
+
+        let ast::RawNode::Identifier(name) = argument.raw else {
+            return DiagnosticData::error()
+                .title(format!(
+                    "Cannot pass a temporary value as '{parameter}', bind it to a variable first"
+                ))
+                .position(argument.position)
+                .to_err();
+        };
+        let (name, data_type) = self.variable(&name, argument.position)?;
+        let node = hir::Node::Borrow {
+            name,
+            mutable: matches!(parameter, hir::Type::RefMut(_)),
+            position: argument.position,
         };
+        Ok((node, data_type))
+    }
+    pub fn expect_type(
+        &self,
+        expected: &hir::Type,
+        found: &hir::Type,
+        position: PositionRange,
+    ) -> DiagnosticResult {
+        if expected == found {
+            return Ok(());
+        }
+
+        let title = match expected.is_numeric() && found.is_numeric() {
+            true => {
+                format!("Expected type: {expected}, got: {found}, convert it with 'as {expected}'")
+            }
+            false => format!("Expected type: {expected}, got: {found}"),
+        };
+        DiagnosticData::error()
+            .title(title)
+            .position(position)
+            .to_err()
+    }
+    fn type_name(&self, name: &ast::Identifier) -> Option<ast::RawType> {
+        match name.raw.as_str() {
+            "Self" => Some(ast::RawType::SelfType),
+            _ if self
+                .definition(name.position)
+                .is_some_and(|d| d.kind == ast::DefinitionKind::Struct) =>
+            {
+                Some(ast::RawType::Other(vec![name.clone()]))
+            }
+            raw => ast::RawType::primitive(raw),
+        }
+    }
+    pub fn capture<T>(&mut self, result: DiagnosticResult<T>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(data) => {
+                self.error(data);
+                None
+            }
+        }
+    }
+    pub fn error(&mut self, data: DiagnosticData) {
+        self.diagnostics.push(data);
+    }
+}
 
-        Ok(data_type)
+pub fn is_extern(raw: &ast::RawNode) -> bool {
+    match raw {
+        ast::RawNode::Modifiers(modifiers, _) => modifiers
+            .iter()
+            .any(|m| matches!(m.raw, ast::RawModifier::Extern(_))),
+        _ => false,
     }
 }
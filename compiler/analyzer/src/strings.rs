@@ -0,0 +1,160 @@
+use common::position::PositionRange;
+use context::files::StdResolver;
+use diagnostics::{DiagnosticData, DiagnosticResult};
+use syntax::{ast, hir};
+
+use crate::Analyzer;
+
+impl Analyzer<'_> {
+    pub fn string_constructor(
+        &mut self,
+        name: &str,
+        arguments: Vec<ast::Node>,
+        position: PositionRange,
+    ) -> DiagnosticResult<(hir::Node, hir::Type)> {
+        let (builtin, parameters) = match name {
+            "new" => (hir::Builtin::StringNew, 0),
+            "from" => (hir::Builtin::StringFrom, 1),
+            _ => {
+                return DiagnosticData::error()
+                    .title(format!(
+                        "No associated function '{name}' found for type 'String'"
+                    ))
+                    .position(position)
+                    .to_err();
+            }
+        };
+        self.builtin(builtin, Vec::new(), arguments, parameters, position)
+    }
+    pub fn intrinsic(
+        &mut self,
+        name: &str,
+        arguments: Vec<ast::Node>,
+        position: PositionRange,
+    ) -> DiagnosticResult<(hir::Node, hir::Type)> {
+        if !StdResolver::contains(&self.relative_path) {
+            return DiagnosticData::error()
+                .title(format!(
+                    "Intrinsic '{}::{name}' can only be used in the standard library",
+                    hir::INTRINSICS
+                ))
+                .position(position)
+                .to_err();
+        }
+
+        let (builtin, parameters) = match hir::Builtin::intrinsic(name) {
+            Some(hir::Builtin::ReadLine) => (hir::Builtin::ReadLine, 0),
+            Some(builtin) => (builtin, 1),
+            None => {
+                return DiagnosticData::error()
+                    .title(format!("Unknown intrinsic '{}::{name}'", hir::INTRINSICS))
+                    .position(position)
+                    .to_err();
+            }
+        };
+        self.builtin(builtin, Vec::new(), arguments, parameters, position)
+    }
+    pub fn string_method(
+        &mut self,
+        (receiver, self_type): (hir::Node, hir::Type),
+        place: Option<String>,
+        receiver_position: PositionRange,
+        name: &str,
+        arguments: Vec<ast::Node>,
+        position: PositionRange,
+    ) -> DiagnosticResult<(hir::Node, hir::Type)> {
+        let (builtin, parameters) = match (&self_type, name) {
+            (hir::Type::String, "push") => (hir::Builtin::Push, 1),
+            (_, "concat") => (hir::Builtin::Concat, 1),
+            (_, "len") => (hir::Builtin::Length, 0),
+            _ => {
+                return DiagnosticData::error()
+                    .title(format!("No method '{name}' found for type '{self_type}'"))
+                    .position(position)
+                    .to_err();
+            }
+        };
+
+        let receiver = match (self_type, place) {
+            (hir::Type::Str, _) => receiver,
+            (_, Some(place)) => {
+                let borrow = hir::Node::Borrow {
+                    name: place,
+                    mutable: builtin == hir::Builtin::Push,
+                    position: receiver_position,
+                };
+                match builtin {
+                    hir::Builtin::Push => borrow,
+                    _ => hir::Node::AsStr(Box::new(borrow)),
+                }
+            }
+            (_, None) => {
+                return DiagnosticData::error()
+                    .title(format!(
+                        "Cannot call '{name}' on a temporary 'String', bind the value to a variable first"
+                    ))
+                    .position(receiver_position)
+                    .to_err();
+            }
+        };
+        self.builtin(builtin, vec![receiver], arguments, parameters, position)
+    }
+    pub fn str_argument(&mut self, argument: ast::Node) -> DiagnosticResult<hir::Node> {
+        let position = argument.position;
+        let (value, found) = match argument.raw {
+            ast::RawNode::Identifier(name) if self.in_scope(&name) => {
+                match self.variable(&name, position)? {
+                    (name, hir::Type::String) => {
+                        let borrow = hir::Node::Borrow {
+                            name,
+                            mutable: false,
+                            position,
+                        };
+                        (hir::Node::AsStr(Box::new(borrow)), hir::Type::Str)
+                    }
+                    _ => {
+                        let variable = ast::RawNode::Identifier(name);
+                        self.expression(ast::Node::new(variable, position), None)?
+                    }
+                }
+            }
+            raw => self.expression(ast::Node::new(raw, position), Some(&hir::Type::Str))?,
+        };
+
+        if found == hir::Type::String {
+            return DiagnosticData::error()
+                .title("Cannot use a temporary 'String' as 'str', bind it to a variable first")
+                .position(position)
+                .to_err();
+        }
+        self.expect_type(&hir::Type::Str, &found, position)?;
+        Ok(value)
+    }
+    fn builtin(
+        &mut self,
+        builtin: hir::Builtin,
+        mut values: Vec<hir::Node>,
+        arguments: Vec<ast::Node>,
+        parameters: usize,
+        position: PositionRange,
+    ) -> DiagnosticResult<(hir::Node, hir::Type)> {
+        if arguments.len() != parameters {
+            return DiagnosticData::error()
+                .title(format!(
+                    "Function '{builtin}' expects {parameters} argument(s), got {}",
+                    arguments.len()
+                ))
+                .position(position)
+                .to_err();
+        }
+
+        for argument in arguments {
+            values.push(self.str_argument(argument)?);
+        }
+        let node = hir::Node::Builtin {
+            builtin,
+            arguments: values,
+        };
+        Ok((node, builtin.return_type()))
+    }
+}
@@ -0,0 +1,254 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+};
+
+use common::position::PositionRange;
+use diagnostics::{DiagnosticData, DiagnosticResult};
+use syntax::{ast, format::join, hir};
+
+use crate::{Analyzer, StructTable};
+
+impl Analyzer<'_> {
+    pub fn struct_table(&mut self, nodes: &[ast::Node]) -> StructTable {
+        let structs = nodes
+            .iter()
+            .filter_map(|node| self.struct_declaration(node))
+            .collect();
+        StructTable {
+            structs,
+            diagnostics: std::mem::take(&mut self.diagnostics),
+        }
+    }
+    pub fn insert_structs(&mut self, structs: Vec<hir::Struct>) {
+        for structure in structs {
+            self.structs.insert(structure.symbol.clone(), structure);
+        }
+    }
+    pub fn struct_declaration(&mut self, node: &ast::Node) -> Option<hir::Struct> {
+        let ast::RawNode::Struct { name, fields } = node.raw.item() else {
+            return None;
+        };
+
+        if fields.is_empty() {
+            self.error(
+                DiagnosticData::error()
+                    .title(format!(
+                        "Struct '{}' must have at least one field",
+                        name.raw
+                    ))
+                    .position(name.position),
+            );
+            return None;
+        }
+
+        let mut valid = true;
+        let mut resolved: Vec<(String, hir::Type)> = Vec::with_capacity(fields.len());
+        for (field, data_type) in fields {
+            if resolved.iter().any(|(name, _)| *name == field.raw) {
+                self.error(
+                    DiagnosticData::error()
+                        .title(format!(
+                            "Field '{}' is defined multiple times in struct '{}'",
+                            field.raw, name.raw
+                        ))
+                        .position(field.position),
+                );
+                valid = false;
+                continue;
+            }
+
+            let result = self.data_type(data_type);
+            let Some(found) = self.capture(result) else {
+                valid = false;
+                continue;
+            };
+            if !found.is_numeric() && !matches!(found, hir::Type::Boolean | hir::Type::Struct(_)) {
+                self.error(
+                    DiagnosticData::error()
+                        .title(format!(
+                            "Field '{}' of struct '{}' cannot have type '{found}', fields must be numbers, booleans or structs",
+                            field.raw, name.raw
+                        ))
+                        .position(data_type.position),
+                );
+                valid = false;
+                continue;
+            }
+            resolved.push((field.raw.clone(), found));
+        }
+
+        valid.then(|| hir::Struct {
+            name: name.raw.clone(),
+            symbol: self.item_symbol(&self.relative_path, &name.raw),
+            path: self.relative_path.clone(),
+            fields: resolved,
+            position: name.position,
+        })
+    }
+    pub fn struct_type(&self, path: &[ast::Identifier]) -> DiagnosticResult<hir::Type> {
+        let name = path.last().expect("Expected a non-empty path");
+        match self.definition(name.position) {
+            Some(definition) if definition.kind == ast::DefinitionKind::Struct => Ok(
+                hir::Type::Struct(self.item_symbol(&definition.path, &definition.name)),
+            ),
+            Some(_) => DiagnosticData::error()
+                .title(format!("'{}' is not a type", name.raw))
+                .position(name.position)
+                .to_err(),
+            None => DiagnosticData::error()
+                .title(format!("'{}' not found in this scope", name.raw))
+                .position(name.position)
+                .to_err(),
+        }
+    }
+    pub fn construct(
+        &mut self,
+        name: ast::Identifier,
+        fields: Vec<(ast::Identifier, ast::Node)>,
+        position: PositionRange,
+    ) -> DiagnosticResult<(hir::Node, hir::Type)> {
+        let data_type = self.struct_type(std::slice::from_ref(&name))?;
+        let hir::Type::Struct(symbol) = &data_type else {
+            unreachable!()
+        };
+        let Some(structure) = self.structs.get(symbol).cloned() else {
+            return DiagnosticData::error()
+                .title(format!(
+                    "Cannot construct '{}', its declaration has errors",
+                    name.raw
+                ))
+                .position(name.position)
+                .to_err();
+        };
+
+        let mut values: Vec<(String, usize, hir::Node)> = Vec::with_capacity(fields.len());
+        for (field, value) in fields {
+            let Some((index, expected)) = structure.field(&field.raw) else {
+                return DiagnosticData::error()
+                    .title(format!(
+                        "Struct '{}' has no field '{}'",
+                        structure.name, field.raw
+                    ))
+                    .position(field.position)
+                    .to_err();
+            };
+            if values.iter().any(|(_, i, _)| *i == index) {
+                return DiagnosticData::error()
+                    .title(format!("Field '{}' is specified multiple times", field.raw))
+                    .position(field.position)
+                    .to_err();
+            }
+
+            let expected = expected.clone();
+            let position = value.position;
+            let (value, found) = self.expression(value, Some(&expected))?;
+            self.expect_type(&expected, &found, position)?;
+            values.push((field.raw, index, value));
+        }
+
+        let missing: Vec<String> = structure
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| !values.iter().any(|(_, i, _)| i == index))
+            .map(|(_, (name, _))| format!("'{name}'"))
+            .collect();
+        if !missing.is_empty() {
+            return DiagnosticData::error()
+                .title(format!(
+                    "Missing field(s) in initializer of struct '{}': {}",
+                    structure.name,
+                    join(missing, ", ")
+                ))
+                .position(position)
+                .to_err();
+        }
+
+        let node = hir::Node::Construct {
+            fields: values,
+            data_type: data_type.clone(),
+        };
+        Ok((node, data_type))
+    }
+    pub fn field(
+        &mut self,
+        receiver: ast::Node,
+        field: ast::Node,
+    ) -> DiagnosticResult<(hir::Node, hir::Type)> {
+        let ast::RawNode::Identifier(name) = field.raw else {
+            return DiagnosticData::error()
+                .title(format!("Expected a field name, got: {}", field.raw))
+                .position(field.position)
+                .to_err();
+        };
+
+        let (value, data_type) = self.expression(receiver, None)?;
+        let found = match data_type.dereference() {
+            hir::Type::Struct(symbol) => self
+                .structs
+                .get(symbol)
+                .and_then(|structure| structure.field(&name)),
+            _ => None,
+        };
+        let Some((index, field_type)) = found else {
+            return DiagnosticData::error()
+                .title(format!("No field '{name}' found for type '{data_type}'"))
+                .position(field.position)
+                .to_err();
+        };
+
+        let field_type = field_type.clone();
+        let node = hir::Node::Field {
+            value: Box::new(value),
+            field: name,
+            index,
+            data_type: field_type.clone(),
+        };
+        Ok((node, field_type))
+    }
+}
+
+pub fn recursive_structs(structs: &[hir::Struct]) -> Vec<(PathBuf, DiagnosticData)> {
+    let table: HashMap<&str, &hir::Struct> = structs
+        .iter()
+        .map(|structure| (structure.symbol.as_str(), structure))
+        .collect();
+
+    structs
+        .iter()
+        .filter_map(|structure| {
+            let field = contains(&table, &structure.symbol, structure, &mut HashSet::new())?;
+            let data = DiagnosticData::error()
+                .title(format!(
+                    "Struct '{}' contains itself through field '{field}', it would have an infinite size",
+                    structure.name
+                ))
+                .position(structure.position);
+            Some((structure.path.clone(), data))
+        })
+        .collect()
+}
+
+fn contains<'a>(
+    table: &HashMap<&str, &'a hir::Struct>,
+    target: &str,
+    structure: &'a hir::Struct,
+    visited: &mut HashSet<&'a str>,
+) -> Option<&'a str> {
+    for (name, data_type) in &structure.fields {
+        let hir::Type::Struct(symbol) = data_type else {
+            continue;
+        };
+        if symbol == target {
+            return Some(name);
+        }
+        if visited.insert(symbol)
+            && let Some(inner) = table.get(symbol.as_str())
+            && contains(table, target, inner, visited).is_some()
+        {
+            return Some(name);
+        }
+    }
+    None
+}
@@ -0,0 +1,70 @@
+use std::path::{Path, PathBuf};
+
+use common::metadata::METADATA_ABI;
+use syntax::ast;
+
+use crate::Analyzer;
+
+pub fn crate_prefixes(crates: &[ast::Crate]) -> Vec<(PathBuf, String)> {
+    crates
+        .iter()
+        .map(|krate| {
+            let directory = krate.root.parent().map(PathBuf::from).unwrap_or_default();
+            let name = match &krate.metadata {
+                Some(metadata) => &metadata.name,
+                None => &krate.name,
+            };
+            (directory, identifier(name))
+        })
+        .collect()
+}
+
+impl Analyzer<'_> {
+    pub fn symbol(&self, raw: &ast::RawNode, name: &str) -> String {
+        if abi(raw).is_some_and(|abi| abi != METADATA_ABI) {
+            return name.to_string();
+        }
+        if name == "main" && self.relative_path == self.compiler.root() {
+            return name.to_string();
+        }
+        self.item_symbol(&self.relative_path, name)
+    }
+    pub fn item_symbol(&self, relative_path: &Path, name: &str) -> String {
+        let Some((directory, prefix)) = self
+            .crates
+            .iter()
+            .filter(|(directory, _)| relative_path.starts_with(directory))
+            .max_by_key(|(directory, _)| directory.components().count())
+        else {
+            return name.to_string();
+        };
+
+        let mut symbol = prefix.clone();
+        let module = relative_path
+            .strip_prefix(directory)
+            .unwrap_or(relative_path);
+        for component in module.with_extension("").components() {
+            symbol.push('$');
+            symbol.push_str(&identifier(&component.as_os_str().to_string_lossy()));
+        }
+        symbol.push('$');
+        symbol.push_str(name);
+        symbol
+    }
+}
+
+fn abi(raw: &ast::RawNode) -> Option<&str> {
+    let ast::RawNode::Modifiers(modifiers, _) = raw else {
+        return None;
+    };
+    modifiers.iter().find_map(|modifier| match &modifier.raw {
+        ast::RawModifier::Extern(abi) => Some(abi.raw.as_str()),
+        _ => None,
+    })
+}
+
+fn identifier(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
@@ -0,0 +1,536 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use common::position::PositionRange;
+use diagnostics::{DiagnosticData, DiagnosticResult};
+use syntax::{ast, format::join, hir};
+
+use crate::{Analyzer, TraitTable, types::is_receiver};
+
+impl Analyzer<'_> {
+    pub fn trait_table(&mut self, nodes: &[ast::Node]) -> TraitTable {
+        use ast::RawNode;
+
+        let mut table = TraitTable::default();
+        for node in nodes {
+            match node.raw.item() {
+                RawNode::Trait { name, methods } => {
+                    self.self_type = Some(self_parameter());
+                    let signatures = methods.iter().filter_map(|m| self.signature(m)).collect();
+                    self.self_type = None;
+                    table.traits.push((name.raw.clone(), signatures));
+                }
+                RawNode::Impl {
+                    trait_name: Some(trait_name),
+                    data_type,
+                    ..
+                } => {
+                    if let (Ok(trait_name), Ok(data_type)) =
+                        (self.trait_name(trait_name), self.data_type(data_type))
+                    {
+                        table.impls.push((trait_name, data_type));
+                    }
+                }
+                RawNode::Impl {
+                    trait_name: None,
+                    data_type,
+                    methods,
+                } => {
+                    let Ok(data_type) = self.data_type(data_type) else {
+                        continue;
+                    };
+                    self.self_type = Some(data_type.clone());
+                    for signature in methods.iter().filter_map(|m| self.signature(m)) {
+                        table.methods.push((data_type.clone(), signature));
+                    }
+                    self.self_type = None;
+                }
+                _ => {}
+            }
+        }
+        table
+    }
+    pub fn insert_traits(&mut self, table: TraitTable) {
+        self.traits.extend(table.traits);
+        self.impls.extend(table.impls);
+        self.methods.extend(table.methods);
+    }
+    pub fn trait_name(&self, name: &ast::Identifier) -> DiagnosticResult<String> {
+        match self.definition(name.position) {
+            Some(definition) if definition.kind == ast::DefinitionKind::Trait => {
+                Ok(definition.name.clone())
+            }
+            Some(_) => DiagnosticData::error()
+                .title(format!("'{}' is not a trait", name.raw))
+                .position(name.position)
+                .to_err(),
+            None if self.names.is_none() || self.traits.contains_key(&name.raw) => {
+                Ok(name.raw.clone())
+            }
+            None => DiagnosticData::error()
+                .title(format!("'{}' not found in this scope", name.raw))
+                .position(name.position)
+                .to_err(),
+        }
+    }
+    pub fn implements(&self, data_type: &hir::Type, trait_name: &str) -> bool {
+        match data_type {
+            hir::Type::Parameter(name) => self
+                .generics
+                .iter()
+                .any(|g| g.name == *name && g.bounds.iter().any(|b| b == trait_name)),
+            data_type => self
+                .impls
+                .iter()
+                .any(|(t, d)| t == trait_name && d == data_type),
+        }
+    }
+    pub fn trait_declaration(&mut self, node: ast::Node) {
+        use ast::RawNode;
+
+        let RawNode::Trait { name, methods } = node.raw.item() else {
+            return;
+        };
+
+        let mut defined = HashSet::new();
+        self.self_type = Some(self_parameter());
+        for method in methods {
+            let RawNode::Function {
+                name: method,
+                generics,
+                parameters,
+                return_type,
+                node: body,
+            } = &method.raw
+            else {
+                continue;
+            };
+
+            if !defined.insert(method.raw.clone()) {
+                self.error(
+                    DiagnosticData::error()
+                        .title(format!(
+                            "Method '{}' is defined multiple times in trait '{}'",
+                            method.raw, name.raw
+                        ))
+                        .position(method.position),
+                );
+            }
+            if !generics.is_empty() {
+                self.error(
+                    DiagnosticData::error()
+                        .title(format!("Trait method '{}' cannot be generic", method.raw))
+                        .position(method.position),
+                );
+            }
+            if !matches!(&body.raw, RawNode::Block(nodes) if nodes.is_empty()) {
+                self.error(
+                    DiagnosticData::error()
+                        .title(format!("Trait method '{}' cannot have a body", method.raw))
+                        .position(body.position),
+                );
+            }
+            if !parameters.first().is_some_and(is_receiver) {
+                self.error(
+                    DiagnosticData::error()
+                        .title(format!(
+                            "Trait method '{}' must take 'self' as its first parameter",
+                            method.raw
+                        ))
+                        .position(method.position),
+                );
+            }
+
+            for data_type in parameters.iter().map(|p| &p.raw.data_type) {
+                let result = self.data_type(data_type);
+                self.capture(result);
+            }
+            let result = self.data_type(return_type);
+            self.capture(result);
+        }
+        self.self_type = None;
+    }
+    pub fn implementation(&mut self, node: ast::Node) -> Option<hir::Impl> {
+        use ast::RawNode;
+
+        let position = node.position;
+        let raw = match node.raw {
+            RawNode::Modifiers(_, node) => node.raw,
+            raw => raw,
+        };
+        let RawNode::Impl {
+            trait_name,
+            data_type,
+            methods,
+        } = raw
+        else {
+            return None;
+        };
+
+        let Some(trait_name) = trait_name else {
+            return self.inherent(data_type, methods);
+        };
+        let result = self.trait_name(&trait_name);
+        let trait_name = self.capture(result)?;
+        let result = self.data_type(&data_type);
+        let self_type = self.capture(result)?;
+
+        let key = (trait_name.clone(), self_type.clone());
+        if self.impls.iter().filter(|i| **i == key).count() > 1 {
+            self.error(
+                DiagnosticData::error()
+                    .title(format!(
+                        "Conflicting implementations of trait '{trait_name}' for type '{self_type}'"
+                    ))
+                    .position(data_type.position),
+            );
+        }
+
+        let required = self.traits.get(&trait_name).cloned().unwrap_or_default();
+        let types = HashMap::from([("Self".to_string(), self_type.clone())]);
+        let mut implemented = HashSet::new();
+        let mut functions = Vec::new();
+
+        self.self_type = Some(self_type.clone());
+        for method in methods {
+            let name = match method.raw.item() {
+                RawNode::Function { name, .. } => name.clone(),
+                raw => {
+                    self.error(
+                        DiagnosticData::error()
+                            .title(format!("Expected a method, got: {raw}"))
+                            .position(method.position),
+                    );
+                    continue;
+                }
+            };
+
+            let Some(expected) = required.iter().find(|s| s.name == name.raw) else {
+                self.error(
+                    DiagnosticData::error()
+                        .title(format!(
+                            "Method '{}' is not a member of trait '{trait_name}'",
+                            name.raw
+                        ))
+                        .position(name.position),
+                );
+                continue;
+            };
+            if !implemented.insert(name.raw.clone()) {
+                self.error(
+                    DiagnosticData::error()
+                        .title(format!(
+                            "Method '{}' is implemented multiple times",
+                            name.raw
+                        ))
+                        .position(name.position),
+                );
+                continue;
+            }
+
+            let expected = hir::Signature {
+                parameters: expected
+                    .parameters
+                    .iter()
+                    .map(|p| p.substitute(&types))
+                    .collect(),
+                return_type: expected.return_type.substitute(&types),
+                ..expected.clone()
+            };
+            let found = self.signature(&method);
+            if let Some(found) = found
+                && (found.parameters != expected.parameters
+                    || found.return_type != expected.return_type
+                    || !found.generics.is_empty())
+            {
+                self.error(
+                    DiagnosticData::error()
+                        .title(format!(
+                            "Method '{}' does not match trait '{trait_name}', expected: {expected}",
+                            name.raw
+                        ))
+                        .position(name.position),
+                );
+                continue;
+            }
+
+            functions.extend(self.item(method));
+        }
+        self.self_type = None;
+
+        let missing: Vec<String> = required
+            .iter()
+            .filter(|s| !implemented.contains(&s.name))
+            .map(|s| format!("'{}'", s.name))
+            .collect();
+        if !missing.is_empty() {
+            self.error(
+                DiagnosticData::error()
+                    .title(format!(
+                        "Not all methods of trait '{trait_name}' are implemented, missing: {}",
+                        join(missing, ", ")
+                    ))
+                    .position(position),
+            );
+        }
+
+        Some(hir::Impl {
+            trait_name: Some(trait_name),
+            self_type,
+            methods: functions,
+        })
+    }
+    fn inherent(&mut self, data_type: ast::Type, methods: Vec<ast::Node>) -> Option<hir::Impl> {
+        use ast::RawNode;
+
+        let result = self.data_type(&data_type);
+        let self_type = self.capture(result)?;
+
+        let mut local: HashMap<String, usize> = HashMap::new();
+        for method in &methods {
+            if let RawNode::Function { name, .. } = method.raw.item() {
+                *local.entry(name.raw.clone()).or_default() += 1;
+            }
+        }
+
+        let mut seen = HashSet::new();
+        let mut functions = Vec::new();
+        self.self_type = Some(self_type.clone());
+        for method in methods {
+            let (name, generics) = match method.raw.item() {
+                RawNode::Function { name, generics, .. } => (name.clone(), !generics.is_empty()),
+                raw => {
+                    self.error(
+                        DiagnosticData::error()
+                            .title(format!("Expected a method, got: {raw}"))
+                            .position(method.position),
+                    );
+                    continue;
+                }
+            };
+
+            let defined = self
+                .methods
+                .iter()
+                .filter(|(t, s)| *t == self_type && s.name == name.raw)
+                .count();
+            let elsewhere = defined > local[&name.raw];
+            if !seen.insert(name.raw.clone()) || elsewhere {
+                self.error(
+                    DiagnosticData::error()
+                        .title(format!(
+                            "Method '{}' is defined multiple times for type '{self_type}'",
+                            name.raw
+                        ))
+                        .position(name.position),
+                );
+            }
+            if generics {
+                self.error(
+                    DiagnosticData::error()
+                        .title(format!("Method '{}' cannot be generic", name.raw))
+                        .position(name.position),
+                );
+                continue;
+            }
+
+            functions.extend(self.item(method));
+        }
+        self.self_type = None;
+
+        Some(hir::Impl {
+            trait_name: None,
+            self_type,
+            methods: functions,
+        })
+    }
+    pub fn associated(
+        &self,
+        self_type: &hir::Type,
+        name: &ast::Identifier,
+    ) -> DiagnosticResult<hir::Signature> {
+        let signature = self
+            .methods
+            .iter()
+            .find(|(t, s)| t == self_type && s.name == name.raw);
+
+        match signature {
+            Some((_, signature)) if signature.receiver => DiagnosticData::error()
+                .title(format!(
+                    "'{}' is a method, call it as value.{}(...)",
+                    name.raw, name.raw
+                ))
+                .position(name.position)
+                .to_err(),
+            Some((_, signature)) => Ok(signature.clone()),
+            None => DiagnosticData::error()
+                .title(format!(
+                    "No associated function '{}' found for type '{self_type}'",
+                    name.raw
+                ))
+                .position(name.position)
+                .to_err(),
+        }
+    }
+    fn method(
+        &self,
+        self_type: &hir::Type,
+        name: &str,
+        position: PositionRange,
+    ) -> DiagnosticResult<(Option<String>, hir::Signature)> {
+        if let Some((_, signature)) = self
+            .methods
+            .iter()
+            .find(|(t, s)| t == self_type && s.name == name)
+        {
+            return Ok((None, signature.clone()));
+        }
+
+        let candidates: BTreeSet<&String> = match self_type {
+            hir::Type::Parameter(generic) => self
+                .generics
+                .iter()
+                .filter(|g| g.name == *generic)
+                .flat_map(|g| &g.bounds)
+                .collect(),
+            data_type => self
+                .impls
+                .iter()
+                .filter(|(_, d)| d == data_type)
+                .map(|(t, _)| t)
+                .collect(),
+        };
+
+        let mut found: Vec<(String, hir::Signature)> = candidates
+            .into_iter()
+            .filter_map(|trait_name| {
+                let signature = self
+                    .traits
+                    .get(trait_name)?
+                    .iter()
+                    .find(|m| m.name == name)?;
+                Some((trait_name.clone(), signature.clone()))
+            })
+            .collect();
+
+        match found.len() {
+            1 => {
+                let (trait_name, signature) = found.pop().unwrap();
+                Ok((Some(trait_name), signature))
+            }
+            0 => DiagnosticData::error()
+                .title(format!("No method '{name}' found for type '{self_type}'"))
+                .position(position)
+                .to_err(),
+            _ => DiagnosticData::error()
+                .title(format!(
+                    "Method '{name}' is ambiguous for type '{self_type}', it is provided by traits: {}",
+                    join(found.iter().map(|(t, _)| format!("'{t}'")), ", ")
+                ))
+                .position(position)
+                .to_err(),
+        }
+    }
+    pub fn method_call(
+        &mut self,
+        receiver: ast::Node,
+        method: ast::Node,
+        arguments: Vec<ast::Node>,
+        position: PositionRange,
+    ) -> DiagnosticResult<(hir::Node, hir::Type)> {
+        let name = match method.raw {
+            ast::RawNode::Identifier(name) => name,
+            raw => {
+                return DiagnosticData::error()
+                    .title(format!("Expected a method name, got: {raw}"))
+                    .position(method.position)
+                    .to_err();
+            }
+        };
+
+        let identifier = matches!(receiver.raw, ast::RawNode::Identifier(_));
+        let receiver_position = receiver.position;
+        let (receiver, self_type) = self.expression(receiver, None)?;
+        let place = match &receiver {
+            hir::Node::Variable { name, .. } if identifier => Some(name.clone()),
+            _ => None,
+        };
+        if matches!(self_type, hir::Type::Str | hir::Type::String) {
+            return self.string_method(
+                (receiver, self_type),
+                place,
+                receiver_position,
+                &name,
+                arguments,
+                position,
+            );
+        }
+        let (trait_name, signature) = self.method(&self_type, &name, method.position)?;
+        if !signature.receiver {
+            return DiagnosticData::error()
+                .title(format!(
+                    "'{name}' is an associated function, call it as {self_type}::{name}(...)"
+                ))
+                .position(method.position)
+                .to_err();
+        }
+
+        let types = HashMap::from([("Self".to_string(), self_type.clone())]);
+        let parameters: Vec<hir::Type> = signature
+            .parameters
+            .iter()
+            .map(|p| p.substitute(&types))
+            .collect();
+        if arguments.len() + 1 != parameters.len() {
+            return DiagnosticData::error()
+                .title(format!(
+                    "Method '{name}' expects {} argument(s), got {}",
+                    parameters.len() - 1,
+                    arguments.len()
+                ))
+                .position(position)
+                .to_err();
+        }
+
+        let receiver = match (&parameters[0], place) {
+            (hir::Type::Ref(_) | hir::Type::RefMut(_), Some(place)) => hir::Node::Borrow {
+                name: place,
+                mutable: matches!(parameters[0], hir::Type::RefMut(_)),
+                position: receiver_position,
+            },
+            (hir::Type::Ref(_) | hir::Type::RefMut(_), None) => {
+                return DiagnosticData::error()
+                    .title(format!(
+                        "Cannot call '{name}' on a temporary value, it takes '{}', bind the value to a variable first",
+                        parameters[0]
+                    ))
+                    .position(receiver_position)
+                    .to_err();
+            }
+            _ => receiver,
+        };
+
+        let mut values = vec![receiver];
+        for (argument, reference) in arguments.into_iter().zip(&parameters[1..]) {
+            let position = argument.position;
+            let parameter = reference.dereference();
+            let (value, found) = self.argument(argument, reference, Some(parameter))?;
+            self.expect_type(parameter, &found, position)?;
+            values.push(value);
+        }
+
+        let return_type = signature.return_type.substitute(&types);
+        let node = hir::Node::MethodCall {
+            trait_name,
+            self_type,
+            method: name,
+            arguments: values,
+            parameters,
+            return_type: return_type.clone(),
+        };
+        Ok((node, return_type))
+    }
+}
+
+fn self_parameter() -> hir::Type {
+    hir::Type::Parameter("Self".into())
+}
@@ -1,32 +1,116 @@
-use std::{collections::HashMap, path::PathBuf};
+use diagnostics::{DiagnosticData, DiagnosticResult};
+use syntax::{ast, hir};
 
-use syntax::ast::{Module, Parameter, Type};
+use crate::{Analyzer, semantic::is_extern};
 
-use crate::Analyzer;
+impl Analyzer<'_> {
+    pub fn signature(&mut self, node: &ast::Node) -> Option<hir::Signature> {
+        let ast::RawNode::Function {
+            name,
+            generics,
+            parameters,
+            return_type,
+            ..
+        } = node.raw.item()
+        else {
+            return None;
+        };
 
-#[derive(Debug)]
-pub struct FunctionType {
-    // generics: Vec<String>,
-    parameters: Vec<Parameter>,
-    return_type: Type,
-}
+        self.generics = generics
+            .iter()
+            .map(|g| hir::Generic {
+                name: g.raw.name.raw.clone(),
+                bounds: g
+                    .raw
+                    .bounds
+                    .iter()
+                    .filter_map(|b| self.trait_name(b).ok())
+                    .collect(),
+            })
+            .collect();
+        let receiver = parameters.first().is_some_and(is_receiver);
+        let parameters = parameters
+            .iter()
+            .map(|p| self.parameter_type(p))
+            .collect::<Result<Vec<_>, _>>()
+            .ok();
+        let return_type = self.data_type(return_type).ok();
+        let generics = std::mem::take(&mut self.generics);
 
-#[derive(Debug, Default)]
-pub struct ModuleTypes {
-    // pub types: HashMap<String, Type>,
-    pub functions: HashMap<String, FunctionType>,
-}
+        Some(hir::Signature {
+            name: name.raw.clone(),
+            symbol: self.symbol(&node.raw, &name.raw),
+            generics,
+            parameters: parameters?,
+            return_type: return_type?,
+            receiver,
+            external: is_extern(&node.raw),
+        })
+    }
 
-#[derive(Debug, Default)]
-pub struct Types {
-    pub modules: HashMap<PathBuf, ModuleTypes>,
-}
-impl Types {
-    pub fn new() -> Self {
-        Self::default()
+    pub fn parameter_type(&self, parameter: &ast::Parameter) -> DiagnosticResult<hir::Type> {
+        let data_type = Box::new(self.data_type(&parameter.raw.data_type)?);
+        Ok(match (&parameter.raw.reference, &parameter.raw.mutable) {
+            (Some(_), Some(_)) => hir::Type::RefMut(data_type),
+            (Some(_), None) => hir::Type::Ref(data_type),
+            (None, _) => *data_type,
+        })
+    }
+    pub fn is_generic(&self, name: &str) -> bool {
+        self.generics.iter().any(|g| g.name == name)
+    }
+    pub fn data_type(&self, data_type: &ast::Type) -> DiagnosticResult<hir::Type> {
+        use ast::RawType;
+
+        Ok(match &data_type.raw {
+            RawType::Void => hir::Type::Void,
+            RawType::Boolean => hir::Type::Boolean,
+            RawType::Int(bits) => hir::Type::Int(*bits),
+            RawType::UInt(bits) => hir::Type::UInt(*bits),
+            RawType::Float32 => hir::Type::Float(32),
+            RawType::Float64 => hir::Type::Float(64),
+            RawType::USize => hir::Type::USize,
+            RawType::ISize => hir::Type::ISize,
+            RawType::String => hir::Type::Str,
+            RawType::Other(path) if path.len() == 1 && self.is_generic(&path[0].raw) => {
+                hir::Type::Parameter(path[0].raw.clone())
+            }
+            RawType::Other(path) if path.len() == 1 && path[0].raw == "String" => hir::Type::String,
+            RawType::Other(path) => self.struct_type(path)?,
+            RawType::Box(data_type) => hir::Type::Box(Box::new(self.data_type(data_type)?)),
+            RawType::Result(ok, err) => hir::Type::Result(
+                Box::new(self.payload_type(ok)?),
+                Box::new(self.payload_type(err)?),
+            ),
+            RawType::Function {
+                parameters,
+                return_type,
+            } => hir::Type::Function {
+                parameters: parameters
+                    .iter()
+                    .map(|p| self.data_type(p))
+                    .collect::<Result<_, _>>()?,
+                return_type: Box::new(self.data_type(return_type)?),
+            },
+            RawType::SelfType => match &self.self_type {
+                Some(data_type) => data_type.clone(),
+                None => {
+                    return DiagnosticData::error()
+                        .title("'Self' is only available in traits and impls")
+                        .position(data_type.position)
+                        .to_err();
+                }
+            },
+            raw => {
+                return DiagnosticData::error()
+                    .title(format!("Unsupported type: {raw}"))
+                    .position(data_type.position)
+                    .to_err();
+            }
+        })
     }
 }
 
-impl Analyzer<'_> {
-    pub fn get_types(&self, module: &Module) {}
+pub fn is_receiver(parameter: &ast::Parameter) -> bool {
+    parameter.raw.name.raw == "self" && parameter.raw.data_type.raw == ast::RawType::SelfType
 }
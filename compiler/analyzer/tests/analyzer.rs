@@ -6,7 +6,7 @@ mod tests {
     use analyzer::analyze;
     use common::constants::FILE_EXTENSION;
     use context::{CompilerCtx, files::MockResolver};
-    use resolver::resolve_modules;
+    use resolver::{resolve_modules, resolve_names};
 
     macro_rules! success_test {
         ($name:ident, $input:expr) => {
@@ -37,7 +37,8 @@ mod tests {
         compiler.write(&main_path, input);
 
         let entry = main_path;
-        let collection = resolve_modules(&mut compiler, &entry);
+        let mut collection = resolve_modules(&mut compiler, &entry);
+        resolve_names(&mut compiler, &mut collection);
         let hlir = analyze(&mut compiler, collection);
 
         compiler
@@ -67,13 +68,315 @@ mod tests {
     success_test!(main_function_return, "func main() { return }");
     success_test!(
         main_function_return_with_expresion,
-        "func main() { return 0 }"
+        "func main() i32 { return 0 }"
     );
     success_test!(type_inheritance_bool, "func main() { var x = false }");
     success_test!(variable_type_boolean, "func main() { var x: bool = false }");
     success_test!(variable_type_integer, "func main() { var x: i32 = 42 }");
+    success_test!(
+        extern_declaration,
+        "extern \"eclipse\" func seven() i32 {}\nfunc main() {}"
+    );
+
+    success_test!(
+        generic_function,
+        "func id<T>(value T) T { return value }\nfunc main() { var x = id(1) var y: i64 = id(2) }"
+    );
+    success_test!(
+        explicit_type_arguments,
+        "func id<T>(value T) T { return value }\nfunc main() { var x = id::<bool>(true) }"
+    );
+    success_test!(
+        generic_calls_generic,
+        "func id<T>(value T) T { return value }\nfunc twice<T>(value T) T { return id(id(value)) }"
+    );
 
     failed_test!(wrong_return_type, "func main() bool { return 0 }");
+    failed_test!(
+        generic_body_is_opaque,
+        "func f<T>(value T) i32 { return value }"
+    );
+    failed_test!(generic_literal, "func f<T>() T { return 1 }");
+    failed_test!(
+        type_argument_mismatch,
+        "func id<T>(value T) T { return value }\nfunc main() { var x = id::<bool>(1) }"
+    );
+    failed_test!(
+        wrong_type_argument_count,
+        "func id<T>(value T) T { return value }\nfunc main() { var x = id::<i32, bool>(1) }"
+    );
+    failed_test!(
+        inconsistent_inference,
+        "func first<T>(a T, b T) T { return a }\nfunc main() { var x = first(true, 1) }"
+    );
+    failed_test!(
+        uninferred_type_parameter,
+        "func none<T>(a i32) {}\nfunc main() { none(1) }"
+    );
+    failed_test!(duplicate_type_parameter, "func f<T, T>() {}");
+    failed_test!(
+        wrong_argument_count,
+        "func f(a i32) {}\nfunc main() { f(1, 2) }"
+    );
+    failed_test!(extern_generic, "extern \"eclipse\" func f<T>(a T) {}");
+
+    success_test!(
+        trait_method_call,
+        "trait Show { func show(self) i32 }\nimpl Show for bool { func show(self) i32 { return 1 } }\nfunc main() { var x = true.show() }"
+    );
+    success_test!(
+        trait_bound,
+        "trait Show { func show(self) i32 }\nimpl Show for i32 { func show(self) i32 { return self } }\nfunc f<T: Show>(value T) i32 { return value.show() }\nfunc main() { var x = f(1) }"
+    );
+    success_test!(
+        self_type_in_trait,
+        "trait Pick { func pick(self, other Self) Self }\nimpl Pick for i64 { func pick(self, other Self) i64 { return other } }\nfunc main() { var x: i64 = 1 var y = x.pick(2) }"
+    );
+    failed_test!(
+        missing_trait_method,
+        "trait Show { func show(self) i32 }\nimpl Show for i32 {}"
+    );
+    failed_test!(
+        mismatched_trait_method,
+        "trait Show { func show(self) i32 }\nimpl Show for i32 { func show(self) bool { return true } }"
+    );
+    failed_test!(
+        unknown_trait_method,
+        "trait Show { func show(self) i32 }\nimpl Show for i32 { func show(self) i32 { return 1 } func other(self) {} }"
+    );
+    failed_test!(
+        conflicting_impls,
+        "trait Show { func show(self) i32 }\nimpl Show for i32 { func show(self) i32 { return 1 } }\nimpl Show for i32 { func show(self) i32 { return 2 } }"
+    );
+    failed_test!(
+        unsatisfied_bound,
+        "trait Show { func show(self) i32 }\nfunc f<T: Show>(value T) {}\nfunc main() { f(true) }"
+    );
+    failed_test!(
+        method_without_bound,
+        "trait Show { func show(self) i32 }\nfunc f<T>(value T) i32 { return value.show() }"
+    );
+    failed_test!(
+        ambiguous_method,
+        "trait A { func show(self) i32 }\ntrait B { func show(self) i32 }\nimpl A for i32 { func show(self) i32 { return 1 } }\nimpl B for i32 { func show(self) i32 { return 2 } }\nfunc main() { var x = 1 var y = x.show() }"
+    );
+    failed_test!(
+        bound_is_not_a_trait,
+        "func main() {}\nfunc f<T: main>(value T) {}"
+    );
+    failed_test!(
+        trait_method_body,
+        "trait Show { func show(self) i32 { return 1 } }"
+    );
+    failed_test!(trait_method_without_self, "trait Show { func show() i32 }");
+    failed_test!(self_outside_impl, "func f(value Self) {}");
+    success_test!(
+        associated_function,
+        "impl i32 { func zero() Self { return 0 } func one() i32 { return Self::zero() } }\nfunc main() { var x = i32::one() }"
+    );
+    success_test!(
+        inherent_method,
+        "impl i32 { func get(&self) i32 { return self } func set(&mut self, v i32) { self = v } }\nfunc main() { var mut x = 1 x.set(2) var y = x.get() }"
+    );
+    success_test!(
+        reference_parameter,
+        "func f(&value i32) i32 { return value }\nfunc main() { var x = 1 var y = f(x) }"
+    );
+    failed_test!(
+        method_called_as_associated,
+        "impl i32 { func get(&self) i32 { return self } }\nfunc main() { var x = 1 var y = i32::get(x) }"
+    );
+    failed_test!(
+        associated_called_as_method,
+        "impl i32 { func zero() i32 { return 0 } }\nfunc main() { var x = 1 var y = x.zero() }"
+    );
+    failed_test!(
+        unknown_associated_function,
+        "func main() { var x = i32::zero() }"
+    );
+    failed_test!(
+        duplicate_inherent_method,
+        "impl i32 { func get(self) i32 { return self } }\nimpl i32 { func get(self) i32 { return 0 } }"
+    );
+    failed_test!(
+        generic_inherent_method,
+        "impl i32 { func get<T>(self, value T) i32 { return self } }"
+    );
+    failed_test!(
+        temporary_receiver,
+        "impl i32 { func get(&self) i32 { return self } }\nfunc main() { var x = (1).get() }"
+    );
+    failed_test!(
+        temporary_reference_argument,
+        "func f(&value i32) {}\nfunc main() { f(1) }"
+    );
+    success_test!(
+        closure_with_expected_type,
+        "func apply(f func(i32) i32) i32 { return f(1) }\nfunc main() i32 { var y = 2 return apply(|x| x + y) }"
+    );
+    success_test!(
+        function_value,
+        "func one(x i32) i32 { return 1 }\nfunc main() i32 { var f = one return f(2) }"
+    );
+    success_test!(
+        binary_operations,
+        "func main() bool { var x = 1 + 2 * 3 return x % 2 != 0 }"
+    );
+    success_test!(
+        closure_with_block_body,
+        "func main() { var mut x = 1 var f = |y i32| { x = y } f(2) }"
+    );
+    success_test!(
+        boxed_value,
+        "func main() i32 { var b: Box<i32> = Box::new(1) return *b + 1 }"
+    );
+    success_test!(
+        nested_box,
+        "func f(b Box<Box<i32>>) i32 { return **b }\nfunc main() { var x = f(Box::new(Box::new(1))) }"
+    );
+    failed_test!(deref_non_box, "func main() { var x = 1 var y = *x }");
+    failed_test!(deref_temporary, "func main() { var y = *Box::new(1) }");
+    failed_test!(
+        box_type_mismatch,
+        "func main() { var b: Box<bool> = Box::new(1) }"
+    );
+    failed_test!(box_arguments, "func main() { var b = Box::new(1, 2) }");
+    success_test!(
+        string_builtins,
+        "func greet(name str) String { var mut s = String::from(\"hi \") s.push(name) return s }\nfunc main() { var s = greet(\"you\") println(s) print(\"!\") var n = s.len() + \"ab\".len() }"
+    );
+    success_test!(
+        string_concat,
+        "func main() { var a = String::new() var b = a.concat(\"x\") var c = \"y\".concat(b) }"
+    );
+    failed_test!(string_to_str_return, "func f(s String) str { return s }");
+    failed_test!(print_integer, "func main() { println(1) }");
+    failed_test!(print_arguments, "func main() { print(\"a\", \"b\") }");
+    failed_test!(push_on_str, "func main() { var s = \"a\" s.push(\"b\") }");
+    failed_test!(
+        unknown_string_method,
+        "func main() { var s = String::new() s.trim() }"
+    );
+    failed_test!(
+        temporary_string_receiver,
+        "func main() { var n = String::new().len() }"
+    );
+    failed_test!(
+        temporary_string_argument,
+        "func main() { println(String::new()) }"
+    );
+    failed_test!(
+        unknown_string_function,
+        "func main() { var s = String::with(\"a\") }"
+    );
+    success_test!(
+        result_variants,
+        "func f(a i32) result<i32, bool> { var r: result<i32, bool> = Err(true) return Ok(a) }\nfunc main() { var x = f(1) }"
+    );
+    success_test!(
+        result_propagation,
+        "func f() result<i32, bool> { return Ok(1) }\nfunc g() result<bool, bool> { var a = f()? f()? return Ok(a == 1) }"
+    );
+    success_test!(
+        shadowed_variant,
+        "func Ok(a i32) i32 { return a }\nfunc main() i32 { return Ok(1) }"
+    );
+    failed_test!(uninferred_variant, "func main() { var x = Ok(1) }");
+    failed_test!(
+        wrong_variant_payload,
+        "func f() result<i32, bool> { return Err(1) }"
+    );
+    success_test!(
+        owned_result_payload,
+        "func f() result<String, bool> { return Err(true) }"
+    );
+    failed_test!(void_result_payload, "func f(a result<void, bool>) {}");
+    failed_test!(
+        propagate_non_result,
+        "func f() result<i32, bool> { var a = 1? return Ok(a) }"
+    );
+    failed_test!(
+        propagate_outside_result,
+        "func f() result<i32, bool> { return Ok(1) }\nfunc main() { var a = f()? }"
+    );
+    failed_test!(
+        propagate_mismatched_error,
+        "func f() result<i32, bool> { return Ok(1) }\nfunc g() result<i32, i32> { var a = f()? return Ok(a) }"
+    );
+    failed_test!(
+        uninferred_closure_parameter,
+        "func main() { var f = |x| x }"
+    );
+    failed_test!(
+        generic_function_value,
+        "func id<T>(value T) T { return value }\nfunc main() { var f = id }"
+    );
+    failed_test!(invoke_non_function, "func main() { var x = 1 x(2) }");
+    failed_test!(
+        invoke_wrong_arguments,
+        "func main() { var f = |x i32| x f() }"
+    );
+    failed_test!(operator_on_bool, "func main() { var x = true + 1 }");
+    failed_test!(
+        mismatched_operands,
+        "func main() { var x: i32 = 1 var y: i64 = 2 var z = x + y }"
+    );
+    success_test!(
+        numeric_casts,
+        "func f(a u8, b f32) i64 { var c = a as i64 + b as i64 return c * (true as i64) }"
+    );
+    success_test!(
+        float_arithmetic,
+        "func f(a f64) bool { var b: f32 = 1.5 return a * 2.0 > b as f64 }"
+    );
+    failed_test!(
+        implicit_widening,
+        "func main() { var x: i32 = 1 var y: i64 = x }"
+    );
+    failed_test!(
+        implicit_float_conversion,
+        "func main() { var x: f64 = 1.5 var y = x + 1 }"
+    );
+    failed_test!(cast_to_bool, "func main() { var x = 1 as bool }");
+    failed_test!(cast_string, "func main() { var x = \"1\" as i32 }");
+    failed_test!(remainder_on_float, "func main() { var x = 1.5 % 2.0 }");
+
+    #[test]
+    fn conversion_hint() {
+        let compiler = init("func main() { var x: u32 = 1 var y: u64 = x }");
+        let rendered = compiler.diagnostics.render();
+        assert!(
+            rendered.contains("Expected type: u64, got: u32, convert it with 'as u64'"),
+            "{rendered}"
+        );
+    }
+
+    #[test]
+    fn lossy_constant_casts() {
+        let compiler = init(
+            "func main() {
+    var a = 300 as u8
+    var b = 2.5 as i32
+    var c = 70000.0 as u16
+    var d = 16777217 as f32
+    var e = 127 as i8
+    var f = (255) as u8
+}",
+        );
+        assert!(!compiler.diagnostics.has_errors());
+
+        let rendered = compiler.diagnostics.render();
+        for warning in [
+            "Lossy cast of constant '300' to 'u8', the value becomes '44'",
+            "Lossy cast of constant '2.5' to 'i32', the value becomes '2'",
+            "Lossy cast of constant '70000.0' to 'u16', the value is out of range",
+            "Lossy cast of constant '16777217' to 'f32', the value becomes '16777216'",
+        ] {
+            assert!(rendered.contains(warning), "{rendered}");
+        }
+        assert_eq!(rendered.matches("Warning").count(), 4, "{rendered}");
+    }
+
     failed_test!(
         wrong_return_type_missing_expression,
         "func main() bool { return }"
@@ -81,4 +384,8 @@ mod tests {
     failed_test!(void_return_type_expression, "func main() { return 0 }");
     failed_test!(wrong_type, "func main() { var x: i32 = false }");
     failed_test!(function_as_expression, "func main() func test()");
+    failed_test!(
+        extern_with_body,
+        "extern \"eclipse\" func seven() i32 { return 7 }"
+    );
 }
@@ -1,7 +1,446 @@
+use std::collections::{HashMap, HashSet};
+
+use common::position::PositionRange;
 use context::CompilerCtx;
+use diagnostics::DiagnosticData;
 use syntax::hir;
 
-pub fn borrow_check(compiler: &mut CompilerCtx, mut collection: hir::ModuleCollection) -> hir::ModuleCollection {
-    
+#[derive(Debug, Clone, Copy)]
+enum Binding {
+    Local { mutable: bool },
+    Parameter { mutable: bool },
+    Reference { mutable: bool },
+}
+
+#[derive(Debug, Clone)]
+struct Environment {
+    capture: String,
+    moves: bool,
+    position: PositionRange,
+}
+
+#[derive(Default)]
+struct BorrowChecker {
+    scopes: Vec<HashMap<String, Binding>>,
+    environments: HashMap<String, Environment>,
+    moved: HashSet<String>,
+    captured: HashSet<String>,
+    diagnostics: Vec<DiagnosticData>,
+}
+
+pub fn borrow_check(
+    compiler: &mut CompilerCtx,
+    collection: hir::ModuleCollection,
+) -> hir::ModuleCollection {
+    for module in &collection.modules {
+        let methods = module.impls.iter().flat_map(|i| &i.methods);
+
+        let mut checker = BorrowChecker::default();
+        for function in module.functions.iter().chain(methods) {
+            checker.function(function);
+        }
+        for data in checker.diagnostics {
+            compiler.diagnostics.insert(&module.path, data);
+        }
+    }
+
     collection
 }
+
+impl BorrowChecker {
+    fn binding(&self, name: &str) -> Option<Binding> {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.get(name).copied())
+    }
+    fn function(&mut self, function: &hir::Function) {
+        let parameters = function
+            .parameters
+            .iter()
+            .map(|parameter| {
+                let binding = match parameter.data_type {
+                    hir::Type::Ref(_) => Binding::Reference { mutable: false },
+                    hir::Type::RefMut(_) => Binding::Reference { mutable: true },
+                    _ => Binding::Parameter {
+                        mutable: parameter.mutable,
+                    },
+                };
+                (parameter.name.clone(), binding)
+            })
+            .collect();
+
+        self.scopes = vec![parameters];
+        self.block(&function.body);
+        self.scopes.clear();
+        self.environments.clear();
+        self.moved.clear();
+    }
+    fn block(&mut self, nodes: &[hir::Node]) {
+        self.scopes.push(HashMap::new());
+        for node in nodes {
+            self.node(node);
+        }
+        self.scopes.pop();
+    }
+    fn node(&mut self, node: &hir::Node) {
+        use hir::Node;
+
+        match node {
+            Node::Block(nodes) => self.block(nodes),
+            Node::DeclareVariable {
+                name,
+                mutable,
+                value,
+                ..
+            } => {
+                self.node(value);
+                self.bind_environment(name, value);
+                self.moved.remove(name);
+                self.captured.remove(name);
+                if let Some(scope) = self.scopes.last_mut() {
+                    scope.insert(name.clone(), Binding::Local { mutable: *mutable });
+                }
+            }
+            Node::Assign {
+                name,
+                value,
+                position,
+                ..
+            } => {
+                self.node(value);
+                self.bind_environment(name, value);
+                self.moved.remove(name);
+
+                let shown = source(name);
+                let title = match self.binding(name) {
+                    Some(Binding::Local { mutable: false }) => {
+                        format!("Cannot assign twice to immutable variable '{shown}'")
+                    }
+                    Some(Binding::Parameter { mutable: false }) => {
+                        format!("Cannot assign to immutable parameter '{shown}'")
+                    }
+                    Some(Binding::Reference { mutable: false }) => {
+                        format!("Cannot assign to '{shown}', which is behind a '&' reference")
+                    }
+                    _ => return,
+                };
+                self.diagnostics
+                    .push(DiagnosticData::error().title(title).position(*position));
+            }
+            Node::Borrow { name, position, .. } if self.moved.contains(name) => {
+                self.diagnostics.push(
+                    DiagnosticData::error()
+                        .title(format!("Borrow of moved value '{}'", source(name)))
+                        .position(*position),
+                );
+            }
+            Node::Borrow {
+                name,
+                mutable: true,
+                position,
+            } => {
+                let shown = source(name);
+                let title = match self.binding(name) {
+                    Some(
+                        Binding::Local { mutable: false } | Binding::Parameter { mutable: false },
+                    ) => {
+                        format!(
+                            "Cannot borrow '{shown}' as mutable, as it is not declared as mutable"
+                        )
+                    }
+                    Some(Binding::Reference { mutable: false }) => {
+                        format!(
+                            "Cannot borrow '{shown}' as mutable, as it is behind a '&' reference"
+                        )
+                    }
+                    _ => return,
+                };
+                self.diagnostics
+                    .push(DiagnosticData::error().title(title).position(*position));
+            }
+            Node::Return(Some(value)) => {
+                self.node(value);
+                self.escape(value);
+            }
+            Node::Call { arguments, .. }
+            | Node::MethodCall { arguments, .. }
+            | Node::Builtin { arguments, .. } => {
+                self.arguments(arguments);
+                for argument in arguments {
+                    self.node(argument);
+                }
+            }
+            Node::Invoke {
+                callee, arguments, ..
+            } => {
+                match callee.as_ref() {
+                    Node::Deref(value, _)
+                        if matches!(**value, Node::Variable { .. } | Node::Deref(..)) =>
+                    {
+                        self.used(value)
+                    }
+                    Node::Deref(value, _) => self.node(value),
+                    callee => self.node(callee),
+                }
+                self.arguments(arguments);
+                for argument in arguments {
+                    self.node(argument);
+                }
+            }
+            Node::Binary { left, right, .. } => {
+                self.node(left);
+                self.node(right);
+            }
+            Node::Closure { .. } => self.closure(node, false),
+            Node::Variable {
+                name,
+                data_type,
+                position,
+            } if data_type.is_owned() => self.move_out(name, *position),
+            Node::Deref(value, data_type) => {
+                let (_, position) = self.place(value);
+                if data_type.is_owned() || matches!(data_type, hir::Type::Function { .. }) {
+                    self.diagnostics.push(
+                        DiagnosticData::error()
+                            .title(format!(
+                                "Cannot move out of a box, '*{value}' has type '{data_type}'"
+                            ))
+                            .position(position),
+                    );
+                }
+                self.used(value);
+            }
+            Node::Construct { fields, .. } => {
+                for (_, _, value) in fields {
+                    self.node(value);
+                }
+            }
+            Node::Boxed { value, .. } if matches!(**value, Node::Closure { .. }) => {
+                self.closure(value, true)
+            }
+            Node::Boxed { value, .. }
+            | Node::Field { value, .. }
+            | Node::AsStr(value)
+            | Node::Variant { value, .. }
+            | Node::Try { value, .. }
+            | Node::Cast { value, .. } => self.node(value),
+            Node::Return(None)
+            | Node::Function { .. }
+            | Node::Borrow { .. }
+            | Node::Variable { .. }
+            | Node::Integer(_)
+            | Node::Float(_)
+            | Node::Boolean(_)
+            | Node::String(_) => {}
+        }
+    }
+    fn closure(&mut self, closure: &hir::Node, boxed: bool) {
+        let hir::Node::Closure {
+            moves,
+            parameters,
+            captures,
+            body,
+            position,
+            ..
+        } = closure
+        else {
+            panic!("Expected a closure, got {closure}");
+        };
+        let (moves, position) = (*moves, *position);
+
+        for capture in captures {
+            let title = match self.moved.contains(&capture.name) {
+                true => format!("Borrow of moved value '{}'", source(&capture.name)),
+                false if moves && boxed && capture.data_type.is_owned() => {
+                    self.move_out(&capture.name, position);
+                    continue;
+                }
+                false if moves && capture.data_type.is_owned() => format!(
+                    "Cannot move '{}' of type '{}' into a closure, box the closure to take ownership",
+                    source(&capture.name),
+                    capture.data_type
+                ),
+                false => continue,
+            };
+            self.diagnostics
+                .push(DiagnosticData::error().title(title).position(position));
+        }
+
+        let mut scope: HashMap<String, Binding> = captures
+            .iter()
+            .filter_map(|c| Some((c.name.clone(), self.binding(&c.name)?)))
+            .collect();
+        for parameter in parameters {
+            let binding = Binding::Parameter {
+                mutable: parameter.mutable,
+            };
+            scope.insert(parameter.name.clone(), binding);
+        }
+
+        let environments = std::mem::take(&mut self.environments);
+        let moved = std::mem::take(&mut self.moved);
+        let captured = std::mem::replace(
+            &mut self.captured,
+            captures.iter().map(|c| c.name.clone()).collect(),
+        );
+        self.scopes.push(scope);
+        self.block(body);
+        self.scopes.pop();
+        self.environments = environments;
+        self.moved = moved;
+        self.captured = captured;
+    }
+    fn move_out(&mut self, name: &str, position: PositionRange) {
+        let shown = source(name);
+        let title = match self.binding(name) {
+            _ if self.captured.contains(name) => {
+                format!("Cannot move '{shown}' out of the environment of a closure")
+            }
+            Some(Binding::Reference { .. }) => {
+                format!("Cannot move out of '{shown}', which is behind a reference")
+            }
+            _ if !self.moved.insert(name.to_string()) => format!("Use of moved value '{shown}'"),
+            _ => return,
+        };
+        self.diagnostics
+            .push(DiagnosticData::error().title(title).position(position));
+    }
+    fn used(&mut self, value: &hir::Node) {
+        let (name, position) = self.place(value);
+        if self.moved.contains(&name) {
+            self.diagnostics.push(
+                DiagnosticData::error()
+                    .title(format!("Use of moved value '{}'", source(&name)))
+                    .position(position),
+            );
+        }
+    }
+    fn place(&self, value: &hir::Node) -> (String, PositionRange) {
+        match value {
+            hir::Node::Variable { name, position, .. } => (name.clone(), *position),
+            hir::Node::Deref(value, _) => self.place(value),
+            value => panic!("Expected a place expression, got {value}"),
+        }
+    }
+    fn bind_environment(&mut self, name: &str, value: &hir::Node) {
+        match value {
+            hir::Node::Boxed { value, .. } if !is_move_closure(value) => {
+                self.bind_environment(name, value)
+            }
+            hir::Node::Closure {
+                moves,
+                captures,
+                position,
+                ..
+            } if !captures.is_empty() => {
+                let environment = Environment {
+                    capture: captures[0].name.clone(),
+                    moves: *moves,
+                    position: *position,
+                };
+                self.environments.insert(name.to_string(), environment);
+            }
+            hir::Node::Variable { name: other, .. } if self.environments.contains_key(other) => {
+                let environment = self.environments[other].clone();
+                self.environments.insert(name.to_string(), environment);
+            }
+            _ => {
+                self.environments.remove(name);
+            }
+        }
+    }
+    fn escape(&mut self, value: &hir::Node) {
+        let environment = match value {
+            hir::Node::Boxed { value, .. } if !is_move_closure(value) => {
+                return self.escape(value);
+            }
+            hir::Node::Closure {
+                moves,
+                captures,
+                position,
+                ..
+            } if !captures.is_empty() => Environment {
+                capture: captures[0].name.clone(),
+                moves: *moves,
+                position: *position,
+            },
+            hir::Node::Variable { name, .. } => match self.environments.get(name) {
+                Some(environment) => environment.clone(),
+                None => return,
+            },
+            _ => return,
+        };
+
+        let Environment {
+            capture,
+            moves,
+            position,
+        } = environment;
+        let capture = source(&capture);
+        let title = match moves {
+            true => format!(
+                "Cannot return a closure that captures '{capture}', its environment is stored in the current function"
+            ),
+            false => format!(
+                "Closure may outlive the current function, but it borrows '{capture}', which is owned by the current function"
+            ),
+        };
+        self.diagnostics
+            .push(DiagnosticData::error().title(title).position(position));
+    }
+    fn arguments(&mut self, arguments: &[hir::Node]) {
+        let mut borrows: HashMap<&String, bool> = HashMap::new();
+
+        let places = arguments.iter().flat_map(|argument| match argument {
+            hir::Node::AsStr(value) => match value.as_ref() {
+                hir::Node::Borrow { name, position, .. } => vec![(name, false, *position)],
+                _ => Vec::new(),
+            },
+            hir::Node::Borrow {
+                name,
+                mutable,
+                position,
+            } => vec![(name, *mutable, *position)],
+            hir::Node::Closure {
+                moves: false,
+                captures,
+                position,
+                ..
+            } => captures
+                .iter()
+                .map(|c| (&c.name, false, *position))
+                .collect(),
+            _ => Vec::new(),
+        });
+
+        for (name, mutable, position) in places {
+            let shown = source(name);
+            let title = match borrows.get(name) {
+                Some(true) if mutable => {
+                    format!("Cannot borrow '{shown}' as mutable more than once at a time")
+                }
+                Some(true) => format!(
+                    "Cannot borrow '{shown}' as immutable because it is also borrowed as mutable"
+                ),
+                Some(false) if mutable => format!(
+                    "Cannot borrow '{shown}' as mutable because it is also borrowed as immutable"
+                ),
+                _ => {
+                    borrows.insert(name, mutable);
+                    continue;
+                }
+            };
+            self.diagnostics
+                .push(DiagnosticData::error().title(title).position(position));
+        }
+    }
+}
+
+fn is_move_closure(value: &hir::Node) -> bool {
+    matches!(value, hir::Node::Closure { moves: true, .. })
+}
+
+fn source(name: &str) -> &str {
+    name.split('$').next().unwrap_or(name)
+}
@@ -0,0 +1,558 @@
+use std::collections::{BTreeSet, HashSet};
+
+use common::string::Appendable;
+use context::CompilerCtx;
+use syntax::{
+    mir::{Block, CastKind, Expression, Function, Module, Node, Parameter, Type},
+    operators::ArithmeticOperator,
+};
+
+const KEYWORDS: [&str; 37] = [
+    "auto",
+    "break",
+    "case",
+    "char",
+    "const",
+    "continue",
+    "default",
+    "do",
+    "double",
+    "else",
+    "enum",
+    "extern",
+    "float",
+    "for",
+    "goto",
+    "if",
+    "inline",
+    "int",
+    "long",
+    "register",
+    "restrict",
+    "return",
+    "short",
+    "signed",
+    "sizeof",
+    "static",
+    "struct",
+    "switch",
+    "typedef",
+    "union",
+    "unsigned",
+    "void",
+    "volatile",
+    "while",
+    "_Bool",
+    "_Complex",
+    "_Imaginary",
+];
+
+pub struct CCodegen {
+    pub source: String,
+    pointers: HashSet<String>,
+}
+
+pub fn generate(_compiler: &CompilerCtx, module: Module) -> String {
+    let mut codegen = CCodegen {
+        source: String::new(),
+        pointers: HashSet::new(),
+    };
+
+    codegen.source.pushln("#include <stdbool.h>");
+    codegen.source.pushln("#include <stdint.h>");
+    codegen.source.line();
+
+    if uses_functions(&module) {
+        codegen
+            .source
+            .pushln("struct func { void* f0; void* f1; };");
+    }
+    for bits in slices(&module) {
+        codegen.source.pushln(format!(
+            "struct slice{bits} {{ void* f0; uint{bits}_t f1; }};"
+        ));
+    }
+    for structure in &module.structs {
+        let fields = structure
+            .fields
+            .iter()
+            .enumerate()
+            .map(|(index, data_type)| format!("{} f{index};", CCodegen::type_to_string(data_type)))
+            .collect::<Vec<String>>()
+            .join(" ");
+        codegen.source.pushln(format!(
+            "struct {} {{ {fields} }};",
+            identifier(&structure.name)
+        ));
+    }
+
+    for constant in &module.constants {
+        let bytes = constant
+            .value
+            .bytes()
+            .chain(std::iter::once(0))
+            .map(|byte| byte.to_string())
+            .collect::<Vec<String>>()
+            .join(", ");
+        codegen.source.pushln(format!(
+            "static const uint8_t {}[] = {{ {bytes} }};",
+            identifier(&constant.name)
+        ));
+    }
+
+    for declaration in &module.declarations {
+        let signature = CCodegen::signature(
+            &declaration.name,
+            &declaration.parameters,
+            &declaration.return_type,
+        );
+        codegen.source.pushln(format!("{signature};"));
+    }
+    for function in &module.functions {
+        let signature = CCodegen::definition(function);
+        codegen.source.pushln(format!("{signature};"));
+    }
+
+    for function in module.functions {
+        codegen.source.line();
+        codegen.function(function);
+    }
+
+    codegen.source
+}
+
+impl CCodegen {
+    pub fn type_to_string(data_type: &Type) -> String {
+        match data_type {
+            Type::Void => "void".to_string(),
+            Type::Boolean => "bool".to_string(),
+            Type::Bytes(_) => "uint8_t".to_string(),
+            Type::Int(bits) => format!("int{bits}_t"),
+            Type::UInt(bits) => format!("uint{bits}_t"),
+            Type::Float(32) => "float".to_string(),
+            Type::Float(64) => "double".to_string(),
+            Type::Float(bits) => panic!("Unsupported float width: {bits}"),
+            Type::Pointer => "void*".to_string(),
+            Type::Function => "struct func".to_string(),
+            Type::Struct(name) => format!("struct {}", identifier(name)),
+            Type::Slice(bits) => format!("struct slice{bits}"),
+        }
+    }
+    fn declaration(data_type: &Type, name: &str) -> String {
+        let name = identifier(name);
+        match data_type {
+            Type::Bytes(bytes) => format!("uint8_t {name}[{bytes}]"),
+            data_type => format!("{} {name}", CCodegen::type_to_string(data_type)),
+        }
+    }
+    fn is_entry(name: &str) -> bool {
+        name == "main"
+    }
+    fn definition(function: &Function) -> String {
+        let signature =
+            CCodegen::signature(&function.name, &function.parameters, &function.return_type);
+        match function.internal {
+            true => format!("static {signature}"),
+            false => signature,
+        }
+    }
+    fn signature(name: &str, parameters: &[Parameter], return_type: &Type) -> String {
+        let return_type = match CCodegen::is_entry(name) {
+            true => "int".to_string(),
+            false => CCodegen::type_to_string(return_type),
+        };
+
+        let parameters = match parameters.is_empty() {
+            true => "void".to_string(),
+            false => parameters
+                .iter()
+                .map(|p| match p.pointer {
+                    true => format!(
+                        "{}* {}",
+                        CCodegen::type_to_string(&p.data_type),
+                        identifier(&p.name)
+                    ),
+                    false => CCodegen::declaration(&p.data_type, &p.name),
+                })
+                .collect::<Vec<String>>()
+                .join(", "),
+        };
+
+        format!("{return_type} {}({parameters})", identifier(name))
+    }
+
+    fn expression_to_string(&self, expression: Expression) -> String {
+        use Expression::*;
+
+        match expression {
+            Integer(_, int) | Float(_, int) => int,
+            Boolean(value) => value.to_string(),
+            Null => "0".to_string(),
+            Local(_, name) if self.pointers.contains(&name) => format!("(*{})", identifier(&name)),
+            Local(_, name) => identifier(&name),
+            Reference(name) if self.pointers.contains(&name) => identifier(&name),
+            Reference(name) => format!("&{}", identifier(&name)),
+            Function {
+                function,
+                environment,
+            } => {
+                let environment = match environment {
+                    Some(environment) => self.expression_to_string(*environment),
+                    None => "0".to_string(),
+                };
+                format!(
+                    "(struct func){{ (void*){}, {environment} }}",
+                    identifier(&function)
+                )
+            }
+            Invoke {
+                callee,
+                arguments,
+                data_type,
+            } => {
+                let callee = self.expression_to_string(*callee);
+                let types = std::iter::once("void*".to_string())
+                    .chain(
+                        arguments
+                            .iter()
+                            .map(|a| CCodegen::type_to_string(&a.data_type())),
+                    )
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                let arguments = std::iter::once(format!("{callee}.f1"))
+                    .chain(arguments.into_iter().map(|a| self.expression_to_string(a)))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+
+                format!(
+                    "(({} (*)({types})){callee}.f0)({arguments})",
+                    CCodegen::type_to_string(&data_type)
+                )
+            }
+            FieldPointer {
+                structure,
+                base,
+                index,
+            } => format!(
+                "(&((struct {}*){})->f{index})",
+                identifier(&structure),
+                self.expression_to_string(*base)
+            ),
+            Load(data_type, pointer) => format!(
+                "(*({}*){})",
+                CCodegen::type_to_string(&data_type),
+                self.expression_to_string(*pointer)
+            ),
+            Str { constant, length } => format!(
+                "({}){{ (void*){}, {} }}",
+                CCodegen::type_to_string(&slice_type(&length)),
+                identifier(&constant),
+                self.expression_to_string(*length)
+            ),
+            Slice { data, length } => format!(
+                "({}){{ {}, {} }}",
+                CCodegen::type_to_string(&slice_type(&length)),
+                self.expression_to_string(*data),
+                self.expression_to_string(*length)
+            ),
+            Extract { value, index, .. } => {
+                format!("{}.f{index}", self.expression_to_string(*value))
+            }
+            Offset { base, offset } => format!(
+                "((uint8_t*){} + {})",
+                self.expression_to_string(*base),
+                self.expression_to_string(*offset)
+            ),
+            Binary {
+                operator,
+                left,
+                right,
+                ..
+            } => format!(
+                "({} {operator} {})",
+                self.expression_to_string(*left),
+                self.expression_to_string(*right)
+            ),
+            Overflow {
+                operator,
+                left,
+                right,
+            } => {
+                let data_type = left.data_type();
+                let a = self.expression_to_string(*left);
+                let b = self.expression_to_string(*right);
+                overflow(operator, &data_type, &a, &b)
+            }
+            Cast {
+                kind: CastKind::FloatToSigned,
+                value,
+                data_type,
+            } => {
+                let Type::Int(bits) = data_type else {
+                    panic!("Expected a signed integer target, got {data_type}");
+                };
+                let value = self.expression_to_string(*value);
+                format!(
+                    "({value} != {value} ? 0 : {value} <= INT{bits}_MIN ? INT{bits}_MIN : {value} >= INT{bits}_MAX ? INT{bits}_MAX : (int{bits}_t){value})"
+                )
+            }
+            Cast {
+                kind: CastKind::FloatToUnsigned,
+                value,
+                data_type,
+            } => {
+                let Type::UInt(bits) = data_type else {
+                    panic!("Expected an unsigned integer target, got {data_type}");
+                };
+                let value = self.expression_to_string(*value);
+                format!(
+                    "({value} != {value} || {value} <= 0 ? 0 : {value} >= UINT{bits}_MAX ? UINT{bits}_MAX : (uint{bits}_t){value})"
+                )
+            }
+            Cast {
+                kind,
+                value,
+                data_type,
+            } => {
+                let source = match (kind, value.data_type()) {
+                    (
+                        CastKind::ZeroExtend | CastKind::UnsignedToFloat,
+                        Type::Int(bits) | Type::UInt(bits),
+                    ) => format!("(uint{bits}_t)"),
+                    (
+                        CastKind::SignExtend | CastKind::SignedToFloat,
+                        Type::Int(bits) | Type::UInt(bits),
+                    ) => format!("(int{bits}_t)"),
+                    _ => String::new(),
+                };
+                format!(
+                    "(({}){source}{})",
+                    CCodegen::type_to_string(&data_type),
+                    self.expression_to_string(*value)
+                )
+            }
+            Call {
+                function,
+                arguments,
+                ..
+            } => {
+                let arguments = arguments
+                    .into_iter()
+                    .map(|argument| self.expression_to_string(argument))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                format!("{}({arguments})", identifier(&function))
+            }
+        }
+    }
+
+    fn function(&mut self, function: Function) {
+        let is_entry = CCodegen::is_entry(&function.name);
+        let signature = CCodegen::definition(&function);
+
+        self.source.pushln(format!("{signature} {{"));
+        self.pointers = function
+            .parameters
+            .iter()
+            .filter(|p| p.pointer)
+            .map(|p| p.name.clone())
+            .collect();
+
+        for block in &function.blocks {
+            for node in &block.body {
+                if let Node::Allocate { name, data_type } = node {
+                    let declaration = CCodegen::declaration(data_type, name);
+                    self.source.pushln(format!("    {declaration};"));
+                }
+            }
+        }
+
+        for block in function.blocks {
+            self.block(block, is_entry);
+        }
+
+        self.source.pushln("}");
+    }
+    fn block(&mut self, block: Block, is_entry: bool) {
+        self.source.pushln(format!("{}:", block.label));
+
+        for node in block.body {
+            let statement = match node {
+                Node::Allocate { .. } => continue,
+                Node::Return(Some(value)) => {
+                    format!("return {};", self.expression_to_string(value))
+                }
+                Node::Return(None) if is_entry => "return 0;".to_string(),
+                Node::Return(None) => "return;".to_string(),
+                Node::Unreachable => "for (;;) {}".to_string(),
+                Node::Goto(label) => format!("goto {label};"),
+                Node::Branch {
+                    condition,
+                    then,
+                    otherwise,
+                } => format!(
+                    "if ({}) goto {then}; else goto {otherwise};",
+                    self.expression_to_string(condition)
+                ),
+                Node::Expression(value) => format!("{};", self.expression_to_string(value)),
+                Node::Store {
+                    pointer,
+                    data_type,
+                    value,
+                } => format!(
+                    "*({}*){} = {};",
+                    CCodegen::type_to_string(&data_type),
+                    self.expression_to_string(pointer),
+                    self.expression_to_string(value)
+                ),
+                Node::Set { name, value, .. } if self.pointers.contains(&name) => {
+                    format!(
+                        "*{} = {};",
+                        identifier(&name),
+                        self.expression_to_string(value)
+                    )
+                }
+                Node::Set { name, value, .. } => {
+                    format!(
+                        "{} = {};",
+                        identifier(&name),
+                        self.expression_to_string(value)
+                    )
+                }
+            };
+            self.source.pushln(format!("    {statement}"));
+        }
+    }
+}
+
+fn uses_functions(module: &Module) -> bool {
+    let signatures = module
+        .declarations
+        .iter()
+        .map(|d| (&d.parameters, &d.return_type))
+        .chain(
+            module
+                .functions
+                .iter()
+                .map(|f| (&f.parameters, &f.return_type)),
+        );
+    let mut types = signatures.flat_map(|(parameters, return_type)| {
+        parameters
+            .iter()
+            .map(|p| &p.data_type)
+            .chain(std::iter::once(return_type))
+    });
+    let mut allocations = module
+        .functions
+        .iter()
+        .flat_map(|f| &f.blocks)
+        .flat_map(|b| &b.body)
+        .filter_map(|node| match node {
+            Node::Allocate { data_type, .. } => Some(data_type),
+            _ => None,
+        });
+
+    types.any(|t| *t == Type::Function) || allocations.any(|t| *t == Type::Function)
+}
+
+fn slices(module: &Module) -> BTreeSet<u8> {
+    fn walk(expression: &Expression, slices: &mut BTreeSet<u8>) {
+        if let Type::Slice(bits) = expression.data_type() {
+            slices.insert(bits);
+        }
+        let children: Vec<&Expression> = match expression {
+            Expression::Call { arguments, .. } => arguments.iter().collect(),
+            Expression::Invoke {
+                callee, arguments, ..
+            } => std::iter::once(callee.as_ref()).chain(arguments).collect(),
+            Expression::Function {
+                environment: Some(environment),
+                ..
+            } => vec![environment],
+            Expression::FieldPointer { base, .. } | Expression::Load(_, base) => vec![base],
+            Expression::Str { length, .. } => vec![length],
+            Expression::Slice { data, length } => vec![data, length],
+            Expression::Extract { value, .. } | Expression::Cast { value, .. } => vec![value],
+            Expression::Offset { base, offset } => vec![base, offset],
+            Expression::Binary { left, right, .. } | Expression::Overflow { left, right, .. } => {
+                vec![left, right]
+            }
+            _ => Vec::new(),
+        };
+        for child in children {
+            walk(child, slices);
+        }
+    }
+
+    let mut slices = BTreeSet::new();
+    let parameters = module
+        .declarations
+        .iter()
+        .flat_map(|d| &d.parameters)
+        .chain(module.functions.iter().flat_map(|f| &f.parameters));
+    for parameter in parameters {
+        if let Type::Slice(bits) = parameter.data_type {
+            slices.insert(bits);
+        }
+    }
+    for node in module
+        .functions
+        .iter()
+        .flat_map(|f| &f.blocks)
+        .flat_map(|b| &b.body)
+    {
+        match node {
+            Node::Set { value, .. } | Node::Return(Some(value)) | Node::Expression(value) => {
+                walk(value, &mut slices)
+            }
+            Node::Store { pointer, value, .. } => {
+                walk(pointer, &mut slices);
+                walk(value, &mut slices);
+            }
+            _ => {}
+        }
+    }
+    slices
+}
+
+fn slice_type(length: &Expression) -> Type {
+    match length.data_type() {
+        Type::UInt(bits) => Type::Slice(bits),
+        data_type => panic!("Expected an integer length, got {data_type}"),
+    }
+}
+
+fn overflow(operator: ArithmeticOperator, data_type: &Type, a: &str, b: &str) -> String {
+    let (min, max) = match data_type {
+        Type::Int(bits) => (format!("INT{bits}_MIN"), format!("INT{bits}_MAX")),
+        Type::UInt(bits) => (String::new(), format!("UINT{bits}_MAX")),
+        data_type => panic!("Expected an integer operand, got {data_type}"),
+    };
+    match (operator, data_type) {
+        (ArithmeticOperator::Plus, Type::UInt(_)) => format!("({a} > {max} - {b})"),
+        (ArithmeticOperator::Subtract, Type::UInt(_)) => format!("({a} < {b})"),
+        (ArithmeticOperator::Multiply, Type::UInt(_)) => {
+            format!("({b} != 0 && {a} > {max} / {b})")
+        }
+        (ArithmeticOperator::Plus, _) => {
+            format!("(({b} > 0 && {a} > {max} - {b}) || ({b} < 0 && {a} < {min} - {b}))")
+        }
+        (ArithmeticOperator::Subtract, _) => {
+            format!("(({b} < 0 && {a} > {max} + {b}) || ({b} > 0 && {a} < {min} + {b}))")
+        }
+        (ArithmeticOperator::Multiply, _) => format!(
+            "({a} > 0 ? ({b} > 0 ? {a} > {max} / {b} : {b} < {min} / {a}) : ({b} > 0 ? {a} < {min} / {b} : ({a} != 0 && {b} < {max} / {a})))"
+        ),
+        (operator, _) => panic!("No overflow check for operator '{operator}'"),
+    }
+}
+
+fn identifier(name: &str) -> String {
+    if name.contains('$') {
+        return format!("E{}", name.replace('_', "_U").replace('$', "_S"));
+    }
+    match KEYWORDS.contains(&name) {
+        true => format!("{name}_"),
+        false => name.to_string(),
+    }
+}
@@ -1,23 +1,97 @@
+use std::collections::BTreeSet;
+
 use common::string::Appendable;
 use context::CompilerCtx;
-use syntax::mir::{Expression, Module, Node, Parameter, Type};
+use syntax::{
+    mir::{Block, CastKind, Expression, Module, Node, Parameter, Type},
+    operators::{ArithmeticOperator, CompareOperator, Operator},
+};
 
+pub mod c;
 mod llvm;
 
 pub struct Codegen {
     pub source: String,
+    temporaries: usize,
+    intrinsics: BTreeSet<String>,
 }
 
 pub fn generate(compiler: &CompilerCtx, module: Module) -> String {
     let mut codegen = Codegen {
         source: String::new(),
+        temporaries: 0,
+        intrinsics: BTreeSet::new(),
     };
 
+    let target = compiler.target();
+    codegen
+        .source
+        .pushln(format!("target datalayout = \"{}\"", target.data_layout()));
     codegen
         .source
-        .pushln("target triple = \"x86_64-unkown-linux-gnu\"");
+        .pushln(format!("target triple = \"{}\"", target.triple()));
+
+    if !module.structs.is_empty() {
+        codegen.source.line();
+    }
+    for structure in &module.structs {
+        let fields = structure
+            .fields
+            .iter()
+            .map(Codegen::type_to_string)
+            .collect::<Vec<String>>()
+            .join(", ");
+        codegen
+            .source
+            .pushln(format!("%{} = type {{ {fields} }}", structure.name));
+    }
 
-    // for function in module.functions {}
+    if !module.constants.is_empty() {
+        codegen.source.line();
+    }
+    for constant in &module.constants {
+        codegen.source.pushln(format!(
+            "@{} = private unnamed_addr constant [{} x i8] c\"{}\"",
+            constant.name,
+            constant.value.len(),
+            escape(&constant.value)
+        ));
+    }
+
+    if !module.declarations.is_empty() {
+        codegen.source.line();
+    }
+    for declaration in module.declarations {
+        let return_type = Codegen::type_to_string(&declaration.return_type);
+        let parameters = declaration
+            .parameters
+            .iter()
+            .map(Codegen::parameter_type)
+            .collect::<Vec<String>>()
+            .join(", ");
+        codegen.source.pushln(format!(
+            "declare {return_type} @{}({parameters})",
+            declaration.name
+        ));
+    }
+
+    for function in module.functions {
+        codegen.source.line();
+        codegen.function(
+            function.internal,
+            function.name,
+            function.parameters,
+            function.return_type,
+            function.blocks,
+        );
+    }
+
+    if !codegen.intrinsics.is_empty() {
+        codegen.source.line();
+    }
+    for intrinsic in std::mem::take(&mut codegen.intrinsics) {
+        codegen.source.pushln(intrinsic);
+    }
 
     codegen.source
 }
@@ -28,7 +102,20 @@ impl Codegen {
             Type::Void => "void".to_string(),
             Type::Boolean => "i1".to_string(),
             Type::Bytes(bytes) => format!("[{bytes} x i8]"),
-            Type::Int(bits) => format!("i{bits}"),
+            Type::Int(bits) | Type::UInt(bits) => format!("i{bits}"),
+            Type::Float(32) => "float".to_string(),
+            Type::Float(64) => "double".to_string(),
+            Type::Float(bits) => panic!("Unsupported float width: {bits}"),
+            Type::Pointer => "ptr".to_string(),
+            Type::Function => "{ ptr, ptr }".to_string(),
+            Type::Struct(name) => format!("%{name}"),
+            Type::Slice(bits) => format!("{{ ptr, i{bits} }}"),
+        }
+    }
+    fn parameter_type(parameter: &Parameter) -> String {
+        match parameter.pointer {
+            true => Codegen::type_to_string(&Type::Pointer),
+            false => Codegen::type_to_string(&parameter.data_type),
         }
     }
     pub fn write_type(&mut self, data_type: &Type) {
@@ -36,53 +123,451 @@ impl Codegen {
         self.source.push_string(data_type);
     }
 
+    fn temporary(&mut self) -> String {
+        self.temporaries += 1;
+        format!("%.t{}", self.temporaries)
+    }
     fn expression_to_string(&mut self, expression: Expression) -> String {
         use Expression::*;
 
         match expression {
-            _ => todo!(),
+            Integer(_, int) => int,
+            Float(data_type, value) => {
+                let value: f64 = value.parse().expect("Expected a float literal");
+                let value = match data_type {
+                    Type::Float(32) => value as f32 as f64,
+                    _ => value,
+                };
+                format!("0x{:016X}", value.to_bits())
+            }
+            Boolean(value) => value.to_string(),
+            Null => "null".to_string(),
+            Local(data_type, name) => {
+                let data_type = Codegen::type_to_string(&data_type);
+                let temporary = self.temporary();
+                self.source
+                    .pushln(format!("  {temporary} = load {data_type}, ptr %{name}"));
+                temporary
+            }
+            Reference(name) => format!("%{name}"),
+            Function {
+                function,
+                environment,
+            } => {
+                let environment = match environment {
+                    Some(environment) => self.expression_to_string(*environment),
+                    None => "null".to_string(),
+                };
+                let data_type = Codegen::type_to_string(&Type::Function);
+
+                let value = self.temporary();
+                self.source.pushln(format!(
+                    "  {value} = insertvalue {data_type} undef, ptr @{function}, 0"
+                ));
+                let temporary = self.temporary();
+                self.source.pushln(format!(
+                    "  {temporary} = insertvalue {data_type} {value}, ptr {environment}, 1"
+                ));
+                temporary
+            }
+            Invoke {
+                callee,
+                arguments,
+                data_type,
+            } => {
+                let callee = self.expression_to_string(*callee);
+                let function_type = Codegen::type_to_string(&Type::Function);
+
+                let function = self.temporary();
+                self.source.pushln(format!(
+                    "  {function} = extractvalue {function_type} {callee}, 0"
+                ));
+                let environment = self.temporary();
+                self.source.pushln(format!(
+                    "  {environment} = extractvalue {function_type} {callee}, 1"
+                ));
+
+                let arguments = arguments
+                    .into_iter()
+                    .map(|argument| {
+                        let data_type = Codegen::type_to_string(&argument.data_type());
+                        format!("{data_type} {}", self.expression_to_string(argument))
+                    })
+                    .collect::<Vec<String>>();
+                let arguments = std::iter::once(format!("ptr {environment}"))
+                    .chain(arguments)
+                    .collect::<Vec<String>>()
+                    .join(", ");
+
+                let call = format!(
+                    "call {} {function}({arguments})",
+                    Codegen::type_to_string(&data_type)
+                );
+                if data_type == Type::Void {
+                    self.source.pushln(format!("  {call}"));
+                    return String::new();
+                }
+
+                let temporary = self.temporary();
+                self.source.pushln(format!("  {temporary} = {call}"));
+                temporary
+            }
+            FieldPointer {
+                structure,
+                base,
+                index,
+            } => {
+                let base = self.expression_to_string(*base);
+                let temporary = self.temporary();
+                self.source.pushln(format!(
+                    "  {temporary} = getelementptr %{structure}, ptr {base}, i32 0, i32 {index}"
+                ));
+                temporary
+            }
+            Str { constant, length } => {
+                let data_type = Codegen::type_to_string(&length.data_type());
+                let slice = format!("{{ ptr, {data_type} }}");
+                let length = self.expression_to_string(*length);
+
+                let value = self.temporary();
+                self.source.pushln(format!(
+                    "  {value} = insertvalue {slice} undef, ptr @{constant}, 0"
+                ));
+                let temporary = self.temporary();
+                self.source.pushln(format!(
+                    "  {temporary} = insertvalue {slice} {value}, {data_type} {length}, 1"
+                ));
+                temporary
+            }
+            Slice { data, length } => {
+                let data_type = Codegen::type_to_string(&length.data_type());
+                let slice = format!("{{ ptr, {data_type} }}");
+                let data = self.expression_to_string(*data);
+                let length = self.expression_to_string(*length);
+
+                let value = self.temporary();
+                self.source.pushln(format!(
+                    "  {value} = insertvalue {slice} undef, ptr {data}, 0"
+                ));
+                let temporary = self.temporary();
+                self.source.pushln(format!(
+                    "  {temporary} = insertvalue {slice} {value}, {data_type} {length}, 1"
+                ));
+                temporary
+            }
+            Extract { value, index, .. } => {
+                let data_type = Codegen::type_to_string(&value.data_type());
+                let value = self.expression_to_string(*value);
+                let temporary = self.temporary();
+                self.source.pushln(format!(
+                    "  {temporary} = extractvalue {data_type} {value}, {index}"
+                ));
+                temporary
+            }
+            Offset { base, offset } => {
+                let data_type = Codegen::type_to_string(&offset.data_type());
+                let base = self.expression_to_string(*base);
+                let offset = self.expression_to_string(*offset);
+                let temporary = self.temporary();
+                self.source.pushln(format!(
+                    "  {temporary} = getelementptr i8, ptr {base}, {data_type} {offset}"
+                ));
+                temporary
+            }
+            Load(data_type, pointer) => {
+                let data_type = Codegen::type_to_string(&data_type);
+                let pointer = self.expression_to_string(*pointer);
+                let temporary = self.temporary();
+                self.source
+                    .pushln(format!("  {temporary} = load {data_type}, ptr {pointer}"));
+                temporary
+            }
+            Binary {
+                operator,
+                left,
+                right,
+                ..
+            } => {
+                let instruction = Codegen::operator(&operator, &left.data_type());
+                let data_type = Codegen::type_to_string(&left.data_type());
+                let left = self.expression_to_string(*left);
+                let right = self.expression_to_string(*right);
+
+                let temporary = self.temporary();
+                self.source.pushln(format!(
+                    "  {temporary} = {instruction} {data_type} {left}, {right}"
+                ));
+                temporary
+            }
+            Overflow {
+                operator,
+                left,
+                right,
+            } => {
+                let sign = match left.data_type() {
+                    Type::UInt(_) => "u",
+                    _ => "s",
+                };
+                let data_type = Codegen::type_to_string(&left.data_type());
+                let left = self.expression_to_string(*left);
+                let right = self.expression_to_string(*right);
+                let instruction = match operator {
+                    ArithmeticOperator::Plus => "add",
+                    ArithmeticOperator::Subtract => "sub",
+                    ArithmeticOperator::Multiply => "mul",
+                    operator => panic!("No overflow intrinsic for operator '{operator}'"),
+                };
+
+                let intrinsic = format!("@llvm.{sign}{instruction}.with.overflow.{data_type}");
+                let pair = format!("{{ {data_type}, i1 }}");
+                self.intrinsics.insert(format!(
+                    "declare {pair} {intrinsic}({data_type}, {data_type})"
+                ));
+
+                let result = self.temporary();
+                self.source.pushln(format!(
+                    "  {result} = call {pair} {intrinsic}({data_type} {left}, {data_type} {right})"
+                ));
+                let temporary = self.temporary();
+                self.source
+                    .pushln(format!("  {temporary} = extractvalue {pair} {result}, 1"));
+                temporary
+            }
+            Cast {
+                kind: CastKind::Bitcast,
+                value,
+                ..
+            } => self.expression_to_string(*value),
+            Cast {
+                kind: kind @ (CastKind::FloatToSigned | CastKind::FloatToUnsigned),
+                value,
+                data_type,
+            } => {
+                let source = value.data_type();
+                let Type::Float(bits) = source else {
+                    panic!("Expected a float operand, got {source}");
+                };
+                let from = Codegen::type_to_string(&source);
+                let value = self.expression_to_string(*value);
+                let data_type = Codegen::type_to_string(&data_type);
+
+                let intrinsic = format!("@llvm.{kind}.sat.{data_type}.f{bits}");
+                self.intrinsics
+                    .insert(format!("declare {data_type} {intrinsic}({from})"));
+                let temporary = self.temporary();
+                self.source.pushln(format!(
+                    "  {temporary} = call {data_type} {intrinsic}({from} {value})"
+                ));
+                temporary
+            }
+            Cast {
+                kind,
+                value,
+                data_type,
+            } => {
+                let from = Codegen::type_to_string(&value.data_type());
+                let value = self.expression_to_string(*value);
+                let data_type = Codegen::type_to_string(&data_type);
+
+                let temporary = self.temporary();
+                self.source.pushln(format!(
+                    "  {temporary} = {kind} {from} {value} to {data_type}"
+                ));
+                temporary
+            }
+            Call {
+                function,
+                arguments,
+                data_type,
+            } => {
+                let arguments = arguments
+                    .into_iter()
+                    .map(|argument| {
+                        let data_type = Codegen::type_to_string(&argument.data_type());
+                        format!("{data_type} {}", self.expression_to_string(argument))
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ");
+
+                let call = format!(
+                    "call {} @{function}({arguments})",
+                    Codegen::type_to_string(&data_type)
+                );
+                if data_type == Type::Void {
+                    self.source.pushln(format!("  {call}"));
+                    return String::new();
+                }
+
+                let temporary = self.temporary();
+                self.source.pushln(format!("  {temporary} = {call}"));
+                temporary
+            }
+        }
+    }
+
+    fn operator(operator: &Operator, data_type: &Type) -> &'static str {
+        use ArithmeticOperator::*;
+        use CompareOperator::*;
+
+        match (operator, data_type) {
+            (Operator::Arithmetic(operator), Type::Float(_)) => match operator {
+                Plus => "fadd",
+                Subtract => "fsub",
+                Multiply => "fmul",
+                Division => "fdiv",
+                Remainder => "frem",
+                operator => panic!("No float instruction for operator '{operator}'"),
+            },
+            (Operator::Arithmetic(operator), data_type) => {
+                let unsigned = matches!(data_type, Type::UInt(_));
+                match operator {
+                    Plus => "add",
+                    Subtract => "sub",
+                    Multiply => "mul",
+                    Division if unsigned => "udiv",
+                    Division => "sdiv",
+                    Remainder if unsigned => "urem",
+                    Remainder => "srem",
+                    LeftBitshift => "shl",
+                    RightBitshift if unsigned => "lshr",
+                    RightBitshift => "ashr",
+                }
+            }
+            (Operator::Compare(operator), Type::Float(_)) => match operator {
+                Compare => "fcmp oeq",
+                NotEquals => "fcmp une",
+                GreaterThan => "fcmp ogt",
+                GreaterThanOrEquals => "fcmp oge",
+                LessThan => "fcmp olt",
+                LessThanOrEquals => "fcmp ole",
+                operator => panic!("No float comparison for operator '{operator}'"),
+            },
+            (Operator::Compare(operator), data_type) => {
+                let unsigned = matches!(data_type, Type::UInt(_));
+                match operator {
+                    Compare => "icmp eq",
+                    NotEquals => "icmp ne",
+                    GreaterThan if unsigned => "icmp ugt",
+                    GreaterThan => "icmp sgt",
+                    GreaterThanOrEquals if unsigned => "icmp uge",
+                    GreaterThanOrEquals => "icmp sge",
+                    LessThan if unsigned => "icmp ult",
+                    LessThan => "icmp slt",
+                    LessThanOrEquals if unsigned => "icmp ule",
+                    LessThanOrEquals => "icmp sle",
+                    And => "and",
+                    Or => "or",
+                }
+            }
         }
     }
 
     fn function(
         &mut self,
+        internal: bool,
         name: String,
         parameters: Vec<Parameter>,
         return_type: Type,
-        body: Vec<Node>,
+        blocks: Vec<Block>,
     ) {
-        self.source.pushln(format!("define i32 @{name}() {{\n"));
-        self.source.pushln("start:");
+        self.temporaries = 0;
+        let return_type = Codegen::type_to_string(&return_type);
+        let signature = parameters
+            .iter()
+            .map(|p| match p.pointer {
+                true => format!("{} %{}", Codegen::parameter_type(p), p.name),
+                false => format!("{} %{}.param", Codegen::parameter_type(p), p.name),
+            })
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        let linkage = match internal {
+            true => "internal ",
+            false => "",
+        };
+        self.source.pushln(format!(
+            "define {linkage}{return_type} @{name}({signature}) {{"
+        ));
 
-        body.into_iter().for_each(|n| self.node_to_string(n));
+        for (index, block) in blocks.into_iter().enumerate() {
+            self.source.pushln(format!("{}:", block.label));
+            if index == 0 {
+                for parameter in parameters.iter().filter(|p| !p.pointer) {
+                    let data_type = Codegen::type_to_string(&parameter.data_type);
+                    let name = &parameter.name;
+                    self.source
+                        .pushln(format!("  %{name} = alloca {data_type}"));
+                    self.source
+                        .pushln(format!("  store {data_type} %{name}.param, ptr %{name}"));
+                }
+            }
+            block.body.into_iter().for_each(|n| self.node_to_string(n));
+        }
 
         self.source.pushln("}");
     }
     fn node_to_string(&mut self, node: Node) {
         use Node::*;
 
-        match node {
+        let instruction = match node {
             Return(expr) => match expr {
-                Some(expr) => match expr {
-                    Expression::Integer(data_type, int) => {
-                        let data_type = Codegen::type_to_string(&data_type);
-                        self.source.push_string(format!("ret {data_type} {int}"))
-                    }
-                },
-                None => self.source.push_str("ret void"),
+                Some(expr) => {
+                    let data_type = Codegen::type_to_string(&expr.data_type());
+                    let value = self.expression_to_string(expr);
+                    format!("ret {data_type} {value}")
+                }
+                None => "ret void".to_string(),
             },
-            Allocate(data_type) => {
+            Unreachable => "unreachable".to_string(),
+            Allocate { name, data_type } => {
                 let data_type = Codegen::type_to_string(&data_type);
-                self.source.push_string(format!("alloca {data_type}"));
+                format!("%{name} = alloca {data_type}")
+            }
+            Goto(label) => format!("br label %{label}"),
+            Branch {
+                condition,
+                then,
+                otherwise,
+            } => {
+                let condition = self.expression_to_string(condition);
+                format!("br i1 {condition}, label %{then}, label %{otherwise}")
             }
-            Goto(label) => self.source.push_string(format!("br %{label}")),
             Set {
                 name,
                 data_type,
                 value,
-            } => todo!(),
+            } => {
+                let data_type = Codegen::type_to_string(&data_type);
+                let value = self.expression_to_string(value);
+                format!("store {data_type} {value}, ptr %{name}")
+            }
+            Store {
+                pointer,
+                data_type,
+                value,
+            } => {
+                let data_type = Codegen::type_to_string(&data_type);
+                let value = self.expression_to_string(value);
+                let pointer = self.expression_to_string(pointer);
+                format!("store {data_type} {value}, ptr {pointer}")
+            }
+            Expression(value) => {
+                self.expression_to_string(value);
+                return;
+            }
         };
 
-        self.source.line();
+        self.source.pushln(format!("  {instruction}"));
     }
 }
+
+fn escape(value: &str) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'"' | b'\\' => format!("\\{byte:02X}"),
+            byte if byte.is_ascii_graphic() || byte == b' ' => (byte as char).to_string(),
+            byte => format!("\\{byte:02X}"),
+        })
+        .collect()
+}
@@ -1,13 +1,30 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{
+    collections::{BTreeSet, HashMap},
+    io,
+    path::{Path, PathBuf},
+};
 
-pub trait ResolveFile {
+use common::constants::FILE_EXTENSION;
+
+pub const STD_DIRECTORY: &str = "std";
+const STD_FILES: [(&str, &str); 4] = [
+    ("lib", include_str!("../../../std/lib.ecl")),
+    ("io", include_str!("../../../std/io.ecl")),
+    ("math", include_str!("../../../std/math.ecl")),
+    ("string", include_str!("../../../std/string.ecl")),
+];
+
+pub trait ResolveFile: Send + Sync {
     fn read(&self, path: &PathBuf) -> Option<String>;
-    fn write(&mut self, path: &PathBuf, contents: &str);
+    fn write(&mut self, path: &PathBuf, contents: &str) -> io::Result<()>;
+    fn exists(&self, path: &PathBuf) -> bool;
+    fn list(&self, path: &PathBuf) -> Vec<PathBuf>;
 }
 
 pub enum FileResolver {
     Mock(MockResolver),
     FileSystem(FileSystemResolver),
+    Overlay(OverlayResolver),
 }
 impl Default for FileResolver {
     fn default() -> Self {
@@ -21,13 +38,31 @@ impl ResolveFile for FileResolver {
         match self {
             Mock(rs) => rs.read(path),
             FileSystem(rs) => rs.read(path),
+            Overlay(rs) => rs.read(path),
         }
     }
-    fn write(&mut self, path: &PathBuf, contents: &str) {
+    fn write(&mut self, path: &PathBuf, contents: &str) -> io::Result<()> {
         use FileResolver::*;
         match self {
             Mock(rs) => rs.write(path, contents),
             FileSystem(rs) => rs.write(path, contents),
+            Overlay(rs) => rs.write(path, contents),
+        }
+    }
+    fn exists(&self, path: &PathBuf) -> bool {
+        use FileResolver::*;
+        match self {
+            Mock(rs) => rs.exists(path),
+            FileSystem(rs) => rs.exists(path),
+            Overlay(rs) => rs.exists(path),
+        }
+    }
+    fn list(&self, path: &PathBuf) -> Vec<PathBuf> {
+        use FileResolver::*;
+        match self {
+            Mock(rs) => rs.list(path),
+            FileSystem(rs) => rs.list(path),
+            Overlay(rs) => rs.list(path),
         }
     }
 }
@@ -36,15 +71,24 @@ impl ResolveFile for FileResolver {
 pub struct FileSystemResolver;
 impl ResolveFile for FileSystemResolver {
     fn read(&self, path: &PathBuf) -> Option<String> {
-        match std::fs::read_to_string(path) {
-            Ok(s) => Some(s),
-            Err(_) => None,
+        std::fs::read_to_string(path).ok()
+    }
+    fn write(&mut self, path: &PathBuf, contents: &str) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
         }
+        std::fs::write(path, contents)
+    }
+    fn exists(&self, path: &PathBuf) -> bool {
+        path.is_file()
     }
-    fn write(&mut self, path: &PathBuf, contents: &str) {
-        let parent = path.parent().unwrap();
-        std::fs::create_dir_all(parent).unwrap();
-        std::fs::write(path, contents).unwrap()
+    fn list(&self, path: &PathBuf) -> Vec<PathBuf> {
+        let mut entries: Vec<PathBuf> = match std::fs::read_dir(path) {
+            Ok(entries) => entries.flatten().map(|entry| entry.path()).collect(),
+            Err(_) => Vec::new(),
+        };
+        entries.sort();
+        entries
     }
 }
 impl From<FileSystemResolver> for FileResolver {
@@ -61,16 +105,23 @@ impl MockResolver {
     pub fn new() -> Self {
         Self::default()
     }
+    pub fn remove(&mut self, path: &PathBuf) -> Option<String> {
+        self.files.remove(path)
+    }
 }
 impl ResolveFile for MockResolver {
     fn read(&self, path: &PathBuf) -> Option<String> {
-        match self.files.get(path) {
-            Some(s) => Some(s.to_string()),
-            None => None,
-        }
+        self.files.get(path).cloned()
     }
-    fn write(&mut self, path: &PathBuf, contents: &str) {
+    fn write(&mut self, path: &PathBuf, contents: &str) -> io::Result<()> {
         self.files.insert(path.clone(), contents.to_string());
+        Ok(())
+    }
+    fn exists(&self, path: &PathBuf) -> bool {
+        self.files.contains_key(path)
+    }
+    fn list(&self, path: &PathBuf) -> Vec<PathBuf> {
+        children(self.files.keys(), path).into_iter().collect()
     }
 }
 impl From<MockResolver> for FileResolver {
@@ -78,3 +129,87 @@ impl From<MockResolver> for FileResolver {
         FileResolver::Mock(value)
     }
 }
+
+#[derive(Default)]
+pub struct OverlayResolver {
+    memory: MockResolver,
+    file_system: FileSystemResolver,
+}
+impl OverlayResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+    pub fn discard(&mut self, path: &PathBuf) -> Option<String> {
+        self.memory.remove(path)
+    }
+}
+impl ResolveFile for OverlayResolver {
+    fn read(&self, path: &PathBuf) -> Option<String> {
+        self.memory
+            .read(path)
+            .or_else(|| self.file_system.read(path))
+    }
+    fn write(&mut self, path: &PathBuf, contents: &str) -> io::Result<()> {
+        self.memory.write(path, contents)
+    }
+    fn exists(&self, path: &PathBuf) -> bool {
+        self.memory.exists(path) || self.file_system.exists(path)
+    }
+    fn list(&self, path: &PathBuf) -> Vec<PathBuf> {
+        let mut entries: BTreeSet<PathBuf> = self.file_system.list(path).into_iter().collect();
+        entries.extend(self.memory.list(path));
+        entries.into_iter().collect()
+    }
+}
+impl From<OverlayResolver> for FileResolver {
+    fn from(value: OverlayResolver) -> Self {
+        FileResolver::Overlay(value)
+    }
+}
+
+#[derive(Default)]
+pub struct StdResolver;
+impl StdResolver {
+    pub fn contains(path: &Path) -> bool {
+        path.starts_with(STD_DIRECTORY)
+    }
+    pub fn file(name: &str) -> PathBuf {
+        let mut path = PathBuf::from(STD_DIRECTORY).join(name);
+        path.set_extension(FILE_EXTENSION);
+        path
+    }
+}
+impl ResolveFile for StdResolver {
+    fn read(&self, path: &PathBuf) -> Option<String> {
+        STD_FILES
+            .iter()
+            .find(|(name, _)| StdResolver::file(name) == *path)
+            .map(|(_, source)| source.to_string())
+    }
+    fn write(&mut self, path: &PathBuf, _contents: &str) -> io::Result<()> {
+        Err(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!("{path:?} is part of the standard library"),
+        ))
+    }
+    fn exists(&self, path: &PathBuf) -> bool {
+        self.read(path).is_some()
+    }
+    fn list(&self, path: &PathBuf) -> Vec<PathBuf> {
+        let files: Vec<PathBuf> = STD_FILES
+            .iter()
+            .map(|(name, _)| StdResolver::file(name))
+            .collect();
+        children(files.iter(), path).into_iter().collect()
+    }
+}
+
+fn children<'a>(files: impl Iterator<Item = &'a PathBuf>, directory: &Path) -> BTreeSet<PathBuf> {
+    files
+        .filter_map(|file| {
+            let relative = file.strip_prefix(directory).ok()?;
+            let child = relative.components().next()?;
+            Some(directory.join(child))
+        })
+        .collect()
+}
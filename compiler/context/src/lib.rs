@@ -1,16 +1,31 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, str::FromStr};
 
-use common::{constants::FILE_EXTENSION, status::Status};
-use diagnostics::Diagnostics;
-use files::{FileResolver, ResolveFile};
+use common::{config::Config, constants::FILE_EXTENSION, status::Status};
+use diagnostics::{DiagnosticData, Diagnostics};
+use files::{FileResolver, ResolveFile, StdResolver};
+use query::Queries;
 
 pub mod files;
+mod options;
+mod query;
+mod target;
+
+pub use options::{OptLevel, OutputKind};
+pub use query::{Query, QueryId, Source};
+pub use target::Arch;
 
 #[derive(Default)]
 pub struct CompilerBuilder {
     pub status: bool,
     pub project_path: Option<PathBuf>,
     pub module_resolver: FileResolver,
+    pub emit: Vec<Emit>,
+    pub target: Option<Arch>,
+    pub backend: Backend,
+    pub release: bool,
+    pub opt_level: Option<OptLevel>,
+    pub output: Option<OutputKind>,
+    pub incremental: bool,
 }
 impl CompilerBuilder {
     pub fn new() -> Self {
@@ -28,6 +43,34 @@ impl CompilerBuilder {
         self.project_path = Some(path);
         self
     }
+    pub fn emit(mut self, emit: Vec<Emit>) -> Self {
+        self.emit = emit;
+        self
+    }
+    pub fn target(mut self, target: Option<Arch>) -> Self {
+        self.target = target;
+        self
+    }
+    pub fn backend(mut self, backend: Backend) -> Self {
+        self.backend = backend;
+        self
+    }
+    pub fn release(mut self, enabled: bool) -> Self {
+        self.release = enabled;
+        self
+    }
+    pub fn opt_level(mut self, opt_level: Option<OptLevel>) -> Self {
+        self.opt_level = opt_level;
+        self
+    }
+    pub fn output(mut self, output: Option<OutputKind>) -> Self {
+        self.output = output;
+        self
+    }
+    pub fn incremental(mut self, enabled: bool) -> Self {
+        self.incremental = enabled;
+        self
+    }
     pub fn build(self) -> CompilerCtx {
         let project_path = self.project_path.expect("Expected a project path");
         let module_resolver = self.module_resolver;
@@ -38,20 +81,88 @@ impl CompilerBuilder {
             project_path,
             diagnostics: Diagnostics::new(),
             status: self.status.then(|| Status::new()),
+            emit: self.emit,
+            target: self.target,
+            backend: self.backend,
+            release: self.release,
+            opt_level: self.opt_level,
+            output: self.output,
+            incremental: self.incremental,
+            libraries: Vec::new(),
+            queries: Queries::default(),
+            config: Config::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Emit {
+    Ast,
+    Hir,
+    Mir,
+    LlvmIr,
+    C,
+}
+impl Emit {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Emit::Ast => "ast",
+            Emit::Hir => "hir",
+            Emit::Mir => "mir",
+            Emit::LlvmIr => "ll",
+            Emit::C => "c",
         }
     }
 }
+impl FromStr for Emit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "ast" => Emit::Ast,
+            "hir" => Emit::Hir,
+            "mir" => Emit::Mir,
+            "llvm-ir" => Emit::LlvmIr,
+            "c" => Emit::C,
+            _ => {
+                return Err(format!(
+                    "unknown emit kind '{s}', expected: ast, hir, mir, llvm-ir, c"
+                ));
+            }
+        })
+    }
+}
 
-#[allow(non_camel_case_types)]
-pub enum Arch {
-    x86_64,
-    x86,
-    arm_64,
-    arm,
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Backend {
+    #[default]
+    Llvm,
+    C,
+}
+impl FromStr for Backend {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "llvm" => Backend::Llvm,
+            "c" => Backend::C,
+            _ => return Err(format!("unknown backend '{s}', expected: llvm, c")),
+        })
+    }
 }
 
 pub struct CompilerCtx {
     status: Option<Status>,
+    emit: Vec<Emit>,
+    target: Option<Arch>,
+    backend: Backend,
+    release: bool,
+    opt_level: Option<OptLevel>,
+    output: Option<OutputKind>,
+    incremental: bool,
+    libraries: Vec<PathBuf>,
+    queries: Queries,
+    pub config: Config,
     module_resolver: FileResolver,
     project_path: PathBuf,
     logs: Vec<String>,
@@ -68,13 +179,142 @@ impl CompilerCtx {
         };
         status.message(message);
     }
+    pub fn load_config(&mut self) {
+        let relative_path = PathBuf::from(Config::file_name());
+        let source = match self.read(&relative_path) {
+            Some(s) => s,
+            None => return,
+        };
+
+        match Config::parse(&source) {
+            Ok(config) => self.config = config,
+            Err(message) => {
+                return self.diagnostics.insert(
+                    &relative_path,
+                    DiagnosticData::error().title(format!("Invalid config: {message}")),
+                );
+            }
+        }
+
+        let build = &self.config.build;
+        let target = build.target.clone();
+        let opt_level = build.opt_level.map(|level| level.to_string());
+        let output = build.output.clone();
+
+        if self.target.is_none() {
+            self.target = self.config_option(target);
+        }
+        if self.opt_level.is_none() {
+            self.opt_level = self.config_option(opt_level);
+        }
+        if self.output.is_none() {
+            self.output = self.config_option(output);
+        }
+    }
+    fn config_option<T: FromStr<Err = String>>(&mut self, value: Option<String>) -> Option<T> {
+        match value?.parse() {
+            Ok(value) => Some(value),
+            Err(message) => {
+                self.diagnostics.insert(
+                    &PathBuf::from(Config::file_name()),
+                    DiagnosticData::error().title(message),
+                );
+                None
+            }
+        }
+    }
+    pub fn target(&self) -> Arch {
+        self.target.unwrap_or_default()
+    }
+    pub fn backend(&self) -> Backend {
+        self.backend
+    }
+    pub fn is_release(&self) -> bool {
+        self.release
+    }
+    pub fn opt_level(&self) -> OptLevel {
+        match (self.opt_level, self.release) {
+            (Some(opt_level), _) => opt_level,
+            (None, true) => OptLevel::O3,
+            (None, false) => OptLevel::O0,
+        }
+    }
+    pub fn output(&self) -> OutputKind {
+        match (self.output, self.is_library()) {
+            (Some(output), _) => output,
+            (None, true) => OutputKind::StaticLib,
+            (None, false) => OutputKind::Executable,
+        }
+    }
+    pub fn is_incremental(&self) -> bool {
+        self.incremental
+    }
+    pub fn link(&mut self, library: PathBuf) {
+        if !self.libraries.contains(&library) {
+            self.libraries.push(library);
+        }
+    }
+    pub fn libraries(&self) -> &[PathBuf] {
+        &self.libraries
+    }
     pub fn read(&self, relative_path: &PathBuf) -> Option<String> {
+        if StdResolver::contains(relative_path) {
+            return StdResolver.read(relative_path);
+        }
         let path = self.resolve_path(relative_path);
         self.module_resolver.read(&path)
     }
     pub fn write(&mut self, relative_path: &PathBuf, contents: &str) {
         let path = self.resolve_path(relative_path);
-        self.module_resolver.write(&path, contents);
+        if let Err(error) = self.module_resolver.write(&path, contents) {
+            return self.diagnostics.insert(
+                relative_path,
+                DiagnosticData::error()
+                    .title(format!("Failed to write {relative_path:?}: {error}")),
+            );
+        }
+        self.invalidate(relative_path);
+    }
+    pub fn discard(&mut self, relative_path: &PathBuf) {
+        let path = self.resolve_path(relative_path);
+        if let FileResolver::Overlay(resolver) = &mut self.module_resolver {
+            resolver.discard(&path);
+            self.invalidate(relative_path);
+        }
+    }
+    pub fn exists(&self, relative_path: &PathBuf) -> bool {
+        if StdResolver::contains(relative_path) {
+            return StdResolver.exists(relative_path);
+        }
+        let path = self.resolve_path(relative_path);
+        self.module_resolver.exists(&path)
+    }
+    pub fn list(&self, relative_path: &PathBuf) -> Vec<PathBuf> {
+        if StdResolver::contains(relative_path) {
+            return StdResolver.list(relative_path);
+        }
+        let path = self.resolve_path(relative_path);
+        self.module_resolver
+            .list(&path)
+            .into_iter()
+            .map(|entry| match entry.strip_prefix(&self.project_path) {
+                Ok(relative) => relative.to_path_buf(),
+                Err(_) => entry,
+            })
+            .collect()
+    }
+    pub fn emit(&mut self, kind: Emit, contents: impl FnOnce() -> String) {
+        if !self.emit.contains(&kind) {
+            return;
+        }
+
+        let mut path = PathBuf::from("target").join("build");
+        path.set_extension(kind.extension());
+        let mut contents = contents();
+        if !contents.ends_with('\n') {
+            contents.push('\n');
+        }
+        self.write(&path, &contents);
     }
     pub fn log(&mut self, message: impl ToString) {
         self.logs.push(message.to_string());
@@ -88,6 +328,25 @@ impl CompilerCtx {
         main_path.set_extension(FILE_EXTENSION);
         main_path
     }
+    pub fn library_entry() -> PathBuf {
+        let src_path = PathBuf::from("src");
+        let mut lib_path = src_path.join("lib");
+        lib_path.set_extension(FILE_EXTENSION);
+        lib_path
+    }
+    pub fn std_root() -> PathBuf {
+        StdResolver::file("lib")
+    }
+    pub fn root(&self) -> PathBuf {
+        let library = Self::library_entry();
+        if !self.exists(&Self::entry()) && self.exists(&library) {
+            return library;
+        }
+        Self::entry()
+    }
+    pub fn is_library(&self) -> bool {
+        self.root() == Self::library_entry()
+    }
     pub fn finish(self) {
         if let Some(status) = &self.status {
             status.quit();
@@ -0,0 +1,74 @@
+use std::{fmt::Display, str::FromStr};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OptLevel {
+    O0,
+    O1,
+    O2,
+    O3,
+}
+impl OptLevel {
+    pub fn flag(&self) -> &'static str {
+        match self {
+            OptLevel::O0 => "-O0",
+            OptLevel::O1 => "-O1",
+            OptLevel::O2 => "-O2",
+            OptLevel::O3 => "-O3",
+        }
+    }
+}
+impl FromStr for OptLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "0" => OptLevel::O0,
+            "1" => OptLevel::O1,
+            "2" => OptLevel::O2,
+            "3" => OptLevel::O3,
+            _ => {
+                return Err(format!(
+                    "unknown optimization level '{s}', expected: 0, 1, 2, 3"
+                ));
+            }
+        })
+    }
+}
+impl Display for OptLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.flag())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum OutputKind {
+    #[default]
+    Executable,
+    StaticLib,
+    SharedLib,
+}
+impl OutputKind {
+    pub fn file_name(&self, name: &str) -> String {
+        match self {
+            OutputKind::Executable => name.to_string(),
+            OutputKind::StaticLib => format!("lib{name}.a"),
+            OutputKind::SharedLib => format!("lib{name}.so"),
+        }
+    }
+}
+impl FromStr for OutputKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "executable" => OutputKind::Executable,
+            "static" => OutputKind::StaticLib,
+            "shared" => OutputKind::SharedLib,
+            _ => {
+                return Err(format!(
+                    "unknown output kind '{s}', expected: executable, static, shared"
+                ));
+            }
+        })
+    }
+}
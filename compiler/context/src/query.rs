@@ -0,0 +1,207 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt::Debug,
+    hash::{DefaultHasher, Hash, Hasher},
+    path::PathBuf,
+    sync::Arc,
+};
+
+use diagnostics::DiagnosticData;
+
+use crate::CompilerCtx;
+
+type Erased = Arc<dyn Any + Send + Sync>;
+
+pub trait Query: 'static {
+    type Key: Clone + Debug + Send + Sync + 'static;
+    type Value: Debug + Send + Sync + 'static;
+
+    const INPUT: bool = false;
+
+    fn execute(compiler: &mut CompilerCtx, key: &Self::Key) -> Self::Value;
+
+    fn cycle(key: &Self::Key) -> Self::Value;
+
+    fn fingerprint(value: &Self::Value) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        format!("{value:?}").hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct QueryId {
+    query: TypeId,
+    name: &'static str,
+    key: String,
+}
+impl QueryId {
+    pub fn new<Q: Query>(key: &Q::Key) -> Self {
+        Self {
+            query: TypeId::of::<Q>(),
+            name: std::any::type_name::<Q>(),
+            key: format!("{key:?}"),
+        }
+    }
+}
+
+struct Memo {
+    value: Erased,
+    key: Erased,
+    fingerprint: u64,
+    dependencies: Vec<QueryId>,
+    verified_at: u64,
+    changed_at: u64,
+    input: bool,
+    dirty: bool,
+    update: fn(&mut CompilerCtx, Erased),
+}
+
+#[derive(Default)]
+pub struct Queries {
+    revision: u64,
+    memos: HashMap<QueryId, Memo>,
+    stack: Vec<(QueryId, Vec<QueryId>)>,
+    executed: Vec<QueryId>,
+}
+
+#[derive(Debug)]
+pub struct Source;
+impl Query for Source {
+    type Key = PathBuf;
+    type Value = Option<String>;
+
+    const INPUT: bool = true;
+
+    fn execute(compiler: &mut CompilerCtx, key: &PathBuf) -> Option<String> {
+        compiler.read(key)
+    }
+
+    fn cycle(_: &PathBuf) -> Option<String> {
+        None
+    }
+}
+
+impl CompilerCtx {
+    pub fn query<Q: Query>(&mut self, key: Q::Key) -> Arc<Q::Value> {
+        let id = QueryId::new::<Q>(&key);
+        if let Some((_, dependencies)) = self.queries.stack.last_mut() {
+            dependencies.push(id.clone());
+        }
+        self.fetch::<Q>(id, key)
+    }
+    pub fn invalidate(&mut self, relative_path: &PathBuf) {
+        self.queries.revision += 1;
+        let id = QueryId::new::<Source>(relative_path);
+        if let Some(memo) = self.queries.memos.get_mut(&id) {
+            memo.dirty = true;
+        }
+    }
+    pub fn take_executed(&mut self) -> Vec<QueryId> {
+        std::mem::take(&mut self.queries.executed)
+    }
+    pub fn executed<Q: Query>(&self, key: &Q::Key) -> bool {
+        self.queries.executed.contains(&QueryId::new::<Q>(key))
+    }
+
+    fn fetch<Q: Query>(&mut self, id: QueryId, key: Q::Key) -> Arc<Q::Value> {
+        if self.verify(&id) {
+            return self.memoized::<Q>(&id);
+        }
+
+        if self.queries.stack.iter().any(|(active, _)| *active == id) {
+            let cycle: Vec<String> = self
+                .queries
+                .stack
+                .iter()
+                .map(|(id, _)| format!("{}({})", id.name, id.key))
+                .collect();
+            let root = self.root();
+            self.diagnostics.insert(
+                &root,
+                DiagnosticData::error().title(format!(
+                    "Query cycle detected: {} -> {}({})",
+                    cycle.join(" -> "),
+                    id.name,
+                    id.key
+                )),
+            );
+            return Arc::new(Q::cycle(&key));
+        }
+
+        self.queries.stack.push((id.clone(), Vec::new()));
+        let value = Q::execute(self, &key);
+        let (_, dependencies) = self.queries.stack.pop().unwrap();
+
+        let revision = self.queries.revision;
+        let fingerprint = Q::fingerprint(&value);
+        let changed_at = match self.queries.memos.get(&id) {
+            Some(memo) if memo.fingerprint == fingerprint => memo.changed_at,
+            _ => revision,
+        };
+
+        let value = Arc::new(value);
+        self.queries.memos.insert(
+            id.clone(),
+            Memo {
+                value: value.clone(),
+                key: Arc::new(key),
+                fingerprint,
+                dependencies,
+                verified_at: revision,
+                changed_at,
+                input: Q::INPUT,
+                dirty: false,
+                update: update::<Q>,
+            },
+        );
+        self.queries.executed.push(id);
+        value
+    }
+    fn verify(&mut self, id: &QueryId) -> bool {
+        let revision = self.queries.revision;
+        let (verified_at, dependencies) = match self.queries.memos.get_mut(id) {
+            None => return false,
+            Some(memo) if memo.verified_at == revision => return true,
+            Some(memo) if memo.input => {
+                memo.verified_at = revision;
+                return !memo.dirty;
+            }
+            Some(memo) => (memo.verified_at, memo.dependencies.clone()),
+        };
+
+        for dependency in dependencies {
+            let (update, key) = match self.queries.memos.get(&dependency) {
+                Some(memo) => (memo.update, memo.key.clone()),
+                None => return false,
+            };
+            update(self, key);
+
+            match self.queries.memos.get(&dependency) {
+                Some(memo) if memo.changed_at <= verified_at => {}
+                _ => return false,
+            }
+        }
+
+        if let Some(memo) = self.queries.memos.get_mut(id) {
+            memo.verified_at = revision;
+        }
+        true
+    }
+    fn memoized<Q: Query>(&self, id: &QueryId) -> Arc<Q::Value> {
+        self.queries.memos[id]
+            .value
+            .clone()
+            .downcast::<Q::Value>()
+            .expect("query values are stored by their query type")
+    }
+}
+
+fn update<Q: Query>(compiler: &mut CompilerCtx, key: Erased) {
+    let key = key
+        .downcast_ref::<Q::Key>()
+        .expect("query keys are stored by their query type")
+        .clone();
+    compiler.fetch::<Q>(QueryId::new::<Q>(&key), key);
+}
@@ -0,0 +1,73 @@
+use std::{fmt::Display, str::FromStr};
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Arch {
+    x86_64,
+    x86,
+    arm_64,
+    arm,
+}
+impl Default for Arch {
+    fn default() -> Self {
+        if cfg!(target_arch = "x86") {
+            Arch::x86
+        } else if cfg!(target_arch = "aarch64") {
+            Arch::arm_64
+        } else if cfg!(target_arch = "arm") {
+            Arch::arm
+        } else {
+            Arch::x86_64
+        }
+    }
+}
+impl Arch {
+    pub fn triple(&self) -> &'static str {
+        match self {
+            Arch::x86_64 => "x86_64-unknown-linux-gnu",
+            Arch::x86 => "i686-unknown-linux-gnu",
+            Arch::arm_64 => "aarch64-unknown-linux-gnu",
+            Arch::arm => "armv7-unknown-linux-gnueabihf",
+        }
+    }
+    pub fn data_layout(&self) -> &'static str {
+        match self {
+            Arch::x86_64 => {
+                "e-m:e-p270:32:32-p271:32:32-p272:64:64-i64:64-i128:128-f80:128-n8:16:32:64-S128"
+            }
+            Arch::x86 => {
+                "e-m:e-p:32:32-p270:32:32-p271:32:32-p272:64:64-i128:128-f64:32:64-f80:32-n8:16:32-S128"
+            }
+            Arch::arm_64 => "e-m:e-i8:8:32-i16:16:32-i64:64-i128:128-n32:64-S128",
+            Arch::arm => "e-m:e-p:32:32-Fi8-i64:64-v128:64:128-a:0:32-n32-S64",
+        }
+    }
+    pub fn pointer_width(&self) -> u8 {
+        match self {
+            Arch::x86_64 | Arch::arm_64 => 64,
+            Arch::x86 | Arch::arm => 32,
+        }
+    }
+}
+impl FromStr for Arch {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(match s {
+            "x86_64" => Arch::x86_64,
+            "x86" => Arch::x86,
+            "arm_64" => Arch::arm_64,
+            "arm" => Arch::arm,
+            _ => {
+                return Err(format!(
+                    "unknown target '{s}', expected: x86_64, x86, arm_64, arm"
+                ));
+            }
+        })
+    }
+}
+impl Display for Arch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use context::{
+        CompilerCtx,
+        files::{MockResolver, OverlayResolver},
+    };
+
+    fn project(name: &str) -> PathBuf {
+        let project = std::env::temp_dir().join(format!("eclipse-files-{name}"));
+        let _ = std::fs::remove_dir_all(&project);
+        std::fs::create_dir_all(project.join("src")).unwrap();
+        std::fs::write(project.join("src/main.ecl"), "func main() {}").unwrap();
+        std::fs::write(project.join("src/foo.ecl"), "").unwrap();
+        project
+    }
+
+    fn overlay(project: PathBuf) -> CompilerCtx {
+        CompilerCtx::builder()
+            .project_path(project)
+            .resolver(OverlayResolver::new())
+            .build()
+    }
+
+    #[test]
+    fn overlay_prefers_memory() {
+        let project = project("memory");
+        let mut compiler = overlay(project.clone());
+        let main = PathBuf::from("src/main.ecl");
+        assert_eq!(compiler.read(&main).unwrap(), "func main() {}");
+
+        compiler.write(&main, "func main() i32 { return 1 }");
+        assert_eq!(
+            compiler.read(&main).unwrap(),
+            "func main() i32 { return 1 }"
+        );
+        assert_eq!(
+            std::fs::read_to_string(project.join(&main)).unwrap(),
+            "func main() {}"
+        );
+
+        compiler.discard(&main);
+        assert_eq!(compiler.read(&main).unwrap(), "func main() {}");
+    }
+
+    #[test]
+    fn overlay_exists_and_list() {
+        let mut compiler = overlay(project("list"));
+        compiler.write(&PathBuf::from("src/bar/mod.ecl"), "");
+
+        assert!(compiler.exists(&PathBuf::from("src/foo.ecl")));
+        assert!(compiler.exists(&PathBuf::from("src/bar/mod.ecl")));
+        assert!(!compiler.exists(&PathBuf::from("src/bar")));
+        assert!(!compiler.exists(&PathBuf::from("src/baz.ecl")));
+
+        assert_eq!(
+            compiler.list(&PathBuf::from("src")),
+            vec![
+                PathBuf::from("src/bar"),
+                PathBuf::from("src/foo.ecl"),
+                PathBuf::from("src/main.ecl"),
+            ]
+        );
+        assert!(compiler.list(&PathBuf::from("src/baz")).is_empty());
+    }
+
+    #[test]
+    fn mock_list() {
+        let mut compiler = CompilerCtx::builder()
+            .project_path(PathBuf::new())
+            .resolver(MockResolver::new())
+            .build();
+        compiler.write(&PathBuf::from("src/main.ecl"), "");
+        compiler.write(&PathBuf::from("src/foo/bar.ecl"), "");
+        compiler.write(&PathBuf::from("src/foo/baz.ecl"), "");
+
+        assert_eq!(
+            compiler.list(&PathBuf::from("src")),
+            vec![PathBuf::from("src/foo"), PathBuf::from("src/main.ecl")]
+        );
+        assert_eq!(compiler.list(&PathBuf::new()), vec![PathBuf::from("src")]);
+    }
+
+    #[test]
+    fn write_error() {
+        let project = project("error");
+        let mut compiler = CompilerCtx::builder().project_path(project).build();
+        compiler.write(&PathBuf::from("src/main.ecl/inner.ecl"), "");
+
+        assert!(compiler.diagnostics.has_errors());
+        assert!(compiler.diagnostics.render().contains("Failed to write"));
+    }
+}
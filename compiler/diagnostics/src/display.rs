@@ -3,6 +3,8 @@ use std::{
     path::PathBuf,
 };
 
+use common::position::PositionRange;
+
 use super::{DiagnosticData, DiagnosticLevel, DiagnosticsFile};
 
 impl DiagnosticsFile {
@@ -19,27 +21,38 @@ impl DiagnosticData {
     fn display(&self, path: &PathBuf) -> String {
         let level = &self.level;
         let title = &self.title;
-        let position = match &self.position {
-            Some(p) => format!(
-                ":{}:{}-{}:{}",
-                p.start.line, p.start.column, p.end.line, p.end.column
-            ),
-            None => String::new(),
-        };
+        let position = display_position(&self.position);
 
         let span = self
             .spans
             .iter()
-            .map(|note| format!("{}", note.message))
+            .map(|note| match &note.path {
+                Some(p) => format!(
+                    "{}\n\t--> {p:?}{}",
+                    note.message,
+                    display_position(&note.position)
+                ),
+                None => format!("{}", note.message),
+            })
             .collect::<Vec<String>>()
             .join("\n");
 
         format!("{level}: {title}\n\t--> {path:?}{position}\n{span}")
     }
 }
+fn display_position(position: &Option<PositionRange>) -> String {
+    match position {
+        Some(p) => format!(
+            ":{}:{}-{}:{}",
+            p.start.line, p.start.column, p.end.line, p.end.column
+        ),
+        None => String::new(),
+    }
+}
+
 impl Debug for DiagnosticData {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.display(&PathBuf::from("/unkown")))
+        write!(f, "{}", self.display(&PathBuf::from("/unknown")))
     }
 }
 
@@ -7,7 +7,13 @@ impl DiagnosticsFile {
         }
     }
     pub fn insert(&mut self, diagnostic: DiagnosticData) {
-        self.diagnostics.push(diagnostic);
+        let reported = self
+            .diagnostics
+            .iter()
+            .any(|d| d.title == diagnostic.title && d.position == diagnostic.position);
+        if !reported {
+            self.diagnostics.push(diagnostic);
+        }
     }
     // pub fn capture<T>(&mut self, result: DiagnosticResult<T>) -> Option<T> {
     //     let error = match result {
@@ -1,5 +1,6 @@
 use common::position::PositionRange;
-use std::{collections::HashMap, path::PathBuf};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::PathBuf};
 
 pub type DiagnosticResult<T = ()> = Result<T, DiagnosticData>;
 
@@ -7,7 +8,7 @@ pub mod builder;
 mod display;
 mod file;
 
-#[derive(Default, PartialEq)]
+#[derive(Default, Clone, PartialEq, Serialize, Deserialize)]
 pub enum DiagnosticLevel {
     #[default]
     Error,
@@ -16,14 +17,14 @@ pub enum DiagnosticLevel {
     Note,
 }
 
-#[derive(Default)]
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct DiagnosticSpan {
     path: Option<PathBuf>,
     position: Option<PositionRange>,
     message: String,
 }
 
-#[derive(Default)]
+#[derive(Default, Clone, Serialize, Deserialize)]
 pub struct DiagnosticData {
     level: DiagnosticLevel,
     position: Option<PositionRange>,
@@ -38,7 +39,7 @@ pub struct DiagnosticsFile {
 
 #[derive(Default)]
 pub struct Diagnostics {
-    files: HashMap<PathBuf, DiagnosticsFile>,
+    files: BTreeMap<PathBuf, DiagnosticsFile>,
 }
 
 impl Diagnostics {
@@ -46,11 +47,13 @@ impl Diagnostics {
         Self::default()
     }
     pub fn check(&self) {
-        if !self.has_errors() {
+        if self.is_empty() {
             return;
         }
         self.display();
-        std::process::exit(0)
+        if self.has_errors() {
+            std::process::exit(1)
+        }
     }
     pub fn file(&mut self, relative_path: &PathBuf) -> &mut DiagnosticsFile {
         self.files
@@ -62,14 +65,35 @@ impl Diagnostics {
         file.insert(diagnostic);
     }
     pub fn display(&self) {
-        println!(
-            "{}",
-            self.files
-                .iter()
-                .map(|(p, f)| f.display(p))
-                .collect::<Vec<String>>()
-                .join("\n")
-        );
+        println!("{}", self.render());
+    }
+    pub fn render(&self) -> String {
+        self.files
+            .iter()
+            .map(|(p, f)| f.display(p))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+    pub fn entries(self) -> Vec<(PathBuf, DiagnosticData)> {
+        self.files
+            .into_iter()
+            .flat_map(|(path, file)| file.diagnostics.into_iter().map(move |d| (path.clone(), d)))
+            .collect()
+    }
+    pub fn warnings(&self, relative_path: &PathBuf) -> Vec<DiagnosticData> {
+        self.files
+            .get(relative_path)
+            .map(|file| {
+                file.diagnostics
+                    .iter()
+                    .filter(|d| d.level == DiagnosticLevel::Warning)
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+    pub fn is_empty(&self) -> bool {
+        self.files.values().all(|f| f.diagnostics.is_empty())
     }
     pub fn has_errors(&self) -> bool {
         for (_, file) in &self.files {
@@ -0,0 +1,178 @@
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    hash::{DefaultHasher, Hash, Hasher},
+    path::PathBuf,
+};
+
+use context::CompilerCtx;
+use syntax::{ast, format::join};
+
+struct Interfaces<'a> {
+    collection: &'a ast::ModuleCollection,
+    hashes: HashMap<&'a PathBuf, u64>,
+    visiting: HashSet<&'a PathBuf>,
+}
+
+pub fn input_hashes(
+    compiler: &CompilerCtx,
+    collection: &ast::ModuleCollection,
+) -> HashMap<PathBuf, u64> {
+    let mut interfaces = Interfaces {
+        collection,
+        hashes: HashMap::new(),
+        visiting: HashSet::new(),
+    };
+
+    let build = build_hash(compiler);
+    let impls = impl_hash(collection);
+    collection
+        .modules
+        .keys()
+        .map(|path| {
+            let mut hasher = DefaultHasher::new();
+            collection.hashes.get(path).hash(&mut hasher);
+            build.hash(&mut hasher);
+            impls.hash(&mut hasher);
+            for dependency in dependencies(collection, path) {
+                dependency.hash(&mut hasher);
+                interfaces.hash(dependency).hash(&mut hasher);
+            }
+            (path.clone(), hasher.finish())
+        })
+        .collect()
+}
+
+fn build_hash(compiler: &CompilerCtx) -> u64 {
+    let config = &compiler.config;
+    let mut hasher = DefaultHasher::new();
+    config.package.name.hash(&mut hasher);
+    config.package.version.hash(&mut hasher);
+    config.dependencies.hash(&mut hasher);
+    compiler.target().hash(&mut hasher);
+    compiler.backend().hash(&mut hasher);
+    compiler.is_release().hash(&mut hasher);
+    compiler.opt_level().hash(&mut hasher);
+    compiler.output().hash(&mut hasher);
+    hasher.finish()
+}
+
+fn impl_hash(collection: &ast::ModuleCollection) -> u64 {
+    let mut paths: Vec<&PathBuf> = collection.modules.keys().collect();
+    paths.sort();
+
+    let mut hasher = DefaultHasher::new();
+    for path in paths {
+        for node in &collection.modules[path].nodes {
+            if let ast::RawNode::Impl { .. } = node.raw.item() {
+                path.hash(&mut hasher);
+                signature(&node.raw).hash(&mut hasher);
+            }
+        }
+    }
+    hasher.finish()
+}
+
+fn dependencies<'a>(
+    collection: &'a ast::ModuleCollection,
+    path: &PathBuf,
+) -> BTreeSet<&'a PathBuf> {
+    let names = &collection.names;
+    let mut dependencies: BTreeSet<&PathBuf> = collection
+        .dependencies
+        .get(path)
+        .into_iter()
+        .flatten()
+        .map(|dependency| &dependency.path)
+        .collect();
+
+    for id in names
+        .bindings
+        .get(path)
+        .into_iter()
+        .flat_map(|b| b.values())
+    {
+        let definition = names.definition(*id);
+        dependencies.insert(&definition.path);
+        if let ast::DefinitionKind::Module(module) = &definition.kind {
+            dependencies.insert(module);
+        }
+    }
+
+    dependencies.retain(|dependency| *dependency != path);
+    dependencies
+}
+
+impl<'a> Interfaces<'a> {
+    fn hash(&mut self, path: &'a PathBuf) -> u64 {
+        if let Some(hash) = self.hashes.get(path) {
+            return *hash;
+        }
+        if !self.visiting.insert(path) {
+            return 0;
+        }
+
+        let mut hasher = DefaultHasher::new();
+        if let Some(module) = self.collection.modules.get(path) {
+            for node in module.nodes.iter().filter(|n| n.raw.is_public()) {
+                signature(&node.raw).hash(&mut hasher);
+            }
+        }
+        for dependency in self.collection.dependencies.get(path).into_iter().flatten() {
+            if dependency.public {
+                dependency.name.hash(&mut hasher);
+                self.hash(&dependency.path).hash(&mut hasher);
+            }
+        }
+
+        let hash = hasher.finish();
+        self.visiting.remove(path);
+        self.hashes.insert(path, hash);
+        hash
+    }
+}
+
+fn signature(raw: &ast::RawNode) -> String {
+    use ast::RawNode;
+
+    match raw {
+        RawNode::Modifiers(modifiers, node) => format!(
+            "{} {}",
+            join(modifiers.iter().map(|m| &m.raw), " "),
+            signature(&node.raw)
+        ),
+        RawNode::Function {
+            name,
+            generics,
+            parameters,
+            return_type,
+            ..
+        } => format!(
+            "func {}{}({}) {}",
+            name.raw,
+            ast::generic_list(generics),
+            join(parameters.iter().map(|p| &p.raw), ", "),
+            return_type.raw
+        ),
+        RawNode::Trait { name, methods } => format!(
+            "trait {} {{{}}}",
+            name.raw,
+            join(methods.iter().map(|m| signature(&m.raw)), "; ")
+        ),
+        RawNode::Impl {
+            trait_name,
+            data_type,
+            methods,
+        } => {
+            let trait_name = match trait_name {
+                Some(name) => format!("{} for ", name.raw),
+                None => String::new(),
+            };
+            format!(
+                "impl {trait_name}{} {{{}}}",
+                data_type.raw,
+                join(methods.iter().map(|m| signature(&m.raw)), "; ")
+            )
+        }
+        raw => raw.to_string(),
+    }
+}
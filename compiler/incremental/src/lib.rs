@@ -0,0 +1,91 @@
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+use context::CompilerCtx;
+use diagnostics::DiagnosticData;
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use syntax::{ast, hir};
+
+mod interface;
+
+pub use interface::input_hashes;
+
+pub const INCREMENTAL_DIRECTORY: &str = "incremental";
+
+#[derive(Serialize, Deserialize)]
+struct Entry<T> {
+    hash: u64,
+    value: T,
+}
+
+pub fn source_hash(source: &str) -> u64 {
+    hash(&(env!("CARGO_PKG_VERSION"), source))
+}
+
+pub fn load_ast(compiler: &CompilerCtx, relative_path: &Path, hash: u64) -> Option<Vec<ast::Node>> {
+    load(compiler, relative_path, "ast", hash)
+}
+
+pub fn store_ast(compiler: &mut CompilerCtx, relative_path: &Path, hash: u64, nodes: &[ast::Node]) {
+    store(compiler, relative_path, "ast", hash, nodes)
+}
+
+pub fn load_hir(
+    compiler: &CompilerCtx,
+    relative_path: &Path,
+    hash: u64,
+) -> Option<(hir::Module, Vec<DiagnosticData>)> {
+    load(compiler, relative_path, "hir", hash)
+}
+
+pub fn store_hir(
+    compiler: &mut CompilerCtx,
+    relative_path: &Path,
+    hash: u64,
+    module: &hir::Module,
+    warnings: &[DiagnosticData],
+) {
+    store(compiler, relative_path, "hir", hash, &(module, warnings))
+}
+
+pub fn entry_path(relative_path: &Path, kind: &str) -> PathBuf {
+    let stem = relative_path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    PathBuf::from("target")
+        .join(INCREMENTAL_DIRECTORY)
+        .join(format!("{stem}-{:016x}.{kind}.json", hash(relative_path)))
+}
+
+fn load<T: DeserializeOwned>(
+    compiler: &CompilerCtx,
+    relative_path: &Path,
+    kind: &str,
+    hash: u64,
+) -> Option<T> {
+    let source = compiler.read(&entry_path(relative_path, kind))?;
+    let entry: Entry<T> = serde_json::from_str(&source).ok()?;
+    (entry.hash == hash).then_some(entry.value)
+}
+
+fn store<T: Serialize + ?Sized>(
+    compiler: &mut CompilerCtx,
+    relative_path: &Path,
+    kind: &str,
+    hash: u64,
+    value: &T,
+) {
+    if let Ok(source) = serde_json::to_string(&Entry { hash, value }) {
+        compiler.write(&entry_path(relative_path, kind), &source);
+    }
+}
+
+fn hash(value: &(impl Hash + ?Sized)) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
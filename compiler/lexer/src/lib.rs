@@ -142,6 +142,8 @@ impl TryFrom<&TokenKind> for ArithmeticOperator {
             Asterisk => ArithmeticOperator::Multiply,
             ForwardSlash => ArithmeticOperator::Division,
             Percent => ArithmeticOperator::Remainder,
+            LeftBitshift => ArithmeticOperator::LeftBitshift,
+            RightBitshift => ArithmeticOperator::RightBitshift,
             _ => return Err(ConversionError),
         };
 
@@ -77,6 +77,6 @@ impl Reader {
         self.chars.last()
     }
     pub fn peek_second(&self) -> Option<&Character> {
-        self.chars.get(self.chars.len() - 2)
+        self.chars.iter().rev().nth(1)
     }
 }
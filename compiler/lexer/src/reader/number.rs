@@ -9,7 +9,7 @@ impl Reader {
         let integer = self.parse_integer()?;
 
         if match self.peek() {
-            Some(char) if char.raw == '.' => match self.peek() {
+            Some(char) if char.raw == '.' => match self.peek_second() {
                 Some(char) if char.raw.is_ascii_digit() => false,
                 _ => true,
             },
@@ -18,6 +18,7 @@ impl Reader {
             return Ok(Some(LexerKind::Integer(integer)));
         }
 
+        self.advance();
         let second = self.parse_integer()?;
 
         let position = integer.position.start.extend(second.position.end);
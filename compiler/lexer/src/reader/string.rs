@@ -1,4 +1,4 @@
-use diagnostics::DiagnosticResult;
+use diagnostics::{DiagnosticData, DiagnosticResult};
 
 use crate::kind::{LexerKind, LocatedString};
 
@@ -17,7 +17,29 @@ impl Reader {
             };
 
             delimiter.position.set_end(char.position.end);
-            body.push(char.raw);
+            if char.raw != '\\' {
+                body.push(char.raw);
+                continue;
+            }
+
+            let escaped = match self.advance() {
+                Some(escaped) => escaped,
+                None => break,
+            };
+            delimiter.position.set_end(escaped.position.end);
+            body.push(match escaped.raw {
+                'n' => '\n',
+                't' => '\t',
+                'r' => '\r',
+                '0' => '\0',
+                '\\' | '"' | '\'' => escaped.raw,
+                other => {
+                    return DiagnosticData::error()
+                        .title(format!("Unknown escape sequence '\\{other}'"))
+                        .position(escaped.position)
+                        .to_err();
+                }
+            });
         }
         self.advance();
 
@@ -43,11 +43,15 @@ pub enum TokenKind {
     Var,     // var
     Enum,    // enum
     Struct,  // struct
+    Trait,   // trait
+    Impl,    // impl
+    For,     // for
 
     Return, // return
     Result, // result
 
     Ampersand,       // &
+    Pipe,            // |
     CommercialAt,    // @
     NumberSign,      // #
     SemiColon,       // ;
@@ -58,6 +62,7 @@ pub enum TokenKind {
     Equals,          // =
     Comma,           // ,
     ExclamationMark, // !
+    QuestionMark,    // ?
     Arrow,           // ->
     FatArrow,        // =>
 
@@ -84,6 +89,8 @@ pub enum TokenKind {
     Continue, // continue
     Break,    // break
     Function, // func
+    Move,     // move
+    As,       // as
 
     Range,               // ..
     RangeEquals,         // ..=
@@ -123,7 +130,7 @@ impl TokenKind {
         use TokenKind::*;
 
         match self {
-            OpenParen | Minus | If | While | Loop => true,
+            OpenParen | Minus | Asterisk | If | While | Loop | Pipe | Or | Move => true,
             _ if self.is_expression() => true,
             _ => false,
         }
@@ -178,6 +185,9 @@ pub fn match_token(word: &String) -> Option<TokenKind> {
 
         "enum" => Enum,
         "struct" => Struct,
+        "trait" => Trait,
+        "impl" => Impl,
+        "for" => For,
 
         "func" => Function,
         "import" => Import,
@@ -189,6 +199,8 @@ pub fn match_token(word: &String) -> Option<TokenKind> {
         "break" => Break,
         "continue" => Continue,
         "self" => SelfKeyword,
+        "move" => Move,
+        "as" => As,
 
         "{" => OpenCurlyBracket,
         "}" => CloseCurlyBracket,
@@ -198,8 +210,10 @@ pub fn match_token(word: &String) -> Option<TokenKind> {
         "]" => CloseBracket,
 
         "&" => Ampersand,
+        "|" => Pipe,
         "_" => Underscore,
         "!" => ExclamationMark,
+        "?" => QuestionMark,
         "'" => Apostrophe,
         "@" => CommercialAt,
         "#" => NumberSign,
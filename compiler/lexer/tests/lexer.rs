@@ -94,11 +94,41 @@ mod tests {
         "var x = 1234",
         [Var, Identifier, Equals, Integer]
     );
+    lexer_test!(
+        closure,
+        "move |x| x",
+        [Move, Pipe, Identifier, Pipe, Identifier]
+    );
+    lexer_test!(
+        try_call,
+        "f()?)",
+        [Identifier, OpenParen, CloseParen, QuestionMark, CloseParen]
+    );
     lexer_test!(not_a_float, "1.b", [Integer, Dot, Identifier]);
+    lexer_test!(float, "2.75 * 1", [Float, Asterisk, Integer]);
+    lexer_test!(trailing_dot, "1.", [Integer, Dot]);
+    lexer_test!(
+        cast,
+        "a + b as i64",
+        [Identifier, Plus, Identifier, As, Identifier]
+    );
     lexer_test!(block, "{  }", [OpenCurlyBracket, CloseCurlyBracket]);
     lexer_test!(
         integer_after_string_literal,
         "\"hello\" 1234",
         [Text, Integer]
     );
+
+    #[test]
+    fn string_escapes() {
+        let tokens = tokenize(r#""a\tb\n\"c\"\\""#).unwrap();
+        assert_eq!(tokens[0].kind, Text);
+        assert_eq!(tokens[0].string, "a\tb\n\"c\"\\");
+    }
+
+    #[test]
+    fn unknown_escape() {
+        let error = tokenize(r#""\q""#).unwrap_err();
+        assert!(format!("{error:?}").contains("Unknown escape sequence '\\q'"));
+    }
 }
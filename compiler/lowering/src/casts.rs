@@ -0,0 +1,60 @@
+use std::cmp::Ordering;
+
+use syntax::{hir, mir};
+
+use crate::{Lowering, function::Function};
+
+impl Lowering {
+    pub fn lower_cast(
+        &mut self,
+        body: &mut Function,
+        value: hir::Node,
+        from: hir::Type,
+        data_type: hir::Type,
+    ) -> mir::Expression {
+        let source = self.lower_type(from.clone());
+        let value = self.lower_expression(body, value, &source);
+        let target = self.lower_type(data_type.clone());
+        if source == target {
+            return value;
+        }
+
+        let ordering = width(&source).cmp(&width(&target));
+        let kind = match (from.is_float(), data_type.is_float()) {
+            (false, false) => match ordering {
+                Ordering::Greater => mir::CastKind::Truncate,
+                Ordering::Less if from.is_signed() => mir::CastKind::SignExtend,
+                Ordering::Less => mir::CastKind::ZeroExtend,
+                Ordering::Equal => mir::CastKind::Bitcast,
+            },
+            (false, true) if from.is_signed() => mir::CastKind::SignedToFloat,
+            (false, true) => mir::CastKind::UnsignedToFloat,
+            (true, false) if data_type.is_signed() => mir::CastKind::FloatToSigned,
+            (true, false) => mir::CastKind::FloatToUnsigned,
+            (true, true) => match ordering {
+                Ordering::Greater => mir::CastKind::FloatTruncate,
+                _ => mir::CastKind::FloatExtend,
+            },
+        };
+
+        let value = match kind {
+            mir::CastKind::FloatToSigned | mir::CastKind::FloatToUnsigned => {
+                self.operand(body, value)
+            }
+            _ => value,
+        };
+        mir::Expression::Cast {
+            kind,
+            value: Box::new(value),
+            data_type: target,
+        }
+    }
+}
+
+fn width(data_type: &mir::Type) -> u8 {
+    match data_type {
+        mir::Type::Boolean => 1,
+        mir::Type::Int(bits) | mir::Type::UInt(bits) | mir::Type::Float(bits) => *bits,
+        data_type => panic!("Cannot cast a value of type {data_type}"),
+    }
+}
@@ -0,0 +1,167 @@
+use common::position::PositionRange;
+use syntax::{
+    mir,
+    operators::{ArithmeticOperator, CompareOperator, Operator},
+};
+
+use crate::{Lowering, function::Function};
+
+impl Lowering {
+    pub fn operand(&mut self, body: &mut Function, value: mir::Expression) -> mir::Expression {
+        match value {
+            mir::Expression::Integer(..) => value,
+            value => self.spill(body, value),
+        }
+    }
+    pub fn check_arithmetic(
+        &mut self,
+        body: &mut Function,
+        operator: ArithmeticOperator,
+        left: &mir::Expression,
+        right: &mir::Expression,
+        position: PositionRange,
+    ) {
+        let data_type = left.data_type();
+        let (bits, signed) = match data_type {
+            mir::Type::Int(bits) => (bits, true),
+            mir::Type::UInt(bits) => (bits, false),
+            _ => panic!("Expected an integer operand, got {data_type}"),
+        };
+        let integer = |value: String| mir::Expression::Integer(data_type.clone(), value);
+        let constant = match right {
+            mir::Expression::Integer(_, value) => value.parse::<i128>().ok(),
+            _ => None,
+        };
+
+        match operator {
+            ArithmeticOperator::Plus
+            | ArithmeticOperator::Subtract
+            | ArithmeticOperator::Multiply => {
+                let message = match operator {
+                    ArithmeticOperator::Plus => "attempt to add with overflow",
+                    ArithmeticOperator::Subtract => "attempt to subtract with overflow",
+                    _ => "attempt to multiply with overflow",
+                };
+                let condition = overflow(operator, left.clone(), right.clone());
+                self.check(body, condition, message, position);
+            }
+            ArithmeticOperator::Division | ArithmeticOperator::Remainder => {
+                let (zero, overflows) = match operator {
+                    ArithmeticOperator::Division => (
+                        "attempt to divide by zero",
+                        "attempt to divide with overflow",
+                    ),
+                    _ => (
+                        "attempt to calculate the remainder with a divisor of zero",
+                        "attempt to calculate the remainder with overflow",
+                    ),
+                };
+                if constant.is_none_or(|value| value == 0) {
+                    let condition = compare(
+                        CompareOperator::Compare,
+                        right.clone(),
+                        integer("0".to_string()),
+                    );
+                    self.check(body, condition, zero, position);
+                }
+                if !signed || constant.is_some_and(|value| value != -1) {
+                    return;
+                }
+
+                let negative = compare(
+                    CompareOperator::Compare,
+                    right.clone(),
+                    integer("-1".to_string()),
+                );
+                let minimum = overflow(
+                    ArithmeticOperator::Subtract,
+                    integer("0".to_string()),
+                    left.clone(),
+                );
+                let condition = compare(CompareOperator::And, negative, minimum);
+                self.check(body, condition, overflows, position);
+            }
+            ArithmeticOperator::LeftBitshift | ArithmeticOperator::RightBitshift => {
+                if constant.is_some_and(|value| (0..bits as i128).contains(&value)) {
+                    return;
+                }
+                let message = match operator {
+                    ArithmeticOperator::LeftBitshift => "attempt to shift left with overflow",
+                    _ => "attempt to shift right with overflow",
+                };
+                let mut condition = compare(
+                    CompareOperator::GreaterThanOrEquals,
+                    right.clone(),
+                    integer(bits.to_string()),
+                );
+                if signed {
+                    let negative = compare(
+                        CompareOperator::LessThan,
+                        right.clone(),
+                        integer("0".to_string()),
+                    );
+                    condition = compare(CompareOperator::Or, negative, condition);
+                }
+                self.check(body, condition, message, position);
+            }
+        }
+    }
+    fn check(
+        &mut self,
+        body: &mut Function,
+        condition: mir::Expression,
+        message: &str,
+        position: PositionRange,
+    ) {
+        let message = format!(
+            "panic at {}:{}:{}: {message}",
+            self.context.path.display(),
+            position.start.line,
+            position.start.column
+        );
+
+        let (failure, success) = body.fork();
+        body.push(mir::Node::Branch {
+            condition,
+            then: failure.clone(),
+            otherwise: success.clone(),
+        });
+
+        body.blocks.push(mir::Block::new(failure));
+        let panic = self.panic();
+        let message = self.lower_string(message);
+        body.push(mir::Node::Expression(mir::Expression::Call {
+            function: panic.to_string(),
+            arguments: vec![message],
+            data_type: mir::Type::Void,
+        }));
+        body.push(mir::Node::Unreachable);
+
+        body.blocks.push(mir::Block::new(success));
+    }
+}
+
+fn overflow(
+    operator: ArithmeticOperator,
+    left: mir::Expression,
+    right: mir::Expression,
+) -> mir::Expression {
+    mir::Expression::Overflow {
+        operator,
+        left: Box::new(left),
+        right: Box::new(right),
+    }
+}
+
+fn compare(
+    operator: CompareOperator,
+    left: mir::Expression,
+    right: mir::Expression,
+) -> mir::Expression {
+    mir::Expression::Binary {
+        operator: Operator::Compare(operator),
+        left: Box::new(left),
+        right: Box::new(right),
+        data_type: mir::Type::Boolean,
+    }
+}
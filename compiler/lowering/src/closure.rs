@@ -0,0 +1,405 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use syntax::{
+    hir,
+    mir::{self, FREE, MALLOC},
+    operators::{CompareOperator, Operator},
+};
+
+use crate::{Lowering, function::Function};
+
+const ENVIRONMENT: &str = "$env";
+
+#[derive(Default)]
+pub struct Context {
+    function: String,
+    pub path: PathBuf,
+    closures: usize,
+    locals: usize,
+    pub captures: HashMap<String, (String, usize, bool)>,
+}
+impl Context {
+    pub fn new(function: String, path: PathBuf) -> Self {
+        Self {
+            function,
+            path,
+            ..Default::default()
+        }
+    }
+}
+
+impl Lowering {
+    pub fn capture(&self, name: &str) -> Option<mir::Expression> {
+        let (structure, index, moves) = self.context.captures.get(name)?;
+        let field = mir::Expression::FieldPointer {
+            structure: structure.clone(),
+            base: Box::new(mir::Expression::Local(
+                mir::Type::Pointer,
+                ENVIRONMENT.to_string(),
+            )),
+            index: *index,
+        };
+
+        Some(match moves {
+            true => field,
+            false => mir::Expression::Load(mir::Type::Pointer, Box::new(field)),
+        })
+    }
+    pub fn spill(&mut self, body: &mut Function, value: mir::Expression) -> mir::Expression {
+        if let mir::Expression::Local(..) = value {
+            return value;
+        }
+
+        let data_type = value.data_type();
+        let name = self.temporary(body, data_type.clone());
+        body.push(mir::Node::Set {
+            name: name.clone(),
+            data_type: data_type.clone(),
+            value,
+        });
+        mir::Expression::Local(data_type, name)
+    }
+    pub fn temporary(&mut self, body: &mut Function, data_type: mir::Type) -> String {
+        let name = format!("$value{}", self.context.locals);
+        self.context.locals += 1;
+
+        body.push(mir::Node::Allocate {
+            name: name.clone(),
+            data_type,
+        });
+        name
+    }
+    pub fn lower_closure(
+        &mut self,
+        body: &mut Function,
+        closure: hir::Node,
+        boxed: bool,
+    ) -> mir::Expression {
+        let hir::Node::Closure {
+            moves,
+            parameters,
+            return_type,
+            captures,
+            body: nodes,
+            ..
+        } = closure
+        else {
+            panic!("Expected a closure, got {closure}");
+        };
+
+        let name = format!("{}$closure{}", self.context.function, self.context.closures);
+        self.context.closures += 1;
+
+        let structure = format!("{name}$env");
+        let offset = usize::from(boxed);
+        let environment = match (captures.is_empty(), boxed) {
+            (true, _) => None,
+            (false, true) => Some(self.heap_environment(body, &name, &captures, moves)),
+            (false, false) => Some(self.environment(body, &structure, &captures, moves)),
+        };
+
+        let mut mir_parameters = vec![mir::Parameter {
+            pointer: false,
+            name: ENVIRONMENT.to_string(),
+            data_type: mir::Type::Pointer,
+        }];
+        let owned = self.owned_parameters(&parameters);
+        mir_parameters.extend(parameters.into_iter().map(|p| self.lower_parameter(p)));
+        let return_type = self.lower_type(return_type);
+
+        let mut context = Context::new(name.clone(), self.context.path.clone());
+        context.captures = captures
+            .into_iter()
+            .enumerate()
+            .map(|(index, capture)| {
+                let field = (structure.clone(), index + offset, moves);
+                (capture.name, field)
+            })
+            .collect();
+        let context = std::mem::replace(&mut self.context, context);
+        self.lower_body(
+            true,
+            name.clone(),
+            mir_parameters,
+            owned,
+            return_type,
+            nodes,
+        );
+        self.context = context;
+
+        mir::Expression::Function {
+            function: name,
+            environment: environment.map(Box::new),
+        }
+    }
+    fn environment(
+        &mut self,
+        body: &mut Function,
+        structure: &str,
+        captures: &[hir::Capture],
+        moves: bool,
+    ) -> mir::Expression {
+        let fields = self.capture_types(captures, moves);
+        self.structs.push(mir::Struct {
+            name: structure.to_string(),
+            fields: fields.clone(),
+        });
+
+        let local = structure.replace("$env", "$environment");
+        body.push(mir::Node::Allocate {
+            name: local.clone(),
+            data_type: mir::Type::Struct(structure.to_string()),
+        });
+
+        for (index, (capture, data_type)) in captures.iter().zip(fields).enumerate() {
+            let value = self.capture_value(body, capture, &data_type, moves);
+            body.push(mir::Node::Store {
+                pointer: mir::Expression::FieldPointer {
+                    structure: structure.to_string(),
+                    base: Box::new(mir::Expression::Reference(local.clone())),
+                    index,
+                },
+                data_type,
+                value,
+            });
+        }
+
+        mir::Expression::Reference(local)
+    }
+    fn heap_environment(
+        &mut self,
+        body: &mut Function,
+        closure: &str,
+        captures: &[hir::Capture],
+        moves: bool,
+    ) -> mir::Expression {
+        let structure = format!("{closure}$env");
+        let mut fields = vec![mir::Type::Function];
+        fields.extend(self.capture_types(captures, moves));
+        self.structs.push(mir::Struct {
+            name: structure.clone(),
+            fields: fields.clone(),
+        });
+
+        let size = mir::Type::UInt(self.pointer_width);
+        self.runtime(MALLOC, &[("size", size.clone())], mir::Type::Pointer);
+        let allocation = mir::Expression::Call {
+            function: MALLOC.to_string(),
+            arguments: vec![mir::Expression::Integer(
+                size,
+                self.size_of(&mir::Type::Struct(structure.clone()))
+                    .to_string(),
+            )],
+            data_type: mir::Type::Pointer,
+        };
+        let base = self.spill(body, allocation);
+
+        let drop = self.drop_captures(closure, &structure, captures, moves);
+        body.push(mir::Node::Store {
+            pointer: mir::Expression::FieldPointer {
+                structure: structure.clone(),
+                base: Box::new(base.clone()),
+                index: 0,
+            },
+            data_type: mir::Type::Function,
+            value: mir::Expression::Function {
+                function: drop,
+                environment: Some(Box::new(base.clone())),
+            },
+        });
+        let fields = fields.into_iter().skip(1);
+        for (index, (capture, data_type)) in captures.iter().zip(fields).enumerate() {
+            let value = self.capture_value(body, capture, &data_type, moves);
+            body.push(mir::Node::Store {
+                pointer: mir::Expression::FieldPointer {
+                    structure: structure.clone(),
+                    base: Box::new(base.clone()),
+                    index: index + 1,
+                },
+                data_type,
+                value,
+            });
+        }
+        base
+    }
+    fn capture_types(&mut self, captures: &[hir::Capture], moves: bool) -> Vec<mir::Type> {
+        captures
+            .iter()
+            .map(|capture| match moves {
+                true => self.lower_type(capture.data_type.clone()),
+                false => mir::Type::Pointer,
+            })
+            .collect()
+    }
+    fn capture_value(
+        &mut self,
+        body: &mut Function,
+        capture: &hir::Capture,
+        data_type: &mir::Type,
+        moves: bool,
+    ) -> mir::Expression {
+        match moves {
+            true => match self.capture(&capture.name) {
+                Some(pointer) => mir::Expression::Load(data_type.clone(), Box::new(pointer)),
+                None => {
+                    if self.owned(&capture.data_type).is_some()
+                        && let Some(owned) = body.owned(&capture.name)
+                    {
+                        owned.live = false;
+                    }
+                    mir::Expression::Local(data_type.clone(), capture.name.clone())
+                }
+            },
+            false => match self.capture(&capture.name) {
+                Some(pointer) => pointer,
+                None => mir::Expression::Reference(capture.name.clone()),
+            },
+        }
+    }
+    fn drop_captures(
+        &mut self,
+        closure: &str,
+        structure: &str,
+        captures: &[hir::Capture],
+        moves: bool,
+    ) -> String {
+        let name = format!("{closure}$drop");
+        let environment = || mir::Expression::Local(mir::Type::Pointer, ENVIRONMENT.to_string());
+
+        let mut body = Function::new(mir::Type::Void);
+        for (index, capture) in captures.iter().enumerate() {
+            let Some(data_type) = self.owned(&capture.data_type).filter(|_| moves) else {
+                continue;
+            };
+            let field = mir::Expression::FieldPointer {
+                structure: structure.to_string(),
+                base: Box::new(environment()),
+                index: index + 1,
+            };
+            let value = mir::Expression::Load(self.lower_type(data_type.clone()), Box::new(field));
+            self.lower_drop(&mut body, value, &data_type);
+        }
+        self.runtime(FREE, &[("pointer", mir::Type::Pointer)], mir::Type::Void);
+        body.push(mir::Node::Expression(mir::Expression::Call {
+            function: FREE.to_string(),
+            arguments: vec![environment()],
+            data_type: mir::Type::Void,
+        }));
+        body.push(mir::Node::Return(None));
+
+        self.functions.push(mir::Function {
+            internal: true,
+            name: name.clone(),
+            parameters: vec![mir::Parameter {
+                pointer: false,
+                name: ENVIRONMENT.to_string(),
+                data_type: mir::Type::Pointer,
+            }],
+            return_type: mir::Type::Void,
+            blocks: body.blocks,
+        });
+        name
+    }
+    pub fn drop_environment(&mut self, body: &mut Function, pointer: mir::Expression) {
+        let function = mir::Expression::Load(mir::Type::Function, Box::new(pointer));
+        let environment = mir::Expression::Extract {
+            value: Box::new(function),
+            index: 1,
+            data_type: mir::Type::Pointer,
+        };
+
+        let (drop, next) = body.fork();
+        body.push(mir::Node::Branch {
+            condition: mir::Expression::Binary {
+                operator: Operator::Compare(CompareOperator::NotEquals),
+                left: Box::new(environment.clone()),
+                right: Box::new(mir::Expression::Null),
+                data_type: mir::Type::Boolean,
+            },
+            then: drop.clone(),
+            otherwise: next.clone(),
+        });
+
+        body.blocks.push(mir::Block::new(drop));
+        body.push(mir::Node::Expression(mir::Expression::Invoke {
+            callee: Box::new(mir::Expression::Load(
+                mir::Type::Function,
+                Box::new(environment),
+            )),
+            arguments: Vec::new(),
+            data_type: mir::Type::Void,
+        }));
+        body.push(mir::Node::Goto(next.clone()));
+
+        body.blocks.push(mir::Block::new(next));
+    }
+    pub fn function_value(&mut self, name: String, data_type: hir::Type) -> mir::Expression {
+        let function = format!("{name}$value");
+        let value = mir::Expression::Function {
+            function: function.clone(),
+            environment: None,
+        };
+        if !self.values.insert(function.clone()) {
+            return value;
+        }
+
+        let hir::Type::Function {
+            parameters,
+            return_type,
+        } = data_type
+        else {
+            panic!("Expected a function type for '{name}', got {data_type}");
+        };
+
+        let parameters: Vec<mir::Parameter> = parameters
+            .into_iter()
+            .enumerate()
+            .map(|(index, data_type)| {
+                self.lower_parameter(hir::Parameter {
+                    mutable: false,
+                    name: format!("a{index}"),
+                    data_type,
+                })
+            })
+            .collect();
+        let arguments = parameters
+            .iter()
+            .map(|parameter| match parameter.pointer {
+                true => mir::Expression::Reference(parameter.name.clone()),
+                false => {
+                    mir::Expression::Local(parameter.data_type.clone(), parameter.name.clone())
+                }
+            })
+            .collect();
+        let return_type = self.lower_type(*return_type);
+
+        let mut body = Function::new(return_type.clone());
+        let call = mir::Expression::Call {
+            function: self.callee(name, Vec::new()),
+            arguments,
+            data_type: return_type.clone(),
+        };
+        match return_type {
+            mir::Type::Void => {
+                body.push(mir::Node::Expression(call));
+                body.push(mir::Node::Return(None));
+            }
+            _ => body.push(mir::Node::Return(Some(call))),
+        }
+
+        let mut mir_parameters = vec![mir::Parameter {
+            pointer: false,
+            name: ENVIRONMENT.to_string(),
+            data_type: mir::Type::Pointer,
+        }];
+        mir_parameters.extend(parameters);
+        self.functions.push(mir::Function {
+            internal: true,
+            name: function,
+            parameters: mir_parameters,
+            return_type,
+            blocks: body.blocks,
+        });
+        value
+    }
+}
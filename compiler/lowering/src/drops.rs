@@ -0,0 +1,188 @@
+use syntax::{
+    hir,
+    mir::{self, FREE, MALLOC},
+};
+
+use crate::{Lowering, function::Function, mangle::mangle_type};
+
+impl Lowering {
+    pub fn lower_box(
+        &mut self,
+        body: &mut Function,
+        value: hir::Node,
+        data_type: hir::Type,
+    ) -> mir::Expression {
+        let data_type = self.lower_type(data_type);
+        let value = match value {
+            closure @ hir::Node::Closure { .. } => self.lower_closure(body, closure, true),
+            value => self.lower_expression(body, value, &data_type),
+        };
+
+        let size = mir::Type::UInt(self.pointer_width);
+        self.runtime(MALLOC, &[("size", size.clone())], mir::Type::Pointer);
+        let allocation = mir::Expression::Call {
+            function: MALLOC.to_string(),
+            arguments: vec![mir::Expression::Integer(
+                size,
+                self.size_of(&data_type).to_string(),
+            )],
+            data_type: mir::Type::Pointer,
+        };
+
+        let pointer = self.spill(body, allocation);
+        body.push(mir::Node::Store {
+            pointer: pointer.clone(),
+            data_type,
+            value,
+        });
+        pointer
+    }
+    pub fn lower_drop(
+        &mut self,
+        body: &mut Function,
+        value: mir::Expression,
+        data_type: &hir::Type,
+    ) {
+        let value = match data_type {
+            hir::Type::Result(..) => match self.spill(body, value) {
+                mir::Expression::Local(_, name) => mir::Expression::Reference(name),
+                value => panic!("Expected a local, got {value}"),
+            },
+            _ => value,
+        };
+        let function = self.drop_glue(data_type);
+        body.push(mir::Node::Expression(mir::Expression::Call {
+            function,
+            arguments: vec![value],
+            data_type: mir::Type::Void,
+        }));
+    }
+    pub fn drop_live(&mut self, body: &mut Function) {
+        for (name, data_type) in body.live() {
+            let value = mir::Expression::Local(self.lower_type(data_type.clone()), name);
+            self.lower_drop(body, value, &data_type);
+        }
+    }
+    pub fn drop_scope(&mut self, body: &mut Function) {
+        let scope = body.scopes.pop().unwrap_or_default();
+        if body.is_terminated() {
+            return;
+        }
+
+        for owned in scope.into_iter().rev().filter(|owned| owned.live) {
+            let value =
+                mir::Expression::Local(self.lower_type(owned.data_type.clone()), owned.name);
+            self.lower_drop(body, value, &owned.data_type);
+        }
+    }
+    fn drop_glue(&mut self, data_type: &hir::Type) -> String {
+        let name = format!("$drop${}", mangle_type(data_type));
+        if !self.drops.insert(name.clone()) {
+            return name;
+        }
+
+        let value = || mir::Expression::Local(mir::Type::Pointer, "value".to_string());
+
+        let mut body = Function::new(mir::Type::Void);
+        match data_type {
+            hir::Type::Box(inner) if inner.is_owned() => {
+                let inner_value =
+                    mir::Expression::Load(self.lower_type(*inner.clone()), Box::new(value()));
+                self.lower_drop(&mut body, inner_value, inner);
+            }
+            hir::Type::Box(inner) if matches!(**inner, hir::Type::Function { .. }) => {
+                self.drop_environment(&mut body, value());
+            }
+            hir::Type::Box(_) => {}
+            hir::Type::String => self.drop_string(&mut body, value()),
+            hir::Type::Result(..) => self.drop_result(&mut body, value(), data_type),
+            data_type => panic!("Expected an owned type, got {data_type}"),
+        }
+        if !body.is_terminated() {
+            self.runtime(FREE, &[("pointer", mir::Type::Pointer)], mir::Type::Void);
+            body.push(mir::Node::Expression(mir::Expression::Call {
+                function: FREE.to_string(),
+                arguments: vec![value()],
+                data_type: mir::Type::Void,
+            }));
+            body.push(mir::Node::Return(None));
+        }
+
+        self.functions.push(mir::Function {
+            internal: true,
+            name: name.clone(),
+            parameters: vec![mir::Parameter {
+                pointer: false,
+                name: "value".to_string(),
+                data_type: mir::Type::Pointer,
+            }],
+            return_type: mir::Type::Void,
+            blocks: body.blocks,
+        });
+        name
+    }
+    pub fn runtime(
+        &mut self,
+        name: &str,
+        parameters: &[(&str, mir::Type)],
+        return_type: mir::Type,
+    ) {
+        if self.declarations.iter().any(|d| d.name == name) {
+            return;
+        }
+
+        self.declarations.push(mir::Declaration {
+            name: name.to_string(),
+            parameters: parameters
+                .iter()
+                .map(|(name, data_type)| mir::Parameter {
+                    pointer: false,
+                    name: name.to_string(),
+                    data_type: data_type.clone(),
+                })
+                .collect(),
+            return_type,
+        });
+    }
+    pub fn size_of(&self, data_type: &mir::Type) -> usize {
+        let pointer = self.pointer_width as usize / 8;
+        match data_type {
+            mir::Type::Void => 0,
+            mir::Type::Bytes(bytes) => *bytes,
+            mir::Type::Boolean => 1,
+            mir::Type::Int(bits) | mir::Type::UInt(bits) | mir::Type::Float(bits) => {
+                (*bits as usize).div_ceil(8)
+            }
+            mir::Type::Pointer => pointer,
+            mir::Type::Function => pointer * 2,
+            mir::Type::Slice(bits) => pointer + *bits as usize / 8,
+            mir::Type::Struct(name) => {
+                let mut size: usize = 0;
+                for field in self.fields(name) {
+                    size = size.next_multiple_of(self.align_of(field)) + self.size_of(field);
+                }
+                size.next_multiple_of(self.align_of(data_type))
+            }
+        }
+    }
+    fn align_of(&self, data_type: &mir::Type) -> usize {
+        match data_type {
+            mir::Type::Void | mir::Type::Bytes(_) => 1,
+            mir::Type::Function | mir::Type::Slice(_) => self.pointer_width as usize / 8,
+            mir::Type::Struct(name) => self
+                .fields(name)
+                .iter()
+                .map(|field| self.align_of(field))
+                .max()
+                .unwrap_or(1),
+            data_type => self.size_of(data_type),
+        }
+    }
+    pub fn fields(&self, name: &str) -> &[mir::Type] {
+        self.structs
+            .iter()
+            .find(|s| s.name == name)
+            .map(|s| s.fields.as_slice())
+            .unwrap_or_else(|| panic!("Expected a lowered struct '{name}'"))
+    }
+}
@@ -1,8 +1,65 @@
-use syntax::mir;
+use syntax::{hir, mir};
 
-struct Function {
-    body: Vec<mir::Node>
+pub struct Owned {
+    pub name: String,
+    pub data_type: hir::Type,
+    pub live: bool,
+}
+
+pub struct Function {
+    pub return_type: mir::Type,
+    pub blocks: Vec<mir::Block>,
+    pub scopes: Vec<Vec<Owned>>,
 }
 
 impl Function {
-}
\ No newline at end of file
+    pub fn new(return_type: mir::Type) -> Self {
+        Self {
+            return_type,
+            blocks: vec![mir::Block::new("start")],
+            scopes: vec![Vec::new()],
+        }
+    }
+    pub fn push(&mut self, node: mir::Node) {
+        if self.is_terminated() {
+            let label = format!("bb{}", self.blocks.len());
+            self.blocks.push(mir::Block::new(label));
+        }
+
+        self.blocks.last_mut().unwrap().body.push(node);
+    }
+    pub fn fork(&mut self) -> (String, String) {
+        if self.is_terminated() {
+            let label = format!("bb{}", self.blocks.len());
+            self.blocks.push(mir::Block::new(label));
+        }
+
+        let next = self.blocks.len();
+        (format!("bb{next}"), format!("bb{}", next + 1))
+    }
+    pub fn is_terminated(&self) -> bool {
+        self.blocks.last().unwrap().terminator().is_some()
+    }
+    pub fn own(&mut self, name: String, data_type: hir::Type) {
+        self.scopes.last_mut().unwrap().push(Owned {
+            name,
+            data_type,
+            live: true,
+        });
+    }
+    pub fn owned(&mut self, name: &str) -> Option<&mut Owned> {
+        self.scopes
+            .iter_mut()
+            .rev()
+            .find_map(|scope| scope.iter_mut().rev().find(|owned| owned.name == name))
+    }
+    pub fn live(&self) -> Vec<(String, hir::Type)> {
+        self.scopes
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.iter().rev())
+            .filter(|owned| owned.live)
+            .map(|owned| (owned.name.clone(), owned.data_type.clone()))
+            .collect()
+    }
+}
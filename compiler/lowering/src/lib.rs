@@ -1,40 +1,591 @@
-use context::CompilerCtx;
-use syntax::{hir, mir};
+use std::collections::{HashMap, HashSet, VecDeque};
 
+use context::{CompilerCtx, files::StdResolver};
+use function::Function;
+use syntax::{hir, mir, operators::Operator};
+
+mod casts;
+mod checks;
+mod closure;
+mod drops;
 mod function;
+mod mangle;
+mod queries;
+mod results;
+mod strings;
+mod structs;
+
+use closure::Context;
+use mangle::{mangle, mangle_method};
+pub use queries::Mir;
 
 struct Lowering {
+    structs: Vec<mir::Struct>,
+    definitions: HashMap<String, Vec<hir::Type>>,
+    constants: Vec<mir::Constant>,
+    declarations: Vec<mir::Declaration>,
     functions: Vec<mir::Function>,
+    deferred: HashMap<String, hir::Function>,
+    instances: VecDeque<(String, Vec<hir::Type>)>,
+    instantiated: HashSet<String>,
+    substitutions: HashMap<String, hir::Type>,
+    values: HashSet<String>,
+    drops: HashSet<String>,
+    context: Context,
+    pointer_width: u8,
+    checks: bool,
 }
 
 pub fn lower_to_mir(compiler: &CompilerCtx, collection: hir::ModuleCollection) -> mir::Module {
-    todo!()
+    let mut lowering = Lowering::new(compiler);
+
+    let mut functions = Vec::new();
+    let mut library = Vec::new();
+    for module in collection.modules {
+        lowering.insert_structs(module.structs);
+        if StdResolver::contains(&module.path) {
+            library.extend(module.functions);
+            continue;
+        }
+        functions.extend(module.functions);
+        for implementation in module.impls {
+            let trait_name = implementation.trait_name.as_deref();
+            functions.extend(
+                implementation
+                    .methods
+                    .into_iter()
+                    .map(|method| hir::Function {
+                        symbol: mangle_method(&implementation.self_type, trait_name, &method.name),
+                        ..method
+                    }),
+            );
+        }
+    }
+
+    for function in library {
+        lowering.deferred.insert(function.symbol.clone(), function);
+    }
+    for function in functions {
+        match function.generics.is_empty() {
+            true => lowering.lower_function(function, false),
+            false => {
+                lowering.deferred.insert(function.symbol.clone(), function);
+            }
+        }
+    }
+    lowering.lower_instances();
+    lowering.module()
 }
 
 impl Lowering {
-    fn lower_node(&mut self, node: hir::Node) {
+    fn new(compiler: &CompilerCtx) -> Self {
+        Self {
+            structs: Vec::new(),
+            definitions: HashMap::new(),
+            constants: Vec::new(),
+            declarations: Vec::new(),
+            functions: Vec::new(),
+            deferred: HashMap::new(),
+            instances: VecDeque::new(),
+            instantiated: HashSet::new(),
+            substitutions: HashMap::new(),
+            values: HashSet::new(),
+            drops: HashSet::new(),
+            context: Context::default(),
+            pointer_width: compiler.target().pointer_width(),
+            checks: !compiler.is_release(),
+        }
+    }
+    fn insert_structs(&mut self, structs: Vec<hir::Struct>) {
+        for structure in structs {
+            let fields = structure.fields.into_iter().map(|(_, t)| t).collect();
+            self.definitions.insert(structure.symbol, fields);
+        }
+    }
+    fn module(self) -> mir::Module {
+        mir::Module {
+            structs: self.structs,
+            constants: self.constants,
+            declarations: self.declarations,
+            functions: self.functions,
+        }
+    }
+    fn lower_instances(&mut self) {
+        while let Some((name, types)) = self.instances.pop_front() {
+            let Some(function) = self.deferred.get(&name).cloned() else {
+                continue;
+            };
+
+            self.substitutions = function
+                .generics
+                .iter()
+                .map(|generic| generic.name.clone())
+                .zip(types.clone())
+                .collect();
+            let function = hir::Function {
+                symbol: mangle(&name, &types),
+                generics: Vec::new(),
+                ..function
+            };
+            self.lower_function(function, true);
+            self.substitutions.clear();
+        }
+    }
+    fn callee(&mut self, function: String, generics: Vec<hir::Type>) -> String {
+        match generics.is_empty() && !self.deferred.contains_key(&function) {
+            true => function,
+            false => self.instance(function, generics),
+        }
+    }
+    fn instance(&mut self, name: String, types: Vec<hir::Type>) -> String {
+        let types: Vec<hir::Type> = types
+            .iter()
+            .map(|t| t.substitute(&self.substitutions))
+            .collect();
+
+        let mangled = mangle(&name, &types);
+        if self.instantiated.insert(mangled.clone()) {
+            self.instances.push_back((name, types));
+        }
+        mangled
+    }
+    fn lower_function(&mut self, function: hir::Function, internal: bool) {
+        let return_type = self.lower_type(function.return_type);
+        let owned = self.owned_parameters(&function.parameters);
+        let parameters = function
+            .parameters
+            .into_iter()
+            .map(|parameter| self.lower_parameter(parameter))
+            .collect();
+
+        if function.external {
+            self.declarations.push(mir::Declaration {
+                name: function.symbol,
+                parameters,
+                return_type,
+            });
+            return;
+        }
+
+        self.context = Context::new(function.symbol.clone(), function.path);
+        self.lower_body(
+            internal,
+            function.symbol,
+            parameters,
+            owned,
+            return_type,
+            function.body,
+        );
+    }
+    fn lower_body(
+        &mut self,
+        internal: bool,
+        name: String,
+        parameters: Vec<mir::Parameter>,
+        owned: Vec<(String, hir::Type)>,
+        return_type: mir::Type,
+        nodes: Vec<hir::Node>,
+    ) {
+        let mut body = Function::new(return_type.clone());
+        for (name, data_type) in owned {
+            body.own(name, data_type);
+        }
+        for node in nodes {
+            self.lower_node(&mut body, node);
+        }
+
+        if return_type == mir::Type::Void && !body.is_terminated() {
+            self.drop_live(&mut body);
+            body.push(mir::Node::Return(None));
+        }
+
+        self.functions.push(mir::Function {
+            internal,
+            name,
+            parameters,
+            return_type,
+            blocks: body.blocks,
+        });
+    }
+    fn owned_parameters(&self, parameters: &[hir::Parameter]) -> Vec<(String, hir::Type)> {
+        parameters
+            .iter()
+            .filter_map(|parameter| {
+                let data_type = self.owned(&parameter.data_type)?;
+                Some((parameter.name.clone(), data_type))
+            })
+            .collect()
+    }
+    fn owned(&self, data_type: &hir::Type) -> Option<hir::Type> {
+        let data_type = data_type.substitute(&self.substitutions);
+        data_type.is_owned().then_some(data_type)
+    }
+    fn lower_parameter(&mut self, parameter: hir::Parameter) -> mir::Parameter {
+        mir::Parameter {
+            pointer: parameter.data_type.is_reference(),
+            name: parameter.name,
+            data_type: self.lower_type(parameter.data_type.dereference().clone()),
+        }
+    }
+
+    fn lower_node(&mut self, body: &mut Function, node: hir::Node) {
         use hir::Node;
 
         match node {
-            r => todo!("{r:?}"),
+            Node::Block(nodes) => {
+                body.scopes.push(Vec::new());
+                for node in nodes {
+                    self.lower_node(body, node);
+                }
+                self.drop_scope(body);
+            }
+            Node::Return(value) => {
+                let return_type = body.return_type.clone();
+                let mut value = value.map(|v| self.lower_expression(body, *v, &return_type));
+                if !body.live().is_empty() {
+                    value = value.map(|v| self.spill(body, v));
+                    self.drop_live(body);
+                }
+                body.push(mir::Node::Return(value));
+            }
+            Node::DeclareVariable {
+                name,
+                mutable: _,
+                data_type,
+                value,
+            } => {
+                let owned = self.owned(&data_type);
+                let data_type = self.lower_type(data_type);
+                let value = self.lower_expression(body, *value, &data_type);
+
+                body.push(mir::Node::Allocate {
+                    name: name.clone(),
+                    data_type: data_type.clone(),
+                });
+                body.push(mir::Node::Set {
+                    name: name.clone(),
+                    data_type,
+                    value,
+                });
+                if let Some(owned) = owned {
+                    body.own(name, owned);
+                }
+            }
+            Node::Assign {
+                name,
+                data_type,
+                value,
+                ..
+            } => {
+                let owned = self.owned(&data_type);
+                let data_type = self.lower_type(data_type);
+                let mut value = self.lower_expression(body, *value, &data_type);
+                if let Some(owned) = owned {
+                    value = self.assign_owned(body, &name, &owned, value);
+                }
+                match self.capture(&name) {
+                    Some(pointer) => body.push(mir::Node::Store {
+                        pointer,
+                        data_type,
+                        value,
+                    }),
+                    None => body.push(mir::Node::Set {
+                        name,
+                        data_type,
+                        value,
+                    }),
+                }
+            }
+            Node::Try { value, data_type } => {
+                let owned = match &data_type {
+                    hir::Type::Result(ok, _) => self.owned(ok),
+                    _ => None,
+                };
+                let value = self.lower_try(body, *value, data_type);
+                if let Some(owned) = owned {
+                    let value = self.spill(body, value);
+                    self.lower_drop(body, value, &owned);
+                }
+            }
+            node @ (Node::Call { .. }
+            | Node::MethodCall { .. }
+            | Node::Invoke { .. }
+            | Node::Boxed { .. }
+            | Node::Builtin { .. }) => {
+                let owned = match &node {
+                    Node::Call { return_type, .. }
+                    | Node::MethodCall { return_type, .. }
+                    | Node::Invoke { return_type, .. } => self.owned(return_type),
+                    Node::Builtin { builtin, .. } => self.owned(&builtin.return_type()),
+                    Node::Boxed { data_type, .. } => {
+                        self.owned(&hir::Type::Box(Box::new(data_type.clone())))
+                    }
+                    _ => None,
+                };
+
+                let value = self.lower_expression(body, node, &mir::Type::Void);
+                match owned {
+                    Some(owned) => {
+                        let value = self.spill(body, value);
+                        self.lower_drop(body, value, &owned);
+                    }
+                    None => body.push(mir::Node::Expression(value)),
+                }
+            }
+            node @ (Node::Binary { .. }
+            | Node::Function { .. }
+            | Node::Closure { .. }
+            | Node::Deref(..)
+            | Node::Construct { .. }
+            | Node::Field { .. }
+            | Node::Variant { .. }
+            | Node::Cast { .. }
+            | Node::AsStr(_)
+            | Node::Variable { .. }
+            | Node::Borrow { .. }
+            | Node::Integer(_)
+            | Node::Float(_)
+            | Node::Boolean(_)
+            | Node::String(_)) => {
+                unreachable!("internal compiler error: expression used as a statement: {node:?}")
+            }
         }
     }
 
-    fn lower_expression(&self, node: hir::Node) -> mir::Expression {
+    fn lower_expression(
+        &mut self,
+        body: &mut Function,
+        node: hir::Node,
+        data_type: &mir::Type,
+    ) -> mir::Expression {
         use hir::Node;
         use mir::Expression;
 
         match node {
-            Node::Integer(n) => Expression::Integer(mir::Type::Int(32), n),
-            r => todo!("{r:?}"),
+            Node::Integer(n) => Expression::Integer(data_type.clone(), n),
+            Node::Float(n) => Expression::Float(data_type.clone(), n),
+            Node::Boolean(b) => Expression::Boolean(b),
+            Node::String(value) => self.lower_string(value),
+            Node::Variable {
+                name, data_type, ..
+            } => {
+                if self.owned(&data_type).is_some()
+                    && let Some(owned) = body.owned(&name)
+                {
+                    owned.live = false;
+                }
+                let data_type = self.lower_type(data_type);
+                self.read(name, data_type)
+            }
+            Node::Variant {
+                variant,
+                value,
+                data_type,
+            } => self.lower_variant(body, variant, *value, data_type),
+            Node::Try { value, data_type } => self.lower_try(body, *value, data_type),
+            Node::Cast {
+                value,
+                from,
+                data_type,
+            } => self.lower_cast(body, *value, from, data_type),
+            Node::Deref(value, data_type) => {
+                let pointer = match *value {
+                    Node::Variable { name, .. } => self.read(name, mir::Type::Pointer),
+                    value => self.lower_expression(body, value, &mir::Type::Pointer),
+                };
+                Expression::Load(self.lower_type(data_type), Box::new(pointer))
+            }
+            Node::Boxed { value, data_type } => self.lower_box(body, *value, data_type),
+            Node::Construct { fields, data_type } => self.lower_construct(body, fields, data_type),
+            Node::Field {
+                value,
+                index,
+                data_type,
+                ..
+            } => self.lower_field(body, *value, index, data_type),
+            Node::AsStr(value) => self.lower_as_str(body, *value),
+            Node::Builtin { builtin, arguments } => self.lower_builtin(body, builtin, arguments),
+            Node::Borrow { name, .. } => match self.capture(&name) {
+                Some(pointer) => pointer,
+                None => Expression::Reference(name),
+            },
+            Node::Binary {
+                left,
+                right,
+                operator,
+                data_type,
+                position,
+            } => {
+                let operand = self.lower_type(data_type);
+                let data_type = match operator {
+                    Operator::Arithmetic(_) => operand.clone(),
+                    Operator::Compare(_) => mir::Type::Boolean,
+                };
+                let checked = match &operator {
+                    Operator::Arithmetic(operator)
+                        if self.checks
+                            && matches!(operand, mir::Type::Int(_) | mir::Type::UInt(_)) =>
+                    {
+                        Some(operator.clone())
+                    }
+                    _ => None,
+                };
+
+                let mut left = self.lower_expression(body, *left, &operand);
+                if checked.is_some() {
+                    left = self.operand(body, left);
+                }
+                let mut right = self.lower_expression(body, *right, &operand);
+                if let Some(checked) = checked {
+                    right = self.operand(body, right);
+                    self.check_arithmetic(body, checked, &left, &right, position);
+                }
+                Expression::Binary {
+                    operator,
+                    left: Box::new(left),
+                    right: Box::new(right),
+                    data_type,
+                }
+            }
+            Node::Function {
+                symbol, data_type, ..
+            } => self.function_value(symbol, data_type),
+            node @ Node::Closure { .. } => self.lower_closure(body, node, false),
+            Node::Invoke {
+                callee,
+                arguments,
+                parameters,
+                return_type,
+            } => {
+                let callee = self.lower_callee(body, *callee);
+                let callee = self.spill(body, callee);
+                Expression::Invoke {
+                    callee: Box::new(callee),
+                    arguments: self.lower_arguments(body, arguments, parameters),
+                    data_type: self.lower_type(return_type),
+                }
+            }
+            Node::Call {
+                symbol,
+                generics,
+                arguments,
+                parameters,
+                return_type,
+                ..
+            } => {
+                let function = self.callee(symbol, generics);
+                Expression::Call {
+                    function,
+                    arguments: self.lower_arguments(body, arguments, parameters),
+                    data_type: self.lower_type(return_type),
+                }
+            }
+            Node::MethodCall {
+                trait_name,
+                self_type,
+                method,
+                arguments,
+                parameters,
+                return_type,
+            } => {
+                let self_type = self_type.substitute(&self.substitutions);
+                Expression::Call {
+                    function: mangle_method(&self_type, trait_name.as_deref(), &method),
+                    arguments: self.lower_arguments(body, arguments, parameters),
+                    data_type: self.lower_type(return_type),
+                }
+            }
+            node @ (Node::DeclareVariable { .. }
+            | Node::Assign { .. }
+            | Node::Block(_)
+            | Node::Return(_)) => {
+                unreachable!("internal compiler error: statement used as an expression: {node:?}")
+            }
         }
     }
 
-    fn node_to_body(&self, node: hir::Node) {
+    fn lower_callee(&mut self, body: &mut Function, callee: hir::Node) -> mir::Expression {
         use hir::Node;
 
-        match node {
-            _ => todo!("{node:#?}"),
+        match callee {
+            Node::Variable { name, .. } => self.read(name, mir::Type::Function),
+            Node::Deref(value, data_type)
+                if !matches!(*value, Node::Variable { .. } | Node::Deref(..)) =>
+            {
+                let pointer = self.lower_expression(body, *value, &mir::Type::Pointer);
+                let pointer = self.spill(body, pointer);
+                if let mir::Expression::Local(_, name) = &pointer {
+                    body.own(name.clone(), hir::Type::Box(Box::new(data_type)));
+                }
+                mir::Expression::Load(mir::Type::Function, Box::new(pointer))
+            }
+            callee => self.lower_expression(body, callee, &mir::Type::Function),
+        }
+    }
+    fn read(&self, name: String, data_type: mir::Type) -> mir::Expression {
+        match self.capture(&name) {
+            Some(pointer) => mir::Expression::Load(data_type, Box::new(pointer)),
+            None => mir::Expression::Local(data_type, name),
+        }
+    }
+    fn assign_owned(
+        &mut self,
+        body: &mut Function,
+        name: &str,
+        data_type: &hir::Type,
+        value: mir::Expression,
+    ) -> mir::Expression {
+        let live = match body.owned(name) {
+            Some(owned) => std::mem::replace(&mut owned.live, true),
+            None => self.capture(name).is_some(),
+        };
+        if !live {
+            return value;
+        }
+
+        let value = self.spill(body, value);
+        let lowered = self.lower_type(data_type.clone());
+        let old = self.read(name.to_string(), lowered);
+        self.lower_drop(body, old, data_type);
+        value
+    }
+
+    fn lower_arguments(
+        &mut self,
+        body: &mut Function,
+        arguments: Vec<hir::Node>,
+        parameters: Vec<hir::Type>,
+    ) -> Vec<mir::Expression> {
+        arguments
+            .into_iter()
+            .zip(parameters)
+            .map(|(argument, parameter)| {
+                let parameter = self.lower_type(parameter);
+                self.lower_expression(body, argument, &parameter)
+            })
+            .collect()
+    }
+
+    fn lower_type(&mut self, data_type: hir::Type) -> mir::Type {
+        match data_type {
+            hir::Type::Void => mir::Type::Void,
+            hir::Type::Int(bits) => mir::Type::Int(bits),
+            hir::Type::UInt(bits) => mir::Type::UInt(bits),
+            hir::Type::Float(bits) => mir::Type::Float(bits),
+            hir::Type::USize => mir::Type::UInt(self.pointer_width),
+            hir::Type::ISize => mir::Type::Int(self.pointer_width),
+            hir::Type::Boolean => mir::Type::Boolean,
+            hir::Type::Ref(_) | hir::Type::RefMut(_) | hir::Type::Box(_) | hir::Type::String => {
+                mir::Type::Pointer
+            }
+            hir::Type::Str => mir::Type::Slice(self.pointer_width),
+            hir::Type::Function { .. } => mir::Type::Function,
+            hir::Type::Result(ok, err) => self.result_type(*ok, *err),
+            hir::Type::Struct(name) => self.struct_type(name),
+            hir::Type::Parameter(name) => match self.substitutions.get(&name) {
+                Some(data_type) => self.lower_type(data_type.clone()),
+                None => panic!("Unbound type parameter '{name}' during lowering"),
+            },
         }
     }
 }
@@ -0,0 +1,48 @@
+use syntax::hir;
+
+pub fn mangle(name: &str, generics: &[hir::Type]) -> String {
+    let mut mangled = name.to_string();
+    for data_type in generics {
+        mangled.push('$');
+        mangled.push_str(&mangle_type(data_type));
+    }
+    mangled
+}
+
+pub fn mangle_method(self_type: &hir::Type, trait_name: Option<&str>, method: &str) -> String {
+    match trait_name {
+        Some(trait_name) => format!("{}${trait_name}${method}", mangle_type(self_type)),
+        None => format!("{}${method}", mangle_type(self_type)),
+    }
+}
+
+pub fn mangle_type(data_type: &hir::Type) -> String {
+    match data_type {
+        hir::Type::Parameter(name) => panic!("Cannot mangle unbound type parameter '{name}'"),
+        hir::Type::Void => "void".to_string(),
+        hir::Type::Int(bits) => format!("i{bits}"),
+        hir::Type::UInt(bits) => format!("u{bits}"),
+        hir::Type::Float(bits) => format!("f{bits}"),
+        hir::Type::USize => "usize".to_string(),
+        hir::Type::ISize => "isize".to_string(),
+        hir::Type::Boolean => "bool".to_string(),
+        hir::Type::Str => "str".to_string(),
+        hir::Type::String => "String".to_string(),
+        hir::Type::Ref(data_type) => format!("ref${}", mangle_type(data_type)),
+        hir::Type::RefMut(data_type) => format!("mut${}", mangle_type(data_type)),
+        hir::Type::Box(data_type) => format!("Box${}", mangle_type(data_type)),
+        hir::Type::Result(ok, err) => format!("result${}${}", mangle_type(ok), mangle_type(err)),
+        hir::Type::Struct(symbol) => symbol.clone(),
+        hir::Type::Function {
+            parameters,
+            return_type,
+        } => {
+            let mut mangled = format!("func{}", parameters.len());
+            for data_type in parameters.iter().chain([return_type.as_ref()]) {
+                mangled.push('$');
+                mangled.push_str(&mangle_type(data_type));
+            }
+            mangled
+        }
+    }
+}
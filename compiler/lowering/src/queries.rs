@@ -0,0 +1,51 @@
+use std::path::PathBuf;
+
+use analyzer::{Signatures, Structs, Typeck, resolved};
+use context::{CompilerCtx, Query, files::StdResolver};
+use syntax::mir;
+
+use crate::Lowering;
+
+pub struct Mir;
+impl Query for Mir {
+    type Key = (PathBuf, String);
+    type Value = Option<mir::Module>;
+
+    fn execute(compiler: &mut CompilerCtx, key: &Self::Key) -> Self::Value {
+        let typed = compiler.query::<Typeck>(key.clone());
+        let function = typed
+            .function
+            .clone()
+            .filter(|f| !f.external && f.generics.is_empty())?;
+
+        let resolved = resolved(compiler);
+        let mut deferred = Vec::new();
+        let mut structs = Vec::new();
+        for path in &resolved.modules {
+            let table = compiler.query::<Structs>(path.clone());
+            structs.extend(table.structs.iter().cloned());
+            let library = StdResolver::contains(path);
+            let signatures = compiler.query::<Signatures>(path.clone());
+            for signature in signatures
+                .iter()
+                .filter(|s| library || !s.generics.is_empty())
+            {
+                let typed = compiler.query::<Typeck>((path.clone(), signature.name.clone()));
+                deferred.extend(typed.function.clone());
+            }
+        }
+
+        let mut lowering = Lowering::new(compiler);
+        lowering.insert_structs(structs);
+        for function in deferred {
+            lowering.deferred.insert(function.symbol.clone(), function);
+        }
+        lowering.lower_function(function, false);
+        lowering.lower_instances();
+        Some(lowering.module())
+    }
+
+    fn cycle(_: &Self::Key) -> Self::Value {
+        None
+    }
+}
@@ -0,0 +1,163 @@
+use syntax::{hir, mir};
+
+use crate::{Lowering, function::Function, mangle::mangle_type};
+
+const TAG: usize = 0;
+
+impl Lowering {
+    pub fn result_type(&mut self, ok: hir::Type, err: hir::Type) -> mir::Type {
+        let data_type = hir::Type::Result(Box::new(ok.clone()), Box::new(err.clone()));
+        let name = mangle_type(&data_type.substitute(&self.substitutions));
+        if !self.structs.iter().any(|s| s.name == name) {
+            let fields = vec![
+                mir::Type::Boolean,
+                self.lower_type(ok),
+                self.lower_type(err),
+            ];
+            self.structs.push(mir::Struct {
+                name: name.clone(),
+                fields,
+            });
+        }
+        mir::Type::Struct(name)
+    }
+    pub fn lower_variant(
+        &mut self,
+        body: &mut Function,
+        variant: hir::Variant,
+        value: hir::Node,
+        data_type: hir::Type,
+    ) -> mir::Expression {
+        let payload = variant
+            .payload(&data_type)
+            .cloned()
+            .expect("Expected a result type");
+        let payload = self.lower_type(payload);
+        let value = self.lower_expression(body, value, &payload);
+
+        let structure = self.lower_type(data_type);
+        let result = self.temporary(body, structure.clone());
+        store(body, &structure, &result, TAG, variant == hir::Variant::Ok);
+        body.push(mir::Node::Store {
+            pointer: field(&structure, &result, index(variant)),
+            data_type: payload,
+            value,
+        });
+        mir::Expression::Local(structure, result)
+    }
+    pub fn lower_try(
+        &mut self,
+        body: &mut Function,
+        value: hir::Node,
+        data_type: hir::Type,
+    ) -> mir::Expression {
+        let hir::Type::Result(ok, err) = &data_type else {
+            panic!("Expected a result type, got {data_type}");
+        };
+        let (ok, err) = (self.lower_type(*ok.clone()), self.lower_type(*err.clone()));
+
+        let structure = self.lower_type(data_type);
+        let value = self.lower_expression(body, value, &structure);
+        let value = self.spill(body, value);
+
+        let (failure, success) = body.fork();
+        body.push(mir::Node::Branch {
+            condition: extract(&value, TAG, mir::Type::Boolean),
+            then: success.clone(),
+            otherwise: failure.clone(),
+        });
+
+        body.blocks.push(mir::Block::new(failure));
+        let return_type = body.return_type.clone();
+        let result = self.temporary(body, return_type.clone());
+        store(body, &return_type, &result, TAG, false);
+        body.push(mir::Node::Store {
+            pointer: field(&return_type, &result, index(hir::Variant::Err)),
+            data_type: err.clone(),
+            value: extract(&value, index(hir::Variant::Err), err),
+        });
+        self.drop_live(body);
+        body.push(mir::Node::Return(Some(mir::Expression::Local(
+            return_type,
+            result,
+        ))));
+
+        body.blocks.push(mir::Block::new(success));
+        extract(&value, index(hir::Variant::Ok), ok)
+    }
+
+    pub fn drop_result(
+        &mut self,
+        body: &mut Function,
+        pointer: mir::Expression,
+        data_type: &hir::Type,
+    ) {
+        let hir::Type::Result(ok, err) = data_type else {
+            panic!("Expected a result type, got {data_type}");
+        };
+        let structure = self.lower_type(data_type.clone());
+        let mir::Type::Struct(name) = &structure else {
+            panic!("Expected a result structure, got {structure}");
+        };
+        let field = |index| mir::Expression::FieldPointer {
+            structure: name.clone(),
+            base: Box::new(pointer.clone()),
+            index,
+        };
+
+        let (failure, success) = body.fork();
+        body.push(mir::Node::Branch {
+            condition: mir::Expression::Load(mir::Type::Boolean, Box::new(field(TAG))),
+            then: success.clone(),
+            otherwise: failure.clone(),
+        });
+        for (label, variant, payload) in [
+            (failure, hir::Variant::Err, err),
+            (success, hir::Variant::Ok, ok),
+        ] {
+            body.blocks.push(mir::Block::new(label));
+            if let Some(payload) = self.owned(payload) {
+                let value = mir::Expression::Load(
+                    self.lower_type(payload.clone()),
+                    Box::new(field(index(variant))),
+                );
+                self.lower_drop(body, value, &payload);
+            }
+            body.push(mir::Node::Return(None));
+        }
+    }
+}
+
+fn index(variant: hir::Variant) -> usize {
+    match variant {
+        hir::Variant::Ok => 1,
+        hir::Variant::Err => 2,
+    }
+}
+
+fn field(structure: &mir::Type, local: &str, index: usize) -> mir::Expression {
+    let mir::Type::Struct(structure) = structure else {
+        panic!("Expected a result structure, got {structure}");
+    };
+    mir::Expression::FieldPointer {
+        structure: structure.clone(),
+        base: Box::new(mir::Expression::Reference(local.to_string())),
+        index,
+    }
+}
+
+fn store(body: &mut Function, structure: &mir::Type, local: &str, index: usize, value: bool) {
+    body.push(mir::Node::Store {
+        pointer: field(structure, local, index),
+        data_type: mir::Type::Boolean,
+        value: mir::Expression::Boolean(value),
+    });
+}
+
+fn extract(value: &mir::Expression, index: usize, data_type: mir::Type) -> mir::Expression {
+    mir::Expression::Extract {
+        value: Box::new(value.clone()),
+        index,
+        data_type,
+    }
+}
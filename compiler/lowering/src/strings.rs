@@ -0,0 +1,488 @@
+use syntax::{
+    hir,
+    mir::{self, EXIT, FREE, MALLOC, MEMCPY, READ, REALLOC, WRITE},
+    operators::{ArithmeticOperator, CompareOperator, Operator},
+};
+
+use crate::{Lowering, function::Function};
+
+const STRING_FROM: &str = "$string$from";
+const STRING_PUSH: &str = "$string$push";
+const STRING_CONCAT: &str = "$string$concat";
+const PRINT: &str = "$print";
+const PRINT_LINE: &str = "$println";
+const READ_LINE: &str = "$read_line";
+const PANIC: &str = "$panic";
+
+const STDIN: i32 = 0;
+const STDOUT: i32 = 1;
+const STDERR: i32 = 2;
+const PANIC_CODE: i32 = 101;
+
+impl Lowering {
+    pub fn lower_string(&mut self, value: String) -> mir::Expression {
+        let length = self.length(value.len());
+        let constant = match self.constants.iter().find(|c| c.value == value) {
+            Some(constant) => constant.name.clone(),
+            None => {
+                let name = format!("$str{}", self.constants.len());
+                self.constants.push(mir::Constant {
+                    name: name.clone(),
+                    value,
+                });
+                name
+            }
+        };
+        mir::Expression::Str {
+            constant,
+            length: Box::new(length),
+        }
+    }
+    pub fn lower_as_str(&mut self, body: &mut Function, value: hir::Node) -> mir::Expression {
+        let pointer = self.lower_expression(body, value, &mir::Type::Pointer);
+        let string = mir::Expression::Load(mir::Type::Pointer, Box::new(pointer));
+        mir::Expression::Load(self.slice(), Box::new(string))
+    }
+    pub fn lower_builtin(
+        &mut self,
+        body: &mut Function,
+        builtin: hir::Builtin,
+        arguments: Vec<hir::Node>,
+    ) -> mir::Expression {
+        let mut arguments: Vec<mir::Expression> = arguments
+            .into_iter()
+            .map(|argument| self.lower_expression(body, argument, &mir::Type::Pointer))
+            .collect();
+
+        let function = match builtin {
+            hir::Builtin::StringNew => {
+                arguments.push(self.lower_string(String::new()));
+                self.string_from()
+            }
+            hir::Builtin::StringFrom => self.string_from(),
+            hir::Builtin::Push => {
+                let string = arguments.remove(0);
+                let string = mir::Expression::Load(mir::Type::Pointer, Box::new(string));
+                arguments.insert(0, string);
+                self.string_push()
+            }
+            hir::Builtin::Concat => self.string_concat(),
+            hir::Builtin::Length => return self.extract(arguments.remove(0), 1),
+            hir::Builtin::Print => self.print(),
+            hir::Builtin::PrintLine => self.print_line(),
+            hir::Builtin::ReadLine => self.read_line(),
+        };
+        mir::Expression::Call {
+            function: function.to_string(),
+            arguments,
+            data_type: self.lower_type(builtin.return_type()),
+        }
+    }
+    pub fn drop_string(&mut self, body: &mut Function, value: mir::Expression) {
+        let view = mir::Expression::Load(self.slice(), Box::new(value));
+        self.runtime(FREE, &[("pointer", mir::Type::Pointer)], mir::Type::Void);
+        body.push(mir::Node::Expression(self.call(
+            FREE,
+            vec![self.extract(view, 0)],
+            mir::Type::Void,
+        )));
+    }
+
+    fn string_from(&mut self) -> &'static str {
+        let (slice, size) = (self.slice(), self.size());
+        self.runtime(MALLOC, &[("size", size.clone())], mir::Type::Pointer);
+        self.runtime(
+            MEMCPY,
+            &[
+                ("destination", mir::Type::Pointer),
+                ("source", mir::Type::Pointer),
+                ("size", size.clone()),
+            ],
+            mir::Type::Pointer,
+        );
+
+        let text = || mir::Expression::Local(slice.clone(), "text".to_string());
+        let header = self.call(
+            MALLOC,
+            vec![self.length(self.size_of(&slice))],
+            mir::Type::Pointer,
+        );
+        let data = self.call(MALLOC, vec![self.extract(text(), 1)], mir::Type::Pointer);
+        let copy = self.call(
+            MEMCPY,
+            vec![
+                local("data", mir::Type::Pointer),
+                self.extract(text(), 0),
+                self.extract(text(), 1),
+            ],
+            mir::Type::Pointer,
+        );
+        let view = mir::Expression::Slice {
+            data: Box::new(local("data", mir::Type::Pointer)),
+            length: Box::new(self.extract(text(), 1)),
+        };
+
+        self.runtime_function(
+            STRING_FROM,
+            vec![("text", slice.clone())],
+            mir::Type::Pointer,
+            vec![
+                set("string", header),
+                set("data", data),
+                mir::Node::Expression(copy),
+                mir::Node::Store {
+                    pointer: local("string", mir::Type::Pointer),
+                    data_type: slice,
+                    value: view,
+                },
+                mir::Node::Return(Some(local("string", mir::Type::Pointer))),
+            ],
+        );
+        STRING_FROM
+    }
+    fn string_push(&mut self) -> &'static str {
+        let (slice, size) = (self.slice(), self.size());
+        self.runtime(
+            REALLOC,
+            &[("pointer", mir::Type::Pointer), ("size", size.clone())],
+            mir::Type::Pointer,
+        );
+        self.runtime(
+            MEMCPY,
+            &[
+                ("destination", mir::Type::Pointer),
+                ("source", mir::Type::Pointer),
+                ("size", size.clone()),
+            ],
+            mir::Type::Pointer,
+        );
+
+        let string = || local("string", mir::Type::Pointer);
+        let text = || local("text", slice.clone());
+        let view = || local("view", slice.clone());
+        let length = mir::Expression::Binary {
+            operator: Operator::Arithmetic(ArithmeticOperator::Plus),
+            left: Box::new(self.extract(view(), 1)),
+            right: Box::new(self.extract(text(), 1)),
+            data_type: size.clone(),
+        };
+        let data = self.call(
+            REALLOC,
+            vec![self.extract(view(), 0), local("length", size.clone())],
+            mir::Type::Pointer,
+        );
+        let end = mir::Expression::Offset {
+            base: Box::new(local("data", mir::Type::Pointer)),
+            offset: Box::new(self.extract(view(), 1)),
+        };
+        let copy = self.call(
+            MEMCPY,
+            vec![end, self.extract(text(), 0), self.extract(text(), 1)],
+            mir::Type::Pointer,
+        );
+        let grown = mir::Expression::Slice {
+            data: Box::new(local("data", mir::Type::Pointer)),
+            length: Box::new(local("length", size)),
+        };
+
+        self.runtime_function(
+            STRING_PUSH,
+            vec![("string", mir::Type::Pointer), ("text", slice.clone())],
+            mir::Type::Void,
+            vec![
+                set(
+                    "view",
+                    mir::Expression::Load(slice.clone(), Box::new(string())),
+                ),
+                set("length", length),
+                set("data", data),
+                mir::Node::Expression(copy),
+                mir::Node::Store {
+                    pointer: string(),
+                    data_type: slice,
+                    value: grown,
+                },
+                mir::Node::Return(None),
+            ],
+        );
+        STRING_PUSH
+    }
+    fn string_concat(&mut self) -> &'static str {
+        let slice = self.slice();
+        let from = self.string_from();
+        let push = self.string_push();
+
+        let string = || local("string", mir::Type::Pointer);
+        let left = self.call(from, vec![local("left", slice.clone())], mir::Type::Pointer);
+        let right = self.call(
+            push,
+            vec![string(), local("right", slice.clone())],
+            mir::Type::Void,
+        );
+
+        self.runtime_function(
+            STRING_CONCAT,
+            vec![("left", slice.clone()), ("right", slice)],
+            mir::Type::Pointer,
+            vec![
+                set("string", left),
+                mir::Node::Expression(right),
+                mir::Node::Return(Some(string())),
+            ],
+        );
+        STRING_CONCAT
+    }
+    pub fn panic(&mut self) -> &'static str {
+        let slice = self.slice();
+        self.runtime(EXIT, &[("code", mir::Type::Int(32))], mir::Type::Void);
+
+        let newline = self.lower_string("\n".to_string());
+        let text = self.write(STDERR, local("text", slice.clone()));
+        let newline = self.write(STDERR, newline);
+        let code = mir::Expression::Integer(mir::Type::Int(32), PANIC_CODE.to_string());
+
+        self.runtime_function(
+            PANIC,
+            vec![("text", slice)],
+            mir::Type::Void,
+            vec![
+                mir::Node::Expression(text),
+                mir::Node::Expression(newline),
+                mir::Node::Expression(self.call(EXIT, vec![code], mir::Type::Void)),
+                mir::Node::Unreachable,
+            ],
+        );
+        PANIC
+    }
+    fn print(&mut self) -> &'static str {
+        let slice = self.slice();
+        let write = self.write(STDOUT, local("text", slice.clone()));
+
+        self.runtime_function(
+            PRINT,
+            vec![("text", slice)],
+            mir::Type::Void,
+            vec![mir::Node::Expression(write), mir::Node::Return(None)],
+        );
+        PRINT
+    }
+    fn write(&mut self, descriptor: i32, text: mir::Expression) -> mir::Expression {
+        let size = self.size();
+        self.runtime(
+            WRITE,
+            &[
+                ("descriptor", mir::Type::Int(32)),
+                ("data", mir::Type::Pointer),
+                ("size", size),
+            ],
+            self.count(),
+        );
+
+        let descriptor = mir::Expression::Integer(mir::Type::Int(32), descriptor.to_string());
+        let (data, length) = (self.extract(text.clone(), 0), self.extract(text, 1));
+        self.call(WRITE, vec![descriptor, data, length], self.count())
+    }
+    fn print_line(&mut self) -> &'static str {
+        let slice = self.slice();
+        let print = self.print();
+
+        let newline = self.lower_string("\n".to_string());
+        let text = self.call(print, vec![local("text", slice.clone())], mir::Type::Void);
+        let newline = self.call(print, vec![newline], mir::Type::Void);
+
+        self.runtime_function(
+            PRINT_LINE,
+            vec![("text", slice)],
+            mir::Type::Void,
+            vec![
+                mir::Node::Expression(text),
+                mir::Node::Expression(newline),
+                mir::Node::Return(None),
+            ],
+        );
+        PRINT_LINE
+    }
+    fn read_line(&mut self) -> &'static str {
+        let size = self.size();
+        let count = self.count();
+        let from = self.string_from();
+        let push = self.string_push();
+        self.runtime(
+            READ,
+            &[
+                ("descriptor", mir::Type::Int(32)),
+                ("data", mir::Type::Pointer),
+                ("size", size),
+            ],
+            count.clone(),
+        );
+
+        let string = || local("string", mir::Type::Pointer);
+        let byte = || mir::Expression::Reference("byte".to_string());
+        let empty = self.lower_string(String::new());
+        let stdin = mir::Expression::Integer(mir::Type::Int(32), STDIN.to_string());
+        let read = self.call(READ, vec![stdin, byte(), self.length(1)], count.clone());
+        let one = mir::Expression::Integer(count.clone(), "1".to_string());
+        let text = mir::Expression::Slice {
+            data: Box::new(byte()),
+            length: Box::new(self.length(1)),
+        };
+        let compare = |left: mir::Expression, right: mir::Expression| mir::Expression::Binary {
+            operator: Operator::Compare(CompareOperator::Compare),
+            left: Box::new(left),
+            right: Box::new(right),
+            data_type: mir::Type::Boolean,
+        };
+        let newline = mir::Expression::Integer(mir::Type::Int(8), "10".to_string());
+
+        let blocks = vec![
+            block(
+                "start",
+                vec![
+                    mir::Node::Allocate {
+                        name: "string".to_string(),
+                        data_type: mir::Type::Pointer,
+                    },
+                    mir::Node::Allocate {
+                        name: "byte".to_string(),
+                        data_type: mir::Type::Int(8),
+                    },
+                    mir::Node::Allocate {
+                        name: "count".to_string(),
+                        data_type: count.clone(),
+                    },
+                    set("string", self.call(from, vec![empty], mir::Type::Pointer)),
+                    mir::Node::Goto("read".to_string()),
+                ],
+            ),
+            block(
+                "read",
+                vec![
+                    set("count", read),
+                    mir::Node::Branch {
+                        condition: compare(local("count", count), one),
+                        then: "append".to_string(),
+                        otherwise: "done".to_string(),
+                    },
+                ],
+            ),
+            block(
+                "append",
+                vec![
+                    mir::Node::Expression(self.call(push, vec![string(), text], mir::Type::Void)),
+                    mir::Node::Branch {
+                        condition: compare(local("byte", mir::Type::Int(8)), newline),
+                        then: "done".to_string(),
+                        otherwise: "read".to_string(),
+                    },
+                ],
+            ),
+            block("done", vec![mir::Node::Return(Some(string()))]),
+        ];
+        self.define(READ_LINE, Vec::new(), mir::Type::Pointer, blocks);
+        READ_LINE
+    }
+    fn runtime_function(
+        &mut self,
+        name: &str,
+        parameters: Vec<(&str, mir::Type)>,
+        return_type: mir::Type,
+        nodes: Vec<mir::Node>,
+    ) {
+        let mut body = Function::new(return_type.clone());
+        for node in nodes {
+            if let mir::Node::Set {
+                name, data_type, ..
+            } = &node
+            {
+                body.push(mir::Node::Allocate {
+                    name: name.clone(),
+                    data_type: data_type.clone(),
+                });
+            }
+            body.push(node);
+        }
+        self.define(name, parameters, return_type, body.blocks);
+    }
+    fn define(
+        &mut self,
+        name: &str,
+        parameters: Vec<(&str, mir::Type)>,
+        return_type: mir::Type,
+        blocks: Vec<mir::Block>,
+    ) {
+        if self.functions.iter().any(|f| f.name == name) {
+            return;
+        }
+
+        self.functions.push(mir::Function {
+            internal: true,
+            name: name.to_string(),
+            parameters: parameters
+                .into_iter()
+                .map(|(name, data_type)| mir::Parameter {
+                    pointer: false,
+                    name: name.to_string(),
+                    data_type,
+                })
+                .collect(),
+            return_type,
+            blocks,
+        });
+    }
+
+    fn call(
+        &self,
+        function: &str,
+        arguments: Vec<mir::Expression>,
+        data_type: mir::Type,
+    ) -> mir::Expression {
+        mir::Expression::Call {
+            function: function.to_string(),
+            arguments,
+            data_type,
+        }
+    }
+    fn extract(&self, value: mir::Expression, index: usize) -> mir::Expression {
+        let data_type = match index {
+            0 => mir::Type::Pointer,
+            _ => self.size(),
+        };
+        mir::Expression::Extract {
+            value: Box::new(value),
+            index,
+            data_type,
+        }
+    }
+    fn length(&self, length: usize) -> mir::Expression {
+        mir::Expression::Integer(self.size(), length.to_string())
+    }
+    fn size(&self) -> mir::Type {
+        mir::Type::UInt(self.pointer_width)
+    }
+    fn count(&self) -> mir::Type {
+        mir::Type::Int(self.pointer_width)
+    }
+    fn slice(&self) -> mir::Type {
+        mir::Type::Slice(self.pointer_width)
+    }
+}
+
+fn local(name: &str, data_type: mir::Type) -> mir::Expression {
+    mir::Expression::Local(data_type, name.to_string())
+}
+
+fn block(label: &str, body: Vec<mir::Node>) -> mir::Block {
+    mir::Block {
+        label: label.to_string(),
+        body,
+    }
+}
+
+fn set(name: &str, value: mir::Expression) -> mir::Node {
+    mir::Node::Set {
+        name: name.to_string(),
+        data_type: value.data_type(),
+        value,
+    }
+}
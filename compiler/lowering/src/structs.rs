@@ -0,0 +1,61 @@
+use syntax::{hir, mir};
+
+use crate::{Lowering, function::Function};
+
+impl Lowering {
+    pub fn struct_type(&mut self, name: String) -> mir::Type {
+        if let Some(fields) = self.definitions.remove(&name) {
+            let fields = fields.into_iter().map(|f| self.lower_type(f)).collect();
+            self.structs.push(mir::Struct {
+                name: name.clone(),
+                fields,
+            });
+        }
+        mir::Type::Struct(name)
+    }
+    pub fn lower_construct(
+        &mut self,
+        body: &mut Function,
+        fields: Vec<(String, usize, hir::Node)>,
+        data_type: hir::Type,
+    ) -> mir::Expression {
+        let structure = self.lower_type(data_type);
+        let mir::Type::Struct(name) = &structure else {
+            panic!("Expected a struct, got {structure}");
+        };
+
+        let name = name.clone();
+        let types = self.fields(&name).to_vec();
+
+        let local = self.temporary(body, structure.clone());
+        for (_, index, value) in fields {
+            let data_type = types[index].clone();
+            let value = self.lower_expression(body, value, &data_type);
+            body.push(mir::Node::Store {
+                pointer: mir::Expression::FieldPointer {
+                    structure: name.clone(),
+                    base: Box::new(mir::Expression::Reference(local.clone())),
+                    index,
+                },
+                data_type,
+                value,
+            });
+        }
+        mir::Expression::Local(structure, local)
+    }
+    pub fn lower_field(
+        &mut self,
+        body: &mut Function,
+        value: hir::Node,
+        index: usize,
+        data_type: hir::Type,
+    ) -> mir::Expression {
+        let value = self.lower_expression(body, value, &mir::Type::Void);
+        let value = self.spill(body, value);
+        mir::Expression::Extract {
+            value: Box::new(value),
+            index,
+            data_type: self.lower_type(data_type),
+        }
+    }
+}
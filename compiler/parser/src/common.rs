@@ -1,6 +1,6 @@
 use std::borrow::Borrow;
 
-use common::position::{Span, Position, PositionRange};
+use common::position::{Position, PositionRange, Span};
 use diagnostics::{DiagnosticData, DiagnosticResult};
 use lexer::token::{Token, TokenKind};
 
@@ -79,6 +79,24 @@ impl Parser {
         self.next()
     }
 
+    pub fn expect_closing_angle(&mut self) -> DiagnosticResult<Token> {
+        let peeked = self.peek();
+        if peeked.kind == TokenKind::RightBitshift {
+            let mut first = peeked.position;
+            first.end = first.start;
+            first.end.column += 1;
+            first.end.character += 1;
+
+            let second = self.tokens.last_mut().unwrap();
+            second.position.start = first.end;
+            second.kind = TokenKind::GreaterThan;
+            second.string = ">".to_string();
+            self.tokens
+                .push(Token::new(">".to_string(), TokenKind::GreaterThan, first));
+        }
+        self.expect_single(TokenKind::GreaterThan)
+    }
+
     pub fn expect_identifier(&mut self) -> DiagnosticResult<Token> {
         self.expect_single(TokenKind::Identifier)
     }
@@ -4,7 +4,6 @@ use lexer::token::Token;
 use syntax::ast;
 
 mod common;
-mod imports;
 mod node;
 
 struct Parser {
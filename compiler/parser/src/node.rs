@@ -6,6 +6,7 @@ use crate::Parser;
 
 mod attribute;
 mod block;
+mod closure;
 mod condition;
 mod expression;
 mod function;
@@ -14,6 +15,8 @@ mod modifiers;
 mod namespace;
 mod semicolon;
 mod set;
+mod structs;
+mod traits;
 mod types;
 mod variable;
 
@@ -23,14 +26,18 @@ impl Parser {
 
         Ok(match &peeked.kind {
             Import => {
-                let start = self.next()?.position.start;
-                let name = self.expect_identifier()?;
-
-                self.located(ast::RawNode::Import(name.into()), start)
+                let start = self.start();
+                let raw = self.parse_import()?;
+                self.located(raw, start)
             }
             _ => return self.expect_node(),
         })
     }
+    pub fn parse_import(&mut self) -> DiagnosticResult<ast::RawNode> {
+        self.expect_single(Import)?;
+        let name = self.expect_identifier()?;
+        Ok(ast::RawNode::Import(name.into()))
+    }
     pub fn expect_node(&mut self) -> DiagnosticResult<ast::Node> {
         let result = self.expect_expression();
         self.skip_semicolons()?;
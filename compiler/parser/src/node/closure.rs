@@ -0,0 +1,42 @@
+use common::position::Span;
+use diagnostics::DiagnosticResult;
+use lexer::token::{Token, TokenKind::*};
+use syntax::ast::RawNode;
+
+use crate::Parser;
+
+impl Parser {
+    pub fn parse_closure(&mut self, info: Token) -> DiagnosticResult<RawNode> {
+        let (moves, info) = match info.kind {
+            Move => (
+                Some(Span::new((), info.position)),
+                self.expect(&vec![Pipe, Or])?,
+            ),
+            _ => (None, info),
+        };
+
+        let mut parameters = Vec::new();
+        if info.kind == Pipe {
+            loop {
+                if self.next_if_eq(Pipe)?.is_some() {
+                    break;
+                }
+
+                let name = self.expect_identifier()?.into();
+                let data_type = match self.peek().kind {
+                    Comma | Pipe => None,
+                    _ => Some(self.expect_type()?),
+                };
+                parameters.push((name, data_type));
+                self.next_if_eq(Comma)?;
+            }
+        }
+
+        let body = Box::new(self.expect_expression()?);
+        Ok(RawNode::Closure {
+            moves,
+            parameters,
+            body,
+        })
+    }
+}
@@ -1,4 +1,4 @@
-use common::position::Span;
+use common::position::{PositionRange, Span};
 use diagnostics::{DiagnosticData, DiagnosticResult};
 use lexer::token::{Token, TokenKind};
 use syntax::ast::{Node, RawNode};
@@ -18,6 +18,10 @@ impl Parser {
         Ok(None)
     }
     fn expect_raw_base_expression(&mut self) -> DiagnosticResult<RawNode> {
+        if self.peek().kind.is_modifier() {
+            return self.expect_modifiers_node();
+        }
+
         let info = self.next()?;
 
         let raw = match info.kind {
@@ -26,6 +30,10 @@ impl Parser {
             While => self.parse_while()?,
             Loop => self.parse_loop()?,
             Function => self.parse_function()?,
+            Trait => self.parse_trait()?,
+            Struct => self.parse_struct()?,
+            Impl => self.parse_impl()?,
+            Pipe | Or | Move => self.parse_closure(info)?,
             Return => self.parse_return()?,
             Break => self.parse_break()?,
             Continue => self.parse_continue()?,
@@ -38,19 +46,33 @@ impl Parser {
             True => RawNode::Bool(true),
             Text => RawNode::String(info.string),
             Minus => RawNode::Minus(self.expect_base_expression()?.into()),
+            Asterisk => RawNode::Deref(self.expect_base_expression()?.into()),
             Identifier if self.peek().kind == DoubleColon => {
+                let start = info.position.start;
                 let mut path: Vec<Span<String>> = vec![info.into()];
-                while self.peek().kind == DoubleColon {
+                while self.next_if_eq(DoubleColon)?.is_some() {
+                    if self.peek().kind == LessThan {
+                        let end = path.last().unwrap().position.end;
+                        let callee = match path.len() {
+                            1 => RawNode::Identifier(path.pop().unwrap().raw),
+                            _ => RawNode::Path(path),
+                        };
+                        let callee = Span::new(callee, PositionRange::new(start, end));
+                        let types = self.expect_type_arguments()?;
+                        return Ok(RawNode::TypeArguments(callee.into(), types));
+                    }
+
                     let ident = self.expect_identifier()?;
                     path.push(ident.into());
                 }
 
                 RawNode::Path(path)
             }
-            Identifier if self.peek().kind.is_equals_operation() => {
+            Identifier if self.is_construct() => self.parse_construct(info)?,
+            Identifier | SelfKeyword if self.peek().kind.is_equals_operation() => {
                 self.parse_set_operation(info)?
             }
-            Identifier => RawNode::Identifier(info.string),
+            Identifier | SelfKeyword => RawNode::Identifier(info.string),
             OpenParen => {
                 let mut items = self.expect_arguments(CloseParen)?;
                 match items.len() {
@@ -59,7 +81,6 @@ impl Parser {
                     _ => RawNode::Tuple(items),
                 }
             }
-            _ if info.kind.is_modifier() => self.expect_modifiers_node()?,
             _ => {
                 return DiagnosticData::error()
                     .title(format!("Expected expression, got: {:?}", info.kind))
@@ -102,6 +123,17 @@ impl Parser {
 
         loop {
             let info = self.peek();
+            if info.kind == QuestionMark {
+                if POSTFIX_BINDING_POWER < min_bp {
+                    break;
+                }
+
+                let start = left.position.start;
+                self.next()?;
+                left = self.located(RawNode::Try(left.into()), start);
+                continue;
+            }
+
             let bp = match binding_power(&info.kind) {
                 Some(bp) if bp.left >= min_bp => bp,
                 _ => break,
@@ -109,6 +141,11 @@ impl Parser {
 
             let start = left.position.start.clone();
             let info = self.next()?;
+            if info.kind == As {
+                let data_type = self.expect_type()?;
+                left = self.located(RawNode::Cast(left.into(), data_type), start);
+                continue;
+            }
 
             let right = self.parse_expression(bp.right)?;
             let raw = self.make_expression(left, right, info);
@@ -138,6 +175,8 @@ impl Parser {
     }
 }
 
+const POSTFIX_BINDING_POWER: u16 = 90;
+
 pub struct BindingPower {
     left: u16,
     right: u16,
@@ -153,8 +192,10 @@ pub fn binding_power(value: &TokenKind) -> Option<BindingPower> {
 
     let power = match value {
         Dot => BindingPower::new(100, 101),
-        Asterisk | ForwardSlash => BindingPower::new(70, 71),
+        As => BindingPower::new(80, 81),
+        Asterisk | ForwardSlash | Percent => BindingPower::new(70, 71),
         Plus | Minus => BindingPower::new(60, 61),
+        LeftBitshift | RightBitshift => BindingPower::new(50, 51),
 
         LessThan | LessThanOrEquals | GreaterThan | GreaterThanOrEquals => {
             BindingPower::new(40, 41)
@@ -1,27 +1,23 @@
 use common::position::Span;
 use diagnostics::DiagnosticResult;
 use lexer::token::TokenKind::*;
-use syntax::ast::{self, Parameter, RawNode, RawParameter};
+use syntax::ast::{self, Generic, Parameter, RawGeneric, RawNode, RawParameter, Type};
 
 use crate::Parser;
 
 impl Parser {
     pub fn parse_function(&mut self) -> DiagnosticResult<RawNode> {
         let name = self.expect_identifier()?.into();
+        let generics = self.expect_generics()?;
         let parameters = self.expect_parameters()?;
 
-        let return_type = if self.peek().kind.is_expression_start() {
-            self.expect_type()?
-        } else {
-            let position = self.last_position.end.to_range();
-
-            Span::new(ast::RawType::Void, position)
-        };
+        let return_type = self.expect_return_type()?;
 
         let node = Box::new(self.expect_node()?);
 
         let raw = RawNode::Function {
             name,
+            generics,
             parameters,
             return_type,
             node,
@@ -29,6 +25,87 @@ impl Parser {
 
         return Ok(raw);
     }
+    pub fn parse_signature(&mut self) -> DiagnosticResult<RawNode> {
+        let name = self.expect_identifier()?.into();
+        let generics = self.expect_generics()?;
+        let parameters = self.expect_parameters()?;
+        let return_type = self.expect_return_type()?;
+
+        let node = match self.peek().kind {
+            OpenCurlyBracket => self.expect_node()?,
+            _ => Span::new(
+                RawNode::Block(Vec::new()),
+                self.last_position.end.to_range(),
+            ),
+        };
+
+        Ok(RawNode::Function {
+            name,
+            generics,
+            parameters,
+            return_type,
+            node: Box::new(node),
+        })
+    }
+    fn expect_return_type(&mut self) -> DiagnosticResult<Type> {
+        if self.peek().kind.is_expression_start() || matches!(self.peek().kind, Function | Result) {
+            return self.expect_type();
+        }
+
+        let position = self.last_position.end.to_range();
+        Ok(Span::new(ast::RawType::Void, position))
+    }
+    pub fn expect_generics(&mut self) -> DiagnosticResult<Vec<Generic>> {
+        let mut generics = Vec::new();
+        if self.next_if_eq(LessThan)?.is_none() {
+            return Ok(generics);
+        }
+
+        loop {
+            if self.next_if_eq(GreaterThan)?.is_some() {
+                break;
+            }
+            let start = self.start();
+
+            let name = self.expect_identifier()?.into();
+            let mut bounds = Vec::new();
+            if self.next_if_eq(Colon)?.is_some() {
+                loop {
+                    bounds.push(self.expect_identifier()?.into());
+                    if self.next_if_eq(Plus)?.is_none() {
+                        break;
+                    }
+                }
+            }
+            generics.push(self.located(RawGeneric { name, bounds }, start));
+
+            if self.next_if_eq(Comma)?.is_none() {
+                self.expect_single(GreaterThan)?;
+                break;
+            }
+        }
+
+        Ok(generics)
+    }
+    pub fn expect_type_arguments(&mut self) -> DiagnosticResult<Vec<Type>> {
+        self.expect_single(LessThan)?;
+        let mut types = Vec::new();
+
+        loop {
+            if self.next_if_eq(GreaterThan)?.is_some() {
+                break;
+            }
+
+            types.push(self.expect_type()?);
+
+            if self.next_if_eq(Comma)?.is_none() {
+                self.expect_single(GreaterThan)?;
+                break;
+            }
+        }
+
+        Ok(types)
+    }
     pub fn expect_parameters(&mut self) -> DiagnosticResult<Vec<Parameter>> {
         self.expect_single(OpenParen)?;
         let mut params = Vec::new();
@@ -48,17 +125,23 @@ impl Parser {
                 None => None,
             };
 
-            let name = self.expect_identifier()?.into();
-            let data_type = self.expect_type()?;
+            let (name, data_type) = match self.next_if_eq(SelfKeyword)? {
+                Some(info) => {
+                    let data_type = Type::new(ast::RawType::SelfType, info.position);
+                    (info.into(), data_type)
+                }
+                None => (self.expect_identifier()?.into(), self.expect_type()?),
+            };
 
             let parameter = RawParameter {
-                 reference,
+                reference,
                 mutable,
                 name,
                 data_type,
             };
 
-            params.push(self.located(parameter, start))
+            params.push(self.located(parameter, start));
+            self.next_if_eq(Comma)?;
         }
 
         return Ok(params);
@@ -44,9 +44,15 @@ impl Parser {
         Ok(modifiers)
     }
     pub fn expect_modifiers_node(&mut self) -> DiagnosticResult<RawNode> {
-        Ok(RawNode::Modifiers(
-            self.expect_modifiers_list()?,
-            Box::new(self.expect_node()?),
-        ))
+        let modifiers = self.expect_modifiers_list()?;
+        let node = match self.peek().kind {
+            Import => {
+                let start = self.start();
+                let raw = self.parse_import()?;
+                self.located(raw, start)
+            }
+            _ => self.expect_node()?,
+        };
+        Ok(RawNode::Modifiers(modifiers, Box::new(node)))
     }
 }
@@ -1,4 +1,3 @@
-use common::path::Path;
 use diagnostics::DiagnosticResult;
 use lexer::token::TokenKind::*;
 use syntax::ast::{RawNode, UsePath};
@@ -11,7 +10,7 @@ impl Parser {
         Ok(RawNode::Use(path))
     }
     fn expect_path(&mut self) -> DiagnosticResult<UsePath> {
-        let info = self.expect(&vec![Identifier, CloseCurlyBracket])?;
+        let info = self.expect(&vec![Identifier, OpenCurlyBracket])?;
         Ok(match info.kind {
             Identifier if self.peek().kind == DoubleColon => {
                 self.next()?;
@@ -24,6 +23,7 @@ impl Parser {
                 while self.next_if_eq(CloseCurlyBracket)?.is_none() {
                     let path = self.expect_path()?;
                     list.push(path);
+                    self.next_if_eq(Comma)?;
                 }
                 UsePath::List(list)
             }
@@ -31,8 +31,3 @@ impl Parser {
         })
     }
 }
-
-#[allow(unused)]
-fn extract_paths(base: &Path, paths: &UsePath) -> Vec<Path> {
-    todo!()
-}
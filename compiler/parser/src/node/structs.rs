@@ -0,0 +1,48 @@
+use diagnostics::DiagnosticResult;
+use lexer::token::{Token, TokenKind::*};
+use syntax::ast::RawNode;
+
+use crate::Parser;
+
+impl Parser {
+    pub fn parse_struct(&mut self) -> DiagnosticResult<RawNode> {
+        let name = self.expect_identifier()?.into();
+        self.expect_single(OpenCurlyBracket)?;
+
+        let mut fields = Vec::new();
+        while self.next_if_eq(CloseCurlyBracket)?.is_none() {
+            let name = self.expect_identifier()?.into();
+            let data_type = self.expect_type()?;
+            fields.push((name, data_type));
+            self.next_if_eq(Comma)?;
+        }
+
+        Ok(RawNode::Struct { name, fields })
+    }
+    pub fn is_construct(&self) -> bool {
+        let mut tokens = self.tokens.iter().rev().map(|t| t.kind);
+        tokens.next() == Some(OpenCurlyBracket)
+            && tokens.next() == Some(Identifier)
+            && tokens.next() == Some(Colon)
+    }
+    pub fn parse_construct(&mut self, name: Token) -> DiagnosticResult<RawNode> {
+        self.expect_single(OpenCurlyBracket)?;
+
+        let mut fields = Vec::new();
+        while self.next_if_eq(CloseCurlyBracket)?.is_none() {
+            let field = self.expect_identifier()?.into();
+            self.expect_single(Colon)?;
+            fields.push((field, self.expect_expression()?));
+
+            if self.next_if_eq(Comma)?.is_none() {
+                self.expect_single(CloseCurlyBracket)?;
+                break;
+            }
+        }
+
+        Ok(RawNode::Construct {
+            name: name.into(),
+            fields,
+        })
+    }
+}
@@ -0,0 +1,53 @@
+use diagnostics::{DiagnosticData, DiagnosticResult};
+use lexer::token::TokenKind::*;
+use syntax::ast::{RawNode, RawType};
+
+use crate::Parser;
+
+impl Parser {
+    pub fn parse_trait(&mut self) -> DiagnosticResult<RawNode> {
+        let name = self.expect_identifier()?.into();
+        self.expect_single(OpenCurlyBracket)?;
+
+        let mut methods = Vec::new();
+        while self.next_if_eq(CloseCurlyBracket)?.is_none() {
+            let start = self.start();
+            self.expect_single(Function)?;
+            let raw = self.parse_signature()?;
+            methods.push(self.located(raw, start));
+            self.skip_semicolons()?;
+        }
+
+        Ok(RawNode::Trait { name, methods })
+    }
+    pub fn parse_impl(&mut self) -> DiagnosticResult<RawNode> {
+        let data_type = self.expect_type()?;
+        let (trait_name, data_type) = match self.next_if_eq(For)? {
+            Some(_) => {
+                let name = match data_type.raw {
+                    RawType::Other(mut path) if path.len() == 1 => path.pop().unwrap(),
+                    raw => {
+                        return DiagnosticData::error()
+                            .title(format!("Expected a trait name, got: {raw}"))
+                            .position(data_type.position)
+                            .to_err();
+                    }
+                };
+                (Some(name), self.expect_type()?)
+            }
+            None => (None, data_type),
+        };
+        self.expect_single(OpenCurlyBracket)?;
+
+        let mut methods = Vec::new();
+        while self.next_if_eq(CloseCurlyBracket)?.is_none() {
+            methods.push(self.expect_node()?);
+        }
+
+        Ok(RawNode::Impl {
+            trait_name,
+            data_type,
+            methods,
+        })
+    }
+}
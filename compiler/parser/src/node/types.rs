@@ -7,7 +7,14 @@ use crate::Parser;
 
 impl Parser {
     pub fn expect_type(&mut self) -> DiagnosticResult<Type> {
-        let info = match self.expect(&vec![Identifier, Ampersand, OpenBracket, OpenParen]) {
+        let info = match self.expect(&vec![
+            Identifier,
+            Ampersand,
+            OpenBracket,
+            OpenParen,
+            Function,
+            Result,
+        ]) {
             Ok(i) => i,
             Err(_) => {
                 let peeked = self.peek();
@@ -51,39 +58,52 @@ impl Parser {
                 RawType::RefMut(None, Box::new(self.expect_type()?))
             }
             Ampersand => RawType::Ref(None, Box::new(self.expect_type()?)),
+            Function => {
+                self.expect_single(OpenParen)?;
+                let mut parameters = Vec::new();
+                while self.next_if_eq(CloseParen)?.is_none() {
+                    parameters.push(self.expect_type()?);
+                    self.next_if_eq(Comma)?;
+                }
 
-            _ => match info.string.as_str() {
-                "i64" => RawType::Int(64),
-                "i32" => RawType::Int(32),
-                "i16" => RawType::Int(16),
-                "i8" => RawType::Int(8),
-
-                "u64" => RawType::Int(64),
-                "u32" => RawType::UInt(32),
-                "u16" => RawType::UInt(16),
-                "u8" => RawType::UInt(8),
-
-                "f32" => RawType::Float32,
-                "f64" => RawType::Float64,
-
-                "void" => RawType::Void,
-                "bool" => RawType::Boolean,
-                "never" => RawType::Never,
-                "str" => RawType::String,
-                "char" => RawType::Char,
-                "isize" => RawType::ISize,
-                "usize" => RawType::USize,
+                let return_type = match self.peek().kind {
+                    Identifier | Ampersand | OpenBracket | OpenParen | Function | Result => {
+                        self.expect_type()?
+                    }
+                    _ => Type::new(RawType::Void, self.last_position),
+                };
+                RawType::Function {
+                    parameters,
+                    return_type: Box::new(return_type),
+                }
+            }
+            Result => {
+                self.expect_single(LessThan)?;
+                let ok = self.expect_type()?;
+                self.expect_single(Comma)?;
+                let err = self.expect_type()?;
+                self.expect_closing_angle()?;
+                RawType::Result(Box::new(ok), Box::new(err))
+            }
 
-                "Self" => RawType::SelfType,
-                _ if self.peek().kind == DoubleColon => {
-                    let mut path = Vec::new();
+            _ => match RawType::primitive(&info.string) {
+                Some(raw) => raw,
+                None if info.string == "Self" => RawType::SelfType,
+                None if info.string == "Box" && self.peek().kind == LessThan => {
+                    self.next()?;
+                    let data_type = self.expect_type()?;
+                    self.expect_closing_angle()?;
+                    RawType::Box(Box::new(data_type))
+                }
+                None if self.peek().kind == DoubleColon => {
+                    let mut path = vec![Span::<String>::new(info.string.clone(), info.position)];
                     while self.next_if_eq(DoubleColon)?.is_some() {
                         let identifier = self.expect_identifier()?.into();
                         path.push(identifier);
                     }
                     RawType::Other(path)
                 }
-                _ => RawType::Other(vec![Span::<String>::new(info.string, info.position)]),
+                None => RawType::Other(vec![Span::<String>::new(info.string, info.position)]),
             },
         };
         Ok(Type::new(raw, info.position))
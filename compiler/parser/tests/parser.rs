@@ -1,6 +1,8 @@
 #[cfg(test)]
 #[allow(unused)]
 mod tests {
+    use std::boxed::Box;
+
     use common::position::{PositionRange, Span};
     use diagnostics::DiagnosticResult;
     use lexer::tokenize;
@@ -106,6 +108,7 @@ mod tests {
     ) -> RawNode {
         RawNode::Function {
             name: name.to_string().into(),
+            generics: Vec::new(),
             parameters: parameters.into_iter().map(|param| param.into()).collect(),
             return_type: return_type.into(),
             node: node.into(),
@@ -206,6 +209,47 @@ mod tests {
             Plus
         )
     );
+    parser_test!(
+        shift_below_addition,
+        "a >> 1 + 2",
+        arithmetic(
+            identifier("a"),
+            arithmetic(integer("1"), integer("2"), Plus),
+            RightBitshift
+        )
+    );
+    parser_test!(
+        try_after_field,
+        "a.b? + 1",
+        arithmetic(Try(field(identifier("a"), "b").into()), integer("1"), Plus)
+    );
+    parser_test!(
+        try_operand,
+        "1 + b?",
+        arithmetic(integer("1"), Try(identifier("b").into()), Plus)
+    );
+    parser_test!(
+        cast_operand,
+        "a + b as i64",
+        arithmetic(
+            identifier("a"),
+            Cast(identifier("b").into(), Int(64).into()),
+            Plus
+        )
+    );
+    parser_test!(
+        chained_casts,
+        "a.b as u8 as f64 * 2.5",
+        arithmetic(
+            Cast(
+                Cast(field(identifier("a"), "b").into(), UInt(8).into()).into(),
+                Float64.into()
+            ),
+            Float("2.5".to_string()),
+            Multiply
+        )
+    );
+    parser_test_fail!(cast_without_type, "a as 1");
     parser_test!(one_field, "a.b", field(identifier("a"), "b"));
     parser_test!(
         field_with_order,
@@ -268,6 +312,10 @@ mod tests {
         tuple(vec![integer("1"), integer("2"), integer("3")])
     );
     parser_test!(integer_wrapped, "(1)", wrapped(integer("1")));
+    parser_test_fail!(unclosed_generics, "func max<T(a T) T {}");
+    parser_test_fail!(missing_type_argument_list, "max::(1)");
+    parser_test_fail!(trait_item_without_func, "trait Show { var x = 1 }");
+    parser_test_fail!(impl_for_path, "impl foo::Show for i32 {}");
     parser_test!(
         if_condition,
         "if true == true {}",
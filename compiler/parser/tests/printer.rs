@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod tests {
+    use lexer::tokenize;
+    use parser::parse;
+    use syntax::ast;
+
+    macro_rules! printer_test {
+        ($name:ident, $input:expr) => {
+            #[test]
+            fn $name() {
+                round_trip($input);
+            }
+        };
+    }
+
+    fn print(input: &str) -> String {
+        let tokens = tokenize(input).unwrap();
+        let nodes = parse(tokens).unwrap();
+        ast::Module::new(nodes).to_string()
+    }
+
+    fn round_trip(input: &'static str) {
+        let printed = print(input);
+        assert!(
+            printed == input,
+            "INPUT:\n{input}\n----------------------------\nPRINTED:\n{printed}"
+        );
+        assert!(print(&printed) == printed, "printer is not stable");
+    }
+
+    printer_test!(empty_function, "func main() void {}");
+    printer_test!(
+        function_body,
+        "func main(x i32, &mut y i32) i32 {\n    var mut z: i32 = 5\n    return x + 1 * 2\n}"
+    );
+    printer_test!(import, "import foo\nfunc main() void {}");
+    printer_test!(
+        conditions,
+        "if a == b {\n    return 1\n} else {\n    return 2\n}"
+    );
+    printer_test!(compare_operators, "a != b\na <= b\na > b");
+    printer_test!(call, "foo(1, bar.baz, (1, 2))");
+    printer_test!(attribute, "#[test]");
+    printer_test!(string, "\"hello\"");
+    printer_test!(loops, "loop {\n    break\n}\nwhile true {\n    continue\n}");
+    printer_test!(use_list, "use a::{b, c::{d, e}}\nuse f");
+    printer_test!(path, "foo::bar::baz(1)");
+    printer_test!(
+        generic_function,
+        "func max<T, U>(a T, b U) T {\n    return a\n}"
+    );
+    printer_test!(
+        type_arguments,
+        "max::<i32, bool>(1, true)
+foo::bar::<T>()"
+    );
+    printer_test!(
+        traits,
+        "trait Show {\n    func show(&self) i32 {}\n    func pick(self, other Self) Self {}\n}\nimpl Show for i32 {\n    func show(&self) i32 {\n        return self\n    }\n}"
+    );
+    printer_test!(
+        inherent_impl,
+        "impl i32 {\n    func new() Self {\n        return Self::zero()\n    }\n    func set(&mut self, v i32) void {\n        self = v\n    }\n}"
+    );
+    printer_test!(
+        structs,
+        "pub struct Point {\n    x i32\n    y i32\n}\nvar p = Point { x: 1, y: a.b }\nif p {\n    x\n}"
+    );
+    printer_test!(
+        closures,
+        "var f = |x i32, y| x\nvar g = move || {\n    return 1\n}"
+    );
+    printer_test!(
+        function_type,
+        "func apply(f func(i32, bool) i32, g func() void) void {}"
+    );
+    printer_test!(
+        boxes,
+        "func f(b Box<Box<i32>>) Box<i32> {\n    return Box::new(**b)\n}"
+    );
+    printer_test!(
+        strings,
+        "func greet(name str) String {\n    var mut s = String::from(\"a\\tb\\n\\\"c\\\"\")\n    s.push(name)\n    return s\n}"
+    );
+    printer_test!(
+        results,
+        "func parse(a i32) result<i32, bool> {\n    var b: result<i32, bool> = Ok(a)\n    return Ok(b? + f(a)?)\n}"
+    );
+    printer_test!(
+        generic_bounds,
+        "func f<T: Show + Eq, U>(a T) i32 {\n    return a.show()\n}"
+    );
+    printer_test!(
+        modifiers,
+        "pub import foo\npub func main() void {}\npub unsafe func other() void {}"
+    );
+    printer_test!(casts, "var x = a + b as i64\nvar y = (1.5 as f32) as u16");
+}
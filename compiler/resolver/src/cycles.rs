@@ -0,0 +1,89 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use context::CompilerCtx;
+use diagnostics::{DiagnosticData, DiagnosticSpan};
+use syntax::ast::{Dependency, ModuleCollection};
+
+#[derive(Clone, Copy, PartialEq)]
+enum State {
+    Visiting,
+    Done,
+}
+
+struct Cycles<'a> {
+    collection: &'a ModuleCollection,
+    states: HashMap<&'a PathBuf, State>,
+    stack: Vec<(&'a PathBuf, &'a Dependency)>,
+    found: Vec<Vec<(&'a PathBuf, &'a Dependency)>>,
+}
+
+pub fn check_cycles(compiler: &mut CompilerCtx, collection: &ModuleCollection, entry: &Path) {
+    let mut cycles = Cycles {
+        collection,
+        states: HashMap::new(),
+        stack: Vec::new(),
+        found: Vec::new(),
+    };
+    if let Some((entry, _)) = collection.dependencies.get_key_value(entry) {
+        cycles.visit(entry);
+    }
+
+    for chain in cycles.found {
+        let (relative_path, first) = chain[0];
+
+        let mut modules: Vec<String> = chain.iter().map(|(path, _)| format!("{path:?}")).collect();
+        modules.push(format!("{relative_path:?}"));
+
+        let mut data = DiagnosticData::error()
+            .title(format!("Import cycle detected: {}", modules.join(" -> ")))
+            .position(first.position);
+        for (path, dependency) in &chain {
+            data = data.span(
+                DiagnosticSpan::new(&format!(
+                    "{path:?} imports '{}' ({:?})",
+                    dependency.name, dependency.path
+                ))
+                .path(path.to_path_buf())
+                .position(dependency.position),
+            );
+        }
+
+        compiler.diagnostics.insert(relative_path, data);
+    }
+}
+
+impl<'a> Cycles<'a> {
+    fn visit(&mut self, path: &'a PathBuf) {
+        self.states.insert(path, State::Visiting);
+
+        for dependency in &self.collection.dependencies[path] {
+            self.stack.push((path, dependency));
+
+            match self.states.get(&dependency.path) {
+                Some(State::Visiting) => {
+                    let start = self
+                        .stack
+                        .iter()
+                        .position(|(p, _)| *p == &dependency.path)
+                        .unwrap();
+                    self.found.push(self.stack[start..].to_vec());
+                }
+                Some(State::Done) => {}
+                None => {
+                    if let Some((key, _)) =
+                        self.collection.dependencies.get_key_value(&dependency.path)
+                    {
+                        self.visit(key);
+                    }
+                }
+            }
+
+            self.stack.pop();
+        }
+
+        self.states.insert(path, State::Done);
+    }
+}
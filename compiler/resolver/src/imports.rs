@@ -0,0 +1,69 @@
+use std::path::{Path, PathBuf};
+
+use common::{constants::FILE_EXTENSION, position::PositionRange};
+use context::CompilerCtx;
+use diagnostics::{DiagnosticData, DiagnosticResult};
+
+pub fn resolve_import(
+    compiler: &CompilerCtx,
+    position: PositionRange,
+    current_relative_path: &Path,
+    name: &str,
+) -> DiagnosticResult<PathBuf> {
+    let file_name = current_relative_path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or_default();
+    let is_module = matches!(file_name, "mod" | "main" | "lib");
+
+    let mut parent = current_relative_path
+        .parent()
+        .map(|p| p.to_path_buf())
+        .unwrap_or_default();
+    if !is_module {
+        parent.push(file_name);
+    }
+
+    let mut expected_paths: [PathBuf; 2] = [parent.join(name), parent.join(name).join("mod")];
+    for relative_path in &mut expected_paths {
+        relative_path.set_extension(FILE_EXTENSION);
+    }
+
+    let mut found: Vec<&PathBuf> = expected_paths
+        .iter()
+        .filter(|relative_path| compiler.exists(relative_path))
+        .collect();
+
+    if found.len() > 1 {
+        return DiagnosticData::error()
+            .title(format!(
+                "Ambiguous module '{name}', found both {:?} and {:?}",
+                expected_paths[0], expected_paths[1]
+            ))
+            .position(position)
+            .to_err();
+    }
+
+    if let Some(path) = found.pop() {
+        return Ok(path.clone());
+    }
+
+    let directory = parent.join(name);
+    if !compiler.list(&directory).is_empty() {
+        return DiagnosticData::error()
+            .title(format!(
+                "Unresolved module '{name}', directory {directory:?} has no {:?}",
+                expected_paths[1]
+            ))
+            .position(position)
+            .to_err();
+    }
+
+    DiagnosticData::error()
+        .title(format!(
+            "Unresolved module '{name}', expected {:?} or {:?}",
+            expected_paths[0], expected_paths[1]
+        ))
+        .position(position)
+        .to_err()
+}
@@ -1,61 +1,230 @@
-use diagnostics::DiagnosticData;
+use common::metadata::Metadata;
+use diagnostics::{DiagnosticData, DiagnosticResult};
 use lexer::tokenize;
 use parser::parse;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use syntax::ast;
 
-use context::CompilerCtx;
+use context::{CompilerCtx, files::STD_DIRECTORY as STD};
+use cycles::check_cycles;
+use imports::resolve_import;
+use incremental::{load_ast, source_hash, store_ast};
+use packages::resolve_packages;
+use queue::WorkQueue;
+
+mod cycles;
+mod imports;
+mod names;
+mod packages;
+mod queries;
+mod queue;
+
+pub use names::{ItemNames, resolve_bindings, resolve_items, resolve_names};
+pub use packages::LOCK_FILE;
+pub use queries::{Function, Parse, Resolve, Resolved};
 
 pub fn resolve_modules(compiler: &mut CompilerCtx, entry: &PathBuf) -> ast::ModuleCollection {
-    let mut to_parse: Vec<PathBuf> = Vec::new();
-    to_parse.push(entry.clone());
+    let mut crates = resolve_packages(compiler, entry);
+    attach_std(&mut crates);
 
     let mut collection = ast::ModuleCollection::default();
-    while let Some(relative_path) = to_parse.pop() {
-        let source = match compiler.read(&relative_path) {
-            Some(s) => s,
-            None => {
-                compiler.diagnostics.insert(
-                    &relative_path,
-                    DiagnosticData::error().title(format!("Failed to read: {relative_path:?}")),
-                );
-                continue;
-            }
-        };
+    for (root, metadata) in crates
+        .iter()
+        .filter_map(|c| Some((&c.root, c.metadata.as_ref()?)))
+    {
+        load_metadata(compiler, &mut collection, root, metadata);
+    }
 
-        let tokens = match tokenize(&source) {
-            Ok(t) => t,
-            Err(data) => {
-                compiler.diagnostics.insert(&relative_path, data);
-                continue;
+    let roots = crates
+        .iter()
+        .filter(|c| c.metadata.is_none())
+        .map(|c| c.root.clone())
+        .collect();
+
+    let mut modules = load_modules(compiler, roots);
+    modules.sort_by(|a, b| a.path.cmp(&b.path));
+
+    for module in modules {
+        for data in module.diagnostics {
+            compiler.diagnostics.insert(&module.path, data);
+        }
+        if let Some(nodes) = module.nodes {
+            if compiler.is_incremental() && !module.cached {
+                store_ast(compiler, &module.path, module.hash, &nodes);
             }
+            collection.hashes.insert(module.path.clone(), module.hash);
+            collection
+                .modules
+                .insert(module.path.clone(), ast::Module { nodes });
+            collection
+                .dependencies
+                .insert(module.path, module.dependencies);
+        }
+    }
+
+    for root in crates
+        .iter()
+        .filter(|c| c.metadata.is_none())
+        .map(|c| &c.root)
+    {
+        check_cycles(compiler, &collection, root);
+    }
+    collection.crates = crates;
+
+    collection
+}
+
+fn attach_std(crates: &mut Vec<ast::Crate>) {
+    let root = CompilerCtx::std_root();
+    for krate in crates.iter_mut() {
+        krate.dependencies.push((STD.to_string(), root.clone()));
+    }
+    crates.push(ast::Crate {
+        name: STD.to_string(),
+        root,
+        dependencies: Vec::new(),
+        metadata: None,
+    });
+}
+
+struct LoadedModule {
+    path: PathBuf,
+    nodes: Option<Vec<ast::Node>>,
+    hash: u64,
+    cached: bool,
+    dependencies: Vec<ast::Dependency>,
+    diagnostics: Vec<DiagnosticData>,
+}
+
+fn load_modules(compiler: &CompilerCtx, roots: Vec<PathBuf>) -> Vec<LoadedModule> {
+    let queue = WorkQueue::new(roots);
+    let workers = std::thread::available_parallelism().map_or(1, |n| n.get());
+
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut loaded = Vec::new();
+                    while let Some(job) = queue.next() {
+                        let module = load_module(compiler, job.path.clone());
+                        job.discover(module.dependencies.iter().map(|d| &d.path));
+                        loaded.push(module);
+                    }
+                    loaded
+                })
+            })
+            .collect();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    })
+}
+
+fn load_module(compiler: &CompilerCtx, relative_path: PathBuf) -> LoadedModule {
+    let mut module = LoadedModule {
+        path: relative_path,
+        nodes: None,
+        hash: 0,
+        cached: false,
+        dependencies: Vec::new(),
+        diagnostics: Vec::new(),
+    };
+
+    let source = match compiler.read(&module.path) {
+        Some(s) => s,
+        None => {
+            let title = format!("Failed to read: {:?}", module.path);
+            module
+                .diagnostics
+                .push(DiagnosticData::error().title(title));
+            return module;
+        }
+    };
+
+    module.hash = source_hash(&source);
+    let cached = match compiler.is_incremental() {
+        true => load_ast(compiler, &module.path, module.hash),
+        false => None,
+    };
+    module.cached = cached.is_some();
+
+    let nodes = match cached.map_or_else(|| parse_module(&source), Ok) {
+        Ok(nodes) => nodes,
+        Err(data) => {
+            module.diagnostics.push(data);
+            return module;
+        }
+    };
+
+    for node in &nodes {
+        let import = match node.raw.item() {
+            ast::RawNode::Import(i) => i,
+            _ => continue,
         };
 
-        let nodes = match parse(tokens) {
-            Ok(n) => n,
+        match resolve_import(compiler, import.position, &module.path, &import.raw) {
+            Ok(path) => module.dependencies.push(ast::Dependency {
+                name: import.raw.clone(),
+                path,
+                position: import.position,
+                public: node.raw.is_public(),
+            }),
+            Err(data) => module.diagnostics.push(data),
+        }
+    }
+
+    module.nodes = Some(nodes);
+    module
+}
+
+fn parse_module(source: &str) -> DiagnosticResult<Vec<ast::Node>> {
+    let tokens = tokenize(source)?;
+    parse(tokens)
+}
+
+fn load_metadata(
+    compiler: &mut CompilerCtx,
+    collection: &mut ast::ModuleCollection,
+    root: &Path,
+    metadata: &Metadata,
+) {
+    let directory = root
+        .parent()
+        .and_then(Path::parent)
+        .unwrap_or(Path::new(""));
+    compiler.link(directory.join("target").join(&metadata.library));
+
+    for module in &metadata.modules {
+        let relative_path = directory.join(&module.path);
+        let declarations = module.declarations();
+        collection
+            .hashes
+            .insert(relative_path.clone(), source_hash(&declarations));
+
+        let nodes = match parse_module(&declarations) {
+            Ok(nodes) => nodes,
             Err(data) => {
                 compiler.diagnostics.insert(&relative_path, data);
-                continue;
+                Vec::new()
             }
         };
 
-        let imports: Vec<PathBuf> = nodes
+        let dependencies = module
+            .imports
             .iter()
-            .filter_map(|node| {
-                let import = match &node.raw {
-                    ast::RawNode::Import(i) => &i.raw,
-                    _ => return None,
-                };
-
-                Some(relative_path.join(import))
+            .map(|import| ast::Dependency {
+                name: import.name.clone(),
+                path: directory.join(&import.path),
+                position: Default::default(),
+                public: true,
             })
             .collect();
 
-        to_parse.extend(imports.clone().into_iter());
-
-        let module = ast::Module { nodes };
-        collection.modules.insert(relative_path, module);
+        collection
+            .modules
+            .insert(relative_path.clone(), ast::Module { nodes });
+        collection.dependencies.insert(relative_path, dependencies);
     }
-
-    collection
 }
@@ -0,0 +1,666 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use common::position::PositionRange;
+use context::CompilerCtx;
+use diagnostics::{DiagnosticData, DiagnosticResult, DiagnosticSpan};
+use syntax::{
+    ast::{
+        self, Definition, DefinitionId, DefinitionKind, Identifier, Names, RawNode, Symbol, UsePath,
+    },
+    hir,
+};
+
+#[derive(Debug, Clone, Default)]
+pub struct ItemNames {
+    pub names: Names,
+    parents: HashMap<PathBuf, PathBuf>,
+    externs: HashMap<PathBuf, HashMap<String, DefinitionId>>,
+}
+
+struct NameResolver {
+    names: Names,
+    relative_path: PathBuf,
+    scopes: Vec<HashMap<String, DefinitionId>>,
+    generics: Vec<String>,
+    parents: HashMap<PathBuf, PathBuf>,
+    externs: HashMap<PathBuf, HashMap<String, DefinitionId>>,
+    diagnostics: Vec<(PathBuf, DiagnosticData)>,
+}
+
+pub fn resolve_names(compiler: &mut CompilerCtx, collection: &mut ast::ModuleCollection) {
+    let (items, diagnostics) = resolve_items(collection);
+    let mut resolver = NameResolver::new(items);
+    resolver.diagnostics = diagnostics;
+
+    let mut paths: Vec<&PathBuf> = collection.modules.keys().collect();
+    paths.sort();
+    for path in paths {
+        resolver.module(path, &collection.modules[path].nodes);
+    }
+
+    for (relative_path, data) in resolver.diagnostics {
+        compiler.diagnostics.insert(&relative_path, data);
+    }
+    collection.names = resolver.names;
+}
+
+pub fn resolve_items(
+    collection: &ast::ModuleCollection,
+) -> (ItemNames, Vec<(PathBuf, DiagnosticData)>) {
+    let mut paths: Vec<PathBuf> = collection.modules.keys().cloned().collect();
+    paths.sort();
+
+    let mut parents = HashMap::new();
+    for path in &paths {
+        for dependency in collection.dependencies.get(path).into_iter().flatten() {
+            parents.insert(dependency.path.clone(), path.clone());
+        }
+    }
+
+    let mut resolver = NameResolver::new(ItemNames {
+        parents,
+        ..ItemNames::default()
+    });
+
+    for krate in &collection.crates {
+        resolver.declare_externs(krate);
+    }
+
+    for path in &paths {
+        let dependencies = collection.dependencies.get(path);
+        resolver.declare_items(path, &collection.modules[path], dependencies);
+    }
+
+    let mut uses = Vec::new();
+    for path in &paths {
+        for node in &collection.modules[path].nodes {
+            if let RawNode::Use(use_path) = node.raw.item() {
+                let public = node.raw.is_public();
+                let extracted = use_path.extract_paths();
+                uses.extend(extracted.into_iter().map(|p| (path.clone(), p, public)));
+            }
+        }
+    }
+    resolver.resolve_uses(uses);
+
+    let items = ItemNames {
+        names: resolver.names,
+        parents: resolver.parents,
+        externs: resolver.externs,
+    };
+    (items, resolver.diagnostics)
+}
+
+pub fn resolve_bindings(
+    items: ItemNames,
+    relative_path: &Path,
+    nodes: &[ast::Node],
+) -> (Names, Vec<DiagnosticData>) {
+    let mut resolver = NameResolver::new(items);
+    resolver.module(relative_path, nodes);
+
+    let diagnostics = resolver.diagnostics.into_iter().map(|(_, d)| d).collect();
+    (resolver.names, diagnostics)
+}
+
+impl NameResolver {
+    fn new(items: ItemNames) -> Self {
+        Self {
+            names: items.names,
+            relative_path: PathBuf::new(),
+            scopes: Vec::new(),
+            generics: Vec::new(),
+            parents: items.parents,
+            externs: items.externs,
+            diagnostics: Vec::new(),
+        }
+    }
+    fn error(&mut self, data: DiagnosticData) {
+        self.diagnostics.push((self.relative_path.clone(), data));
+    }
+    fn definition(&mut self, name: &Identifier, kind: DefinitionKind) -> DefinitionId {
+        self.names.define(Definition {
+            name: name.raw.clone(),
+            kind,
+            path: self.relative_path.clone(),
+            position: name.position,
+        })
+    }
+    fn declare_externs(&mut self, krate: &ast::Crate) {
+        self.relative_path = krate.root.clone();
+
+        let mut externs = HashMap::new();
+        for (name, root) in &krate.dependencies {
+            let name = Identifier::new(name.clone(), PositionRange::default());
+            let id = self.definition(&name, DefinitionKind::Module(root.clone()));
+            externs.insert(name.raw, id);
+        }
+        self.externs.insert(krate.root.clone(), externs);
+    }
+    fn declare_items(
+        &mut self,
+        relative_path: &Path,
+        module: &ast::Module,
+        dependencies: Option<&Vec<ast::Dependency>>,
+    ) {
+        self.relative_path = relative_path.to_path_buf();
+        self.names
+            .modules
+            .entry(self.relative_path.clone())
+            .or_default();
+
+        for dependency in dependencies.into_iter().flatten() {
+            let imported = self
+                .names
+                .lookup(relative_path, &dependency.name)
+                .map(|id| self.names.definition(id));
+            if matches!(imported, Some(d) if d.kind == DefinitionKind::Module(dependency.path.clone()))
+            {
+                continue;
+            }
+
+            let name = Identifier::new(dependency.name.clone(), dependency.position);
+            let id = self.definition(&name, DefinitionKind::Module(dependency.path.clone()));
+            self.insert_item(&name, id, dependency.public);
+        }
+
+        for node in &module.nodes {
+            let (name, kind) = match node.raw.item() {
+                RawNode::Function { name, .. } => (name, DefinitionKind::Function),
+                RawNode::Trait { name, .. } => (name, DefinitionKind::Trait),
+                RawNode::Struct { name, .. } => (name, DefinitionKind::Struct),
+                _ => continue,
+            };
+            let id = self.definition(name, kind);
+            self.insert_item(name, id, node.raw.is_public());
+        }
+    }
+    fn insert_item(&mut self, name: &Identifier, id: DefinitionId, public: bool) {
+        let table = self
+            .names
+            .modules
+            .get_mut(&self.relative_path)
+            .expect("Expected a symbol table for the module");
+
+        let existing = match table.get(&name.raw) {
+            Some(existing) if existing.id != id => existing.id,
+            _ => {
+                table.insert(name.raw.clone(), Symbol { id, public });
+                return;
+            }
+        };
+
+        let existing = self.names.definition(existing);
+        let data = DiagnosticData::error()
+            .title(format!("'{}' is defined multiple times", name.raw))
+            .position(name.position)
+            .span(
+                DiagnosticSpan::new(&format!("previous definition of '{}' here", name.raw))
+                    .path(existing.path.clone())
+                    .position(existing.position),
+            );
+        self.error(data);
+    }
+    fn resolve_uses(&mut self, mut uses: Vec<(PathBuf, Vec<Identifier>, bool)>) {
+        loop {
+            let count = uses.len();
+            let mut failed = Vec::new();
+
+            for (relative_path, path, public) in uses {
+                self.relative_path = relative_path.clone();
+                match self.resolve_path(&path) {
+                    Ok(id) => self.insert_item(path.last().unwrap(), id, public),
+                    Err(data) => failed.push((relative_path, path, public, data)),
+                }
+            }
+
+            if failed.is_empty() || failed.len() == count {
+                for (relative_path, _, _, data) in failed {
+                    self.relative_path = relative_path;
+                    self.error(data);
+                }
+                return;
+            }
+            uses = failed.into_iter().map(|(r, p, v, _)| (r, p, v)).collect();
+        }
+    }
+    fn module(&mut self, relative_path: &Path, nodes: &[ast::Node]) {
+        self.relative_path = relative_path.to_path_buf();
+
+        for node in nodes {
+            self.item(node.raw.item());
+        }
+    }
+    fn item(&mut self, raw: &RawNode) {
+        match raw {
+            RawNode::Function {
+                generics,
+                parameters,
+                return_type,
+                node,
+                ..
+            } => self.function(generics, parameters, return_type, node),
+            RawNode::Trait { methods, .. } => {
+                methods.iter().for_each(|m| self.item(m.raw.item()));
+            }
+            RawNode::Struct { fields, .. } => {
+                fields.iter().for_each(|(_, t)| self.data_type(t));
+            }
+            RawNode::Impl {
+                trait_name,
+                data_type,
+                methods,
+            } => {
+                if let Some(name) = trait_name {
+                    self.identifier(&name.raw, name.position);
+                }
+                self.data_type(data_type);
+                methods.iter().for_each(|m| self.item(m.raw.item()));
+            }
+            _ => {}
+        }
+    }
+    fn function(
+        &mut self,
+        generics: &[ast::Generic],
+        parameters: &[ast::Parameter],
+        return_type: &ast::Type,
+        body: &ast::Node,
+    ) {
+        let scopes = std::mem::replace(&mut self.scopes, vec![HashMap::new()]);
+        let names = generics.iter().map(|g| g.raw.name.raw.clone()).collect();
+        let outer = std::mem::replace(&mut self.generics, names);
+
+        for bound in generics.iter().flat_map(|g| &g.raw.bounds) {
+            self.identifier(&bound.raw, bound.position);
+        }
+        for parameter in parameters {
+            self.data_type(&parameter.raw.data_type);
+            self.declare(&parameter.raw.name, DefinitionKind::Parameter);
+        }
+        self.data_type(return_type);
+        self.node(body);
+
+        self.generics = outer;
+        self.scopes = scopes;
+    }
+    fn data_type(&mut self, data_type: &ast::Type) {
+        use ast::RawType;
+
+        match &data_type.raw {
+            RawType::Other(path) if is_type_path(path) => {}
+            RawType::Other(path) if path.len() == 1 && self.generics.contains(&path[0].raw) => {}
+            RawType::Other(path) => {
+                let result = self.resolve_path(path);
+                self.capture(result);
+            }
+            RawType::Ref(_, data_type)
+            | RawType::RefMut(_, data_type)
+            | RawType::Box(data_type)
+            | RawType::Array(data_type, _)
+            | RawType::Slice(data_type) => self.data_type(data_type),
+            RawType::Result(ok, err) => {
+                self.data_type(ok);
+                self.data_type(err);
+            }
+            RawType::Tuple(types) => types.iter().for_each(|t| self.data_type(t)),
+            RawType::Function {
+                parameters,
+                return_type,
+            } => {
+                parameters.iter().for_each(|p| self.data_type(p));
+                self.data_type(return_type);
+            }
+            _ => {}
+        }
+    }
+    fn declare(&mut self, name: &Identifier, kind: DefinitionKind) -> DefinitionId {
+        let id = self.definition(name, kind);
+        self.scopes
+            .last_mut()
+            .expect("Expected a scope")
+            .insert(name.raw.clone(), id);
+        id
+    }
+    fn node(&mut self, node: &ast::Node) {
+        match &node.raw {
+            RawNode::Modifiers(_, node) => self.node(node),
+            RawNode::Function {
+                name,
+                generics,
+                parameters,
+                return_type,
+                node,
+            } => {
+                self.declare(name, DefinitionKind::Function);
+                self.function(generics, parameters, return_type, node);
+            }
+            RawNode::Trait { name, .. } => {
+                self.declare(name, DefinitionKind::Trait);
+                self.item(&node.raw);
+            }
+            raw @ RawNode::Impl { .. } => self.item(raw),
+            RawNode::SetPath { path, value, .. } => {
+                self.node(value);
+                self.identifier(&path.raw, path.position);
+            }
+            RawNode::Declare {
+                name,
+                data_type,
+                node,
+                ..
+            } => {
+                if let Some(data_type) = data_type {
+                    self.data_type(data_type);
+                }
+                self.node(node);
+                self.declare(name, DefinitionKind::Variable);
+            }
+            RawNode::Construct { name, fields } => {
+                self.identifier(&name.raw, name.position);
+                fields.iter().for_each(|(_, value)| self.node(value));
+            }
+            RawNode::Conditional {
+                condition,
+                body,
+                conditions,
+                else_condition,
+            } => {
+                self.node(condition);
+                self.node(body);
+                for (condition, body) in conditions {
+                    self.node(condition);
+                    self.node(body);
+                }
+                if let Some(body) = else_condition {
+                    self.node(body);
+                }
+            }
+            RawNode::Operation { left, right, .. } => {
+                self.node(left);
+                self.node(right);
+            }
+            RawNode::While { condition, body } => {
+                self.node(condition);
+                self.node(body);
+            }
+            RawNode::Field(left, right) => {
+                self.node(left);
+                if let RawNode::Call(_, arguments) = &right.raw {
+                    arguments.iter().for_each(|a| self.node(a));
+                }
+            }
+            RawNode::Call(callee, arguments) => {
+                self.node(callee);
+                arguments.iter().for_each(|a| self.node(a));
+            }
+            RawNode::TypeArguments(node, types) => {
+                self.node(node);
+                types.iter().for_each(|t| self.data_type(t));
+            }
+            RawNode::Cast(node, data_type) => {
+                self.node(node);
+                self.data_type(data_type);
+            }
+            RawNode::Return(Some(node))
+            | RawNode::Break(Some(node))
+            | RawNode::Continue(Some(node))
+            | RawNode::Wrapped(Some(node))
+            | RawNode::Loop(node)
+            | RawNode::Minus(node)
+            | RawNode::Deref(node)
+            | RawNode::Try(node) => self.node(node),
+            RawNode::Tuple(nodes) => nodes.iter().for_each(|n| self.node(n)),
+            RawNode::Block(nodes) => {
+                self.scopes.push(HashMap::new());
+                nodes.iter().for_each(|n| self.node(n));
+                self.scopes.pop();
+            }
+            RawNode::Closure {
+                parameters, body, ..
+            } => {
+                self.scopes.push(HashMap::new());
+                for (name, data_type) in parameters {
+                    if let Some(data_type) = data_type {
+                        self.data_type(data_type);
+                    }
+                    self.declare(name, DefinitionKind::Parameter);
+                }
+                self.node(body);
+                self.scopes.pop();
+            }
+            RawNode::Use(path) => self.local_use(path),
+            RawNode::Identifier(name) => self.identifier(name, node.position),
+            RawNode::Path(path) if is_type_path(path) || is_intrinsic(path) => {}
+            RawNode::Path(path) if self.is_associated(path) => {}
+            RawNode::Path(path) => {
+                let result = self.resolve_path(path);
+                if let Some(id) = self.capture(result) {
+                    self.names.bind(&self.relative_path, node.position, id);
+                }
+            }
+            RawNode::Return(None)
+            | RawNode::Struct { .. }
+            | RawNode::Break(None)
+            | RawNode::Continue(None)
+            | RawNode::Wrapped(None)
+            | RawNode::Attribute(_)
+            | RawNode::Import(_)
+            | RawNode::String(_)
+            | RawNode::Bool(_)
+            | RawNode::Integer(_)
+            | RawNode::Float(_) => {}
+        }
+    }
+    fn is_associated(&mut self, path: &[Identifier]) -> bool {
+        let prefix = &path[..path.len() - 1];
+        if prefix.is_empty() {
+            return false;
+        }
+        match self.resolve_path(prefix) {
+            Ok(id) => self.names.definition(id).kind == DefinitionKind::Struct,
+            Err(_) => false,
+        }
+    }
+    fn local_use(&mut self, path: &UsePath) {
+        for path in path.extract_paths() {
+            let result = self.resolve_path(&path);
+            let id = match self.capture(result) {
+                Some(id) => id,
+                None => continue,
+            };
+            let name = path.last().unwrap();
+            self.scopes
+                .last_mut()
+                .expect("Expected a scope")
+                .insert(name.raw.clone(), id);
+        }
+    }
+    fn identifier(&mut self, name: &str, position: PositionRange) {
+        if self.lookup(name).is_none() && hir::Variant::constructor(name).is_some() {
+            return;
+        }
+
+        let name = Identifier::new(name.to_string(), position);
+        let result = self.resolve_path(&[name]);
+        if let Some(id) = self.capture(result) {
+            self.names.bind(&self.relative_path, position, id);
+        }
+    }
+    fn capture<T>(&mut self, result: DiagnosticResult<T>) -> Option<T> {
+        match result {
+            Ok(value) => Some(value),
+            Err(data) => {
+                self.error(data);
+                None
+            }
+        }
+    }
+    fn lookup(&self, name: &str) -> Option<DefinitionId> {
+        for scope in self.scopes.iter().rev() {
+            if let Some(id) = scope.get(name) {
+                return Some(*id);
+            }
+        }
+        if let Some(id) = self.names.lookup(&self.relative_path, name) {
+            return Some(id);
+        }
+        if let Some(id) = self
+            .externs
+            .get(self.crate_root())
+            .and_then(|e| e.get(name))
+        {
+            return Some(*id);
+        }
+        self.prelude(name)
+    }
+    fn prelude(&self, name: &str) -> Option<DefinitionId> {
+        let symbol = self.names.symbol(&CompilerCtx::std_root(), name)?;
+        symbol.public.then_some(symbol.id)
+    }
+    fn visible_names(&self) -> Vec<&String> {
+        let mut names: Vec<&String> = self.scopes.iter().flat_map(|s| s.keys()).collect();
+        if let Some(table) = self.names.modules.get(&self.relative_path) {
+            names.extend(table.keys());
+        }
+        if let Some(externs) = self.externs.get(self.crate_root()) {
+            names.extend(externs.keys());
+        }
+        if let Some(prelude) = self.names.modules.get(&CompilerCtx::std_root()) {
+            names.extend(prelude.iter().filter(|(_, s)| s.public).map(|(n, _)| n));
+        }
+        names
+    }
+    fn crate_root(&self) -> &PathBuf {
+        let mut current = &self.relative_path;
+        while let Some(parent) = self.parents.get(current) {
+            current = parent;
+        }
+        current
+    }
+    fn is_inside(&self, module: &Path) -> bool {
+        let mut current = Some(&self.relative_path);
+        while let Some(path) = current {
+            if path == module {
+                return true;
+            }
+            current = self.parents.get(path);
+        }
+        false
+    }
+    fn resolve_path(&mut self, path: &[Identifier]) -> DiagnosticResult<DefinitionId> {
+        let (first, rest) = path.split_first().expect("Expected a non-empty path");
+
+        let mut id = match self.lookup(&first.raw) {
+            Some(id) => id,
+            None => {
+                let similar = similar_name(&first.raw, self.visible_names());
+                return not_found(first, "this scope", similar);
+            }
+        };
+        self.names.bind(&self.relative_path, first.position, id);
+
+        let mut previous = first;
+        for segment in rest {
+            let module = match &self.names.definition(id).kind {
+                DefinitionKind::Module(module) => module.clone(),
+                _ => {
+                    return DiagnosticData::error()
+                        .title(format!("'{}' is not a module", previous.raw))
+                        .position(previous.position)
+                        .to_err();
+                }
+            };
+
+            id = match self.names.symbol(&module, &segment.raw) {
+                Some(symbol) if !symbol.public && !self.is_inside(&module) => {
+                    let definition = self.names.definition(symbol.id);
+                    return DiagnosticData::error()
+                        .title(format!(
+                            "'{}' is private to module '{}'",
+                            segment.raw, previous.raw
+                        ))
+                        .position(segment.position)
+                        .span(
+                            DiagnosticSpan::new(&format!(
+                                "'{}' is defined here without 'pub'",
+                                segment.raw
+                            ))
+                            .path(definition.path.clone())
+                            .position(definition.position),
+                        )
+                        .to_err();
+                }
+                Some(symbol) => symbol.id,
+                None => {
+                    let names = self.names.modules.get(&module);
+                    let similar =
+                        similar_name(&segment.raw, names.into_iter().flat_map(|t| t.keys()));
+                    let scope = format!("module '{}'", previous.raw);
+                    return not_found(segment, &scope, similar);
+                }
+            };
+            self.names.bind(&self.relative_path, segment.position, id);
+            previous = segment;
+        }
+
+        Ok(id)
+    }
+}
+
+fn is_type_path(path: &[Identifier]) -> bool {
+    let first = &path[0].raw;
+    matches!(first.as_str(), "Self" | "Box" | "String") || ast::RawType::primitive(first).is_some()
+}
+
+fn is_intrinsic(path: &[Identifier]) -> bool {
+    path.len() == 2 && path[0].raw == hir::INTRINSICS
+}
+
+fn not_found<T>(name: &Identifier, scope: &str, similar: Option<String>) -> DiagnosticResult<T> {
+    let mut data = DiagnosticData::error()
+        .title(format!("'{}' not found in {scope}", name.raw))
+        .position(name.position);
+    if let Some(similar) = similar {
+        data = data.span(DiagnosticSpan::new(&format!(
+            "help: a similar name exists: '{similar}'"
+        )));
+    }
+    data.to_err()
+}
+
+fn similar_name<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a String>,
+) -> Option<String> {
+    let threshold = name.len().div_ceil(3);
+
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= threshold)
+        .min()
+        .map(|(_, candidate)| candidate.clone())
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b) in b.iter().enumerate() {
+            let cost = if a == *b { 0 } else { 1 };
+            let value = (previous[j] + cost)
+                .min(previous[j + 1] + 1)
+                .min(current[j] + 1);
+            current.push(value);
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
@@ -0,0 +1,288 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::{Component, Path, PathBuf},
+};
+
+use common::{
+    config::{Config, Dependency},
+    constants::FILE_EXTENSION,
+    lock::{Lock, REGISTRY_SOURCE},
+    metadata::Metadata,
+};
+use context::CompilerCtx;
+use diagnostics::DiagnosticData;
+use syntax::ast::Crate;
+
+pub const LOCK_FILE: &str = "eclipse.lock";
+
+struct Package {
+    name: String,
+    version: String,
+    source: Option<String>,
+    directory: PathBuf,
+    dependencies: BTreeSet<String>,
+}
+
+pub fn resolve_packages(compiler: &mut CompilerCtx, entry: &Path) -> Vec<Crate> {
+    let mut crates = vec![Crate {
+        name: compiler.config.package.name.clone(),
+        root: entry.to_path_buf(),
+        dependencies: Vec::new(),
+        metadata: None,
+    }];
+    let mut packages = vec![Package {
+        name: compiler.config.package.name.clone(),
+        version: compiler.config.package.version.clone(),
+        source: None,
+        directory: PathBuf::new(),
+        dependencies: BTreeSet::new(),
+    }];
+
+    let registry = registry_path(compiler);
+    let lock = read_lock_file(compiler);
+    let mut known: HashMap<PathBuf, usize> = HashMap::new();
+    let mut queue = vec![(0, compiler.config.dependencies.clone())];
+
+    while let Some((index, dependencies)) = queue.pop() {
+        let config_path = packages[index].directory.join(Config::file_name());
+
+        for (name, dependency) in dependencies {
+            let (directory, source, registered) = match &dependency {
+                Dependency::Path { path } => {
+                    let directory = normalize(&packages[index].directory.join(path));
+                    let source = format!("path+{}", directory.display());
+                    (directory, source, None)
+                }
+                Dependency::Version(requirement)
+                | Dependency::Registry {
+                    version: requirement,
+                } => {
+                    let version = registry_version(compiler, &registry, &name, requirement, &lock);
+                    let directory = registry.join(&name).join(&version);
+                    let source = format!("{REGISTRY_SOURCE}{name}@{version}");
+                    (directory, source, Some(version))
+                }
+            };
+
+            let mut root = directory.join("src").join("lib");
+            root.set_extension(FILE_EXTENSION);
+
+            let dependency_index = match known.get(&directory) {
+                Some(i) => *i,
+                None => {
+                    let config = read_config(compiler, &directory);
+                    let package_name = match &config {
+                        Some(config) => config.package.name.clone(),
+                        None => name.clone(),
+                    };
+                    let metadata = read_metadata(compiler, &directory, &package_name);
+
+                    if metadata.is_none() && !compiler.exists(&root) {
+                        compiler.diagnostics.insert(
+                            &config_path,
+                            DiagnosticData::error().title(format!(
+                                "Dependency '{name}' has no library root, expected {root:?}"
+                            )),
+                        );
+                        continue;
+                    }
+
+                    let version = match (&config, &metadata) {
+                        (Some(config), _) => config.package.version.clone(),
+                        (None, Some(metadata)) => metadata.version.clone(),
+                        (None, None) => registered.unwrap_or_else(|| "0.0.0".into()),
+                    };
+
+                    packages.push(Package {
+                        name: name.clone(),
+                        version,
+                        source: Some(source),
+                        directory: directory.clone(),
+                        dependencies: BTreeSet::new(),
+                    });
+                    crates.push(Crate {
+                        name: name.clone(),
+                        root: root.clone(),
+                        dependencies: Vec::new(),
+                        metadata,
+                    });
+
+                    let dependency_index = packages.len() - 1;
+                    known.insert(directory, dependency_index);
+                    if let Some(config) = config {
+                        queue.push((dependency_index, config.dependencies));
+                    }
+                    dependency_index
+                }
+            };
+
+            packages[index].dependencies.insert(name.clone());
+            let root = crates[dependency_index].root.clone();
+            crates[index].dependencies.push((name, root));
+        }
+    }
+
+    write_lock_file(compiler, &packages);
+    crates
+}
+
+fn read_config(compiler: &mut CompilerCtx, directory: &Path) -> Option<Config> {
+    let path = directory.join(Config::file_name());
+    let source = compiler.read(&path)?;
+
+    match Config::parse(&source) {
+        Ok(config) => Some(config),
+        Err(message) => {
+            compiler.diagnostics.insert(
+                &path,
+                DiagnosticData::error().title(format!("Invalid config: {message}")),
+            );
+            None
+        }
+    }
+}
+
+fn read_metadata(compiler: &mut CompilerCtx, directory: &Path, package: &str) -> Option<Metadata> {
+    let path = directory.join("target").join(Metadata::file_name(package));
+    let source = compiler.read(&path)?;
+
+    match Metadata::parse(&source) {
+        Ok(metadata) => Some(metadata),
+        Err(message) => {
+            compiler.diagnostics.insert(
+                &path,
+                DiagnosticData::error().title(format!("Invalid metadata: {message}")),
+            );
+            None
+        }
+    }
+}
+
+fn registry_path(compiler: &CompilerCtx) -> PathBuf {
+    if let Some(path) = &compiler.config.registry.path {
+        return PathBuf::from(path);
+    }
+    if let Ok(path) = std::env::var("ECLIPSE_REGISTRY") {
+        return PathBuf::from(path);
+    }
+
+    let home = std::env::var("HOME").unwrap_or_default();
+    PathBuf::from(home).join(".eclipse").join("registry")
+}
+
+fn registry_version(
+    compiler: &CompilerCtx,
+    registry: &Path,
+    name: &str,
+    requirement: &str,
+    lock: &Option<Lock>,
+) -> String {
+    let available: Vec<String> = compiler
+        .list(&registry.join(name))
+        .iter()
+        .filter_map(|path| Some(path.file_name()?.to_str()?.to_string()))
+        .filter(|version| version == requirement || version.starts_with(&format!("{requirement}.")))
+        .collect();
+
+    let locked = lock
+        .iter()
+        .flat_map(|lock| lock.registry_versions(name))
+        .find(|version| available.iter().any(|v| v == version));
+    if let Some(version) = locked {
+        return version.to_string();
+    }
+
+    available
+        .into_iter()
+        .max_by_key(|version| version_key(version))
+        .unwrap_or_else(|| requirement.to_string())
+}
+
+fn version_key(version: &str) -> Vec<Option<u64>> {
+    version.split('.').map(|part| part.parse().ok()).collect()
+}
+
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) =>
+            {
+                normalized.pop();
+            }
+            component => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+fn read_lock_file(compiler: &mut CompilerCtx) -> Option<Lock> {
+    let path = PathBuf::from(LOCK_FILE);
+    let source = compiler.read(&path)?;
+
+    match Lock::parse(&source) {
+        Ok(lock) => Some(lock),
+        Err(message) => {
+            compiler.diagnostics.insert(
+                &path,
+                DiagnosticData::error().title(format!("Invalid lock file: {message}")),
+            );
+            None
+        }
+    }
+}
+
+fn write_lock_file(compiler: &mut CompilerCtx, packages: &[Package]) {
+    let mut packages: Vec<&Package> = packages.iter().collect();
+    packages.sort_by(|a, b| (&a.name, &a.source).cmp(&(&b.name, &b.source)));
+
+    let mut lock = String::from(
+        "# This file is automatically generated by eclipse.\n# It is not intended for manual editing.\n",
+    );
+    for package in packages {
+        lock.push_str(&format!(
+            "\n[[package]]\nname = {}\nversion = {}\n",
+            toml_string(&package.name),
+            toml_string(&package.version)
+        ));
+        if let Some(source) = &package.source {
+            lock.push_str(&format!("source = {}\n", toml_string(source)));
+        }
+        if !package.dependencies.is_empty() {
+            let dependencies: Vec<String> = package
+                .dependencies
+                .iter()
+                .map(|d| toml_string(d))
+                .collect();
+            lock.push_str(&format!("dependencies = [{}]\n", dependencies.join(", ")));
+        }
+    }
+
+    let path = PathBuf::from(LOCK_FILE);
+    if compiler.read(&path).as_ref() != Some(&lock) {
+        compiler.write(&path, &lock);
+    }
+}
+
+fn toml_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for char in value.chars() {
+        match char {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\t' => escaped.push_str("\\t"),
+            '\r' => escaped.push_str("\\r"),
+            char if char.is_control() => escaped.push_str(&format!("\\u{:04X}", char as u32)),
+            char => escaped.push(char),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
@@ -0,0 +1,142 @@
+use std::{
+    collections::HashMap,
+    fmt::Debug,
+    hash::{DefaultHasher, Hash, Hasher},
+    path::{Path, PathBuf},
+};
+
+use common::config::Config;
+use context::{CompilerCtx, Query, Source};
+use diagnostics::{DiagnosticData, DiagnosticResult};
+use syntax::ast;
+
+use crate::{ItemNames, parse_module, resolve_items, resolve_modules};
+
+#[derive(Debug, Default)]
+pub struct Resolved {
+    pub crates: Vec<ast::Crate>,
+    pub modules: Vec<PathBuf>,
+    pub metadata: Vec<(PathBuf, Vec<ast::Node>)>,
+    pub items: ItemNames,
+    pub diagnostics: Vec<(PathBuf, DiagnosticData)>,
+}
+impl Resolved {
+    pub fn metadata(&self, relative_path: &Path) -> Option<&Vec<ast::Node>> {
+        self.metadata
+            .iter()
+            .find(|(path, _)| path == relative_path)
+            .map(|(_, nodes)| nodes)
+    }
+}
+
+pub struct Resolve;
+impl Query for Resolve {
+    type Key = PathBuf;
+    type Value = Resolved;
+
+    fn execute(compiler: &mut CompilerCtx, entry: &PathBuf) -> Self::Value {
+        let previous = std::mem::take(&mut compiler.diagnostics);
+        let mut collection = resolve_modules(compiler, entry);
+        let (items, errors) = resolve_items(&collection);
+        let mut diagnostics = std::mem::replace(&mut compiler.diagnostics, previous).entries();
+        diagnostics.extend(errors);
+
+        let mut configs: Vec<PathBuf> = collection
+            .crates
+            .iter()
+            .filter_map(|krate| Some(krate.root.parent()?.parent()?.join(Config::file_name())))
+            .collect();
+        configs.sort();
+        configs.dedup();
+        for config in configs {
+            compiler.query::<Source>(config);
+        }
+
+        let mut modules: Vec<PathBuf> = collection.modules.keys().cloned().collect();
+        modules.sort();
+        let mut metadata = Vec::new();
+        for path in &modules {
+            if compiler.query::<Source>(path.clone()).is_none()
+                && let Some(module) = collection.modules.remove(path)
+            {
+                metadata.push((path.clone(), module.nodes));
+            }
+        }
+
+        Resolved {
+            crates: collection.crates,
+            modules,
+            metadata,
+            items,
+            diagnostics,
+        }
+    }
+
+    fn cycle(_: &PathBuf) -> Self::Value {
+        Resolved::default()
+    }
+
+    fn fingerprint(value: &Self::Value) -> u64 {
+        let names = &value.items.names;
+        let mut hasher = DefaultHasher::new();
+        format!("{:?}", value.crates).hash(&mut hasher);
+        format!("{:?}", value.modules).hash(&mut hasher);
+        format!("{:?}", value.metadata).hash(&mut hasher);
+        format!("{:?}", names.definitions).hash(&mut hasher);
+        for (path, table) in sorted(&names.modules) {
+            format!("{path:?} {:?}", sorted(table)).hash(&mut hasher);
+        }
+        for (path, bindings) in sorted(&names.bindings) {
+            format!("{path:?} {:?}", sorted(bindings)).hash(&mut hasher);
+        }
+        format!("{:?}", value.diagnostics).hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+fn sorted<K: Debug, V: Debug>(map: &HashMap<K, V>) -> Vec<(String, &V)> {
+    let mut entries: Vec<(String, &V)> = map.iter().map(|(k, v)| (format!("{k:?}"), v)).collect();
+    entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+    entries
+}
+
+pub struct Parse;
+impl Query for Parse {
+    type Key = PathBuf;
+    type Value = DiagnosticResult<Vec<ast::Node>>;
+
+    fn execute(compiler: &mut CompilerCtx, relative_path: &PathBuf) -> Self::Value {
+        match compiler.query::<Source>(relative_path.clone()).as_ref() {
+            Some(source) => parse_module(source),
+            None => DiagnosticData::error()
+                .title(format!("Failed to read: {relative_path:?}"))
+                .to_err(),
+        }
+    }
+
+    fn cycle(relative_path: &PathBuf) -> Self::Value {
+        DiagnosticData::error()
+            .title(format!("Cyclic parse of {relative_path:?}"))
+            .to_err()
+    }
+}
+
+pub struct Function;
+impl Query for Function {
+    type Key = (PathBuf, String);
+    type Value = Option<ast::Node>;
+
+    fn execute(compiler: &mut CompilerCtx, (relative_path, name): &Self::Key) -> Self::Value {
+        let nodes = compiler.query::<Parse>(relative_path.clone());
+        let nodes = nodes.as_ref().as_ref().ok()?;
+
+        nodes
+            .iter()
+            .find(|node| matches!(node.raw.item(), ast::RawNode::Function { name: n, .. } if n.raw == *name))
+            .cloned()
+    }
+
+    fn cycle(_: &Self::Key) -> Self::Value {
+        None
+    }
+}
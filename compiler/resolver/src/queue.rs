@@ -0,0 +1,69 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    path::PathBuf,
+    sync::{Condvar, Mutex},
+};
+
+struct State {
+    pending: VecDeque<PathBuf>,
+    visited: HashSet<PathBuf>,
+    active: usize,
+}
+
+pub struct WorkQueue {
+    state: Mutex<State>,
+    ready: Condvar,
+}
+impl WorkQueue {
+    pub fn new(roots: Vec<PathBuf>) -> Self {
+        let visited = roots.iter().cloned().collect();
+        Self {
+            state: Mutex::new(State {
+                pending: roots.into(),
+                visited,
+                active: 0,
+            }),
+            ready: Condvar::new(),
+        }
+    }
+    pub fn next(&self) -> Option<Job<'_>> {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some(path) = state.pending.pop_front() {
+                state.active += 1;
+                return Some(Job { queue: self, path });
+            }
+            if state.active == 0 {
+                return None;
+            }
+            state = self.ready.wait(state).unwrap();
+        }
+    }
+    fn push<'a>(&self, paths: impl IntoIterator<Item = &'a PathBuf>) {
+        let mut state = self.state.lock().unwrap();
+        for path in paths {
+            if state.visited.insert(path.clone()) {
+                state.pending.push_back(path.clone());
+            }
+        }
+    }
+    fn done(&self) {
+        self.state.lock().unwrap().active -= 1;
+        self.ready.notify_all();
+    }
+}
+
+pub struct Job<'a> {
+    queue: &'a WorkQueue,
+    pub path: PathBuf,
+}
+impl Job<'_> {
+    pub fn discover<'a>(&self, paths: impl IntoIterator<Item = &'a PathBuf>) {
+        self.queue.push(paths);
+    }
+}
+impl Drop for Job<'_> {
+    fn drop(&mut self) {
+        self.queue.done();
+    }
+}
@@ -0,0 +1,195 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use context::{CompilerCtx, files::MockResolver};
+    use resolver::{resolve_modules, resolve_names};
+    use syntax::ast::{DefinitionKind, ModuleCollection};
+
+    fn resolve(files: &[(&str, &str)]) -> (CompilerCtx, ModuleCollection) {
+        let mut compiler = CompilerCtx::builder()
+            .project_path(PathBuf::new())
+            .resolver(MockResolver::new())
+            .build();
+        for (path, source) in files {
+            compiler.write(&PathBuf::from(path), source);
+        }
+
+        let mut collection = resolve_modules(&mut compiler, &CompilerCtx::entry());
+        resolve_names(&mut compiler, &mut collection);
+        (compiler, collection)
+    }
+
+    fn resolved(files: &[(&str, &str)]) -> ModuleCollection {
+        let (compiler, collection) = resolve(files);
+        if compiler.diagnostics.has_errors() {
+            compiler.diagnostics.display();
+            panic!("Expected to not fail\nFILES:\n{files:#?}")
+        }
+        collection
+    }
+
+    fn failed(files: &[(&str, &str)], message: &str) {
+        let (compiler, _) = resolve(files);
+        let rendered = compiler.diagnostics.render();
+        assert!(
+            compiler.diagnostics.has_errors() && rendered.contains(message),
+            "Expected an error containing {message:?}\nFILES:\n{files:#?}\nGOT:\n{rendered}"
+        );
+    }
+
+    fn uses_of(collection: &ModuleCollection, file: &str, name: &str) -> Vec<(usize, usize)> {
+        let names = &collection.names;
+        let mut uses: Vec<_> = names.bindings[&PathBuf::from(file)]
+            .iter()
+            .map(|(position, id)| (position, names.definition(*id)))
+            .filter(|(position, d)| d.name == name && d.position != **position)
+            .map(|(position, d)| (position.start.character, d.position.start.character))
+            .collect();
+        uses.sort();
+        uses
+    }
+
+    #[test]
+    fn locals_and_parameters() {
+        let collection = resolved(&[(
+            "src/main.ecl",
+            "func main(a i32) i32 { var b = a return b }",
+        )]);
+        assert_eq!(uses_of(&collection, "src/main.ecl", "a").len(), 1);
+        assert_eq!(uses_of(&collection, "src/main.ecl", "b").len(), 1);
+    }
+
+    #[test]
+    fn shadowing() {
+        let source = "func main() { var x = 1 { var x = x return x } return x }";
+        let collection = resolved(&[("src/main.ecl", source)]);
+
+        let outer = source.find("var x").unwrap() + 5;
+        let inner = source.rfind("var x").unwrap() + 5;
+        let targets: Vec<usize> = uses_of(&collection, "src/main.ecl", "x")
+            .into_iter()
+            .map(|(_, definition)| definition)
+            .collect();
+
+        assert_eq!(targets.len(), 3);
+        assert_eq!(targets, vec![outer, inner, outer]);
+    }
+
+    #[test]
+    fn block_scope_ends() {
+        failed(
+            &[("src/main.ecl", "func main() { { var x = 1 } return x }")],
+            "'x' not found in this scope",
+        );
+    }
+
+    #[test]
+    fn use_list() {
+        let collection = resolved(&[
+            (
+                "src/main.ecl",
+                "import foo\nuse foo::{bar, baz::qux}\nfunc main() { bar() qux() }",
+            ),
+            ("src/foo.ecl", "pub import baz\npub func bar() {}"),
+            ("src/foo/baz.ecl", "pub func qux() {}"),
+        ]);
+
+        let names = &collection.names;
+        let main = PathBuf::from("src/main.ecl");
+        let qux = names.lookup(&main, "qux").unwrap();
+        assert_eq!(names.definition(qux).kind, DefinitionKind::Function);
+        assert_eq!(names.definition(qux).path, PathBuf::from("src/foo/baz.ecl"));
+        assert_eq!(uses_of(&collection, "src/main.ecl", "bar").len(), 2);
+    }
+
+    #[test]
+    fn path_expression() {
+        let collection = resolved(&[
+            ("src/main.ecl", "import foo\nfunc main() { foo::bar() }"),
+            ("src/foo.ecl", "pub func bar() {}"),
+        ]);
+        let names = &collection.names;
+        let bar = names.lookup(&PathBuf::from("src/foo.ecl"), "bar").unwrap();
+        assert!(
+            names.bindings[&PathBuf::from("src/main.ecl")]
+                .values()
+                .any(|id| *id == bar)
+        );
+    }
+
+    #[test]
+    fn use_order_independent() {
+        resolved(&[
+            (
+                "src/main.ecl",
+                "import foo\nuse bar::qux\nuse foo::bar\nfunc main() { qux() }",
+            ),
+            ("src/foo.ecl", "pub import bar"),
+            ("src/foo/bar.ecl", "pub func qux() {}"),
+        ]);
+    }
+
+    #[test]
+    fn similar_name_suggestion() {
+        failed(
+            &[("src/main.ecl", "func main() { var count = 1 return coutn }")],
+            "a similar name exists: 'count'",
+        );
+    }
+
+    #[test]
+    fn missing_in_module() {
+        failed(
+            &[
+                ("src/main.ecl", "import foo\nuse foo::baa"),
+                ("src/foo.ecl", "func bar() {}"),
+            ],
+            "'baa' not found in module 'foo'",
+        );
+    }
+
+    #[test]
+    fn not_a_module() {
+        failed(
+            &[("src/main.ecl", "func f() {}\nuse f::g")],
+            "'f' is not a module",
+        );
+    }
+
+    #[test]
+    fn defined_multiple_times() {
+        failed(
+            &[("src/main.ecl", "func a() {}\nfunc a() {}")],
+            "'a' is defined multiple times",
+        );
+    }
+
+    #[test]
+    fn prelude_names() {
+        let collection = resolved(&[(
+            "src/main.ecl",
+            "func main() { println(\"a\") var n = max(1, 2) }",
+        )]);
+        assert_eq!(uses_of(&collection, "src/main.ecl", "println").len(), 1);
+        assert_eq!(uses_of(&collection, "src/main.ecl", "max").len(), 1);
+    }
+
+    #[test]
+    fn prelude_shadowed() {
+        let collection = resolved(&[("src/main.ecl", "func max() {}\nfunc main() { max() }")]);
+        assert_eq!(uses_of(&collection, "src/main.ecl", "max"), vec![(16, 6)]);
+    }
+
+    #[test]
+    fn std_paths() {
+        resolved(&[(
+            "src/main.ecl",
+            "use std::string::join\nfunc main() { std::math::abs(1) join(\"a\", \"b\", \"c\") }",
+        )]);
+        failed(
+            &[("src/main.ecl", "func main() { std::math::hidden() }")],
+            "'hidden' not found in module 'math'",
+        );
+    }
+}
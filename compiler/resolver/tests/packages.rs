@@ -0,0 +1,287 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use context::{CompilerCtx, files::MockResolver};
+    use resolver::{LOCK_FILE, resolve_modules, resolve_names};
+    use syntax::ast::ModuleCollection;
+
+    fn resolve(files: &[(&str, &str)]) -> (CompilerCtx, ModuleCollection) {
+        let mut compiler = CompilerCtx::builder()
+            .project_path(PathBuf::new())
+            .resolver(MockResolver::new())
+            .build();
+        for (path, source) in files {
+            compiler.write(&PathBuf::from(path), source);
+        }
+        compiler.load_config();
+
+        let mut collection = resolve_modules(&mut compiler, &CompilerCtx::entry());
+        resolve_names(&mut compiler, &mut collection);
+        (compiler, collection)
+    }
+
+    fn resolved(files: &[(&str, &str)]) -> (CompilerCtx, ModuleCollection) {
+        let (compiler, collection) = resolve(files);
+        if compiler.diagnostics.has_errors() {
+            compiler.diagnostics.display();
+            panic!("Expected to not fail\nFILES:\n{files:#?}")
+        }
+        (compiler, collection)
+    }
+
+    fn failed(files: &[(&str, &str)], message: &str) {
+        let (compiler, _) = resolve(files);
+        let rendered = compiler.diagnostics.render();
+        assert!(
+            compiler.diagnostics.has_errors() && rendered.contains(message),
+            "Expected an error containing {message:?}\nFILES:\n{files:#?}\nGOT:\n{rendered}"
+        );
+    }
+
+    const APP: &str = "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\n[dependencies]\nmath = { path = \"../math\" }\n";
+    const MATH: &str = "[package]\nname = \"math\"\nversion = \"1.2.0\"\n\n[dependencies]\nutil = { path = \"../util\" }\n";
+
+    #[test]
+    fn path_dependency() {
+        let (compiler, collection) = resolved(&[
+            ("eclipse.toml", APP),
+            ("src/main.ecl", "func main() { math::add() }"),
+            ("../math/src/lib.ecl", "import ops\npub func add() {}"),
+            ("../math/src/ops.ecl", "pub func sub() {}"),
+        ]);
+
+        let roots: Vec<&PathBuf> = collection.crates.iter().map(|c| &c.root).collect();
+        assert_eq!(
+            roots,
+            vec![
+                &PathBuf::from("src/main.ecl"),
+                &PathBuf::from("../math/src/lib.ecl"),
+                &CompilerCtx::std_root()
+            ]
+        );
+        assert!(
+            collection
+                .modules
+                .contains_key(&PathBuf::from("../math/src/ops.ecl"))
+        );
+        assert!(compiler.read(&PathBuf::from(LOCK_FILE)).is_some());
+    }
+
+    #[test]
+    fn lock_file() {
+        let (compiler, _) = resolved(&[
+            ("eclipse.toml", APP),
+            ("src/main.ecl", "func main() {}"),
+            ("../math/eclipse.toml", MATH),
+            ("../math/src/lib.ecl", ""),
+            ("../util/src/lib.ecl", ""),
+        ]);
+
+        let lock = compiler.read(&PathBuf::from(LOCK_FILE)).unwrap();
+        assert_eq!(
+            lock,
+            "# This file is automatically generated by eclipse.
+# It is not intended for manual editing.
+
+[[package]]
+name = \"app\"
+version = \"0.1.0\"
+dependencies = [\"math\"]
+
+[[package]]
+name = \"math\"
+version = \"1.2.0\"
+source = \"path+../math\"
+dependencies = [\"util\"]
+
+[[package]]
+name = \"util\"
+version = \"0.0.0\"
+source = \"path+../util\"
+"
+        );
+    }
+
+    #[test]
+    fn lock_file_escapes_strings() {
+        let (compiler, _) = resolved(&[
+            (
+                "eclipse.toml",
+                "[dependencies]\nmath = { path = \"../cafe\\u0301 \\\"x\\\"\" }\n",
+            ),
+            ("src/main.ecl", "func main() {}"),
+            ("../cafe\u{301} \"x\"/src/lib.ecl", ""),
+        ]);
+
+        let lock = compiler.read(&PathBuf::from(LOCK_FILE)).unwrap();
+        assert!(
+            lock.contains("source = \"path+../cafe\u{301} \\\"x\\\"\"\n"),
+            "{lock}"
+        );
+    }
+
+    #[test]
+    fn transitive_dependency_is_not_in_scope() {
+        failed(
+            &[
+                ("eclipse.toml", APP),
+                ("src/main.ecl", "func main() { util::id() }"),
+                ("../math/eclipse.toml", MATH),
+                ("../math/src/lib.ecl", "func main() { util::id() }"),
+                ("../util/src/lib.ecl", "pub func id() {}"),
+            ],
+            "'util' not found in this scope\n\t--> \"src/main.ecl\"",
+        );
+    }
+
+    #[test]
+    fn registry_dependency() {
+        resolved(&[
+            (
+                "eclipse.toml",
+                "[registry]\npath = \"registry\"\n\n[dependencies]\njson = \"1.0.0\"\n",
+            ),
+            ("src/main.ecl", "use json::parse\nfunc main() { parse() }"),
+            ("registry/json/1.0.0/src/lib.ecl", "pub func parse() {}"),
+        ]);
+    }
+
+    const JSON: &str = "[registry]\npath = \"registry\"\n\n[dependencies]\njson = \"1\"\n";
+    const JSON_LOCK: &str =
+        "[[package]]\nname = \"json\"\nversion = \"1.2.0\"\nsource = \"registry+json@1.2.0\"\n";
+
+    fn registry(lock: Option<&str>, main: &str) -> (CompilerCtx, ModuleCollection) {
+        let mut files = vec![
+            ("eclipse.toml", JSON),
+            ("src/main.ecl", main),
+            ("registry/json/1.2.0/src/lib.ecl", "pub func old() {}"),
+            ("registry/json/1.10.0/src/lib.ecl", "pub func new() {}"),
+            ("registry/json/2.0.0/src/lib.ecl", "pub func next() {}"),
+        ];
+        if let Some(lock) = lock {
+            files.push((LOCK_FILE, lock));
+        }
+        resolved(&files)
+    }
+
+    #[test]
+    fn registry_picks_newest_match() {
+        let (compiler, _) = registry(None, "use json::new\nfunc main() { new() }");
+
+        let lock = compiler.read(&PathBuf::from(LOCK_FILE)).unwrap();
+        assert!(
+            lock.contains("source = \"registry+json@1.10.0\"\n"),
+            "{lock}"
+        );
+    }
+
+    #[test]
+    fn registry_prefers_locked_version() {
+        let (compiler, _) = registry(Some(JSON_LOCK), "use json::old\nfunc main() { old() }");
+
+        let lock = compiler.read(&PathBuf::from(LOCK_FILE)).unwrap();
+        assert!(
+            lock.contains("version = \"1.2.0\"\nsource = \"registry+json@1.2.0\"\n"),
+            "{lock}"
+        );
+    }
+
+    #[test]
+    fn registry_ignores_stale_lock() {
+        let stale = JSON_LOCK.replace("1.2.0", "0.9.0");
+        let (compiler, _) = registry(Some(&stale), "use json::new\nfunc main() { new() }");
+
+        let lock = compiler.read(&PathBuf::from(LOCK_FILE)).unwrap();
+        assert!(
+            lock.contains("source = \"registry+json@1.10.0\"\n"),
+            "{lock}"
+        );
+    }
+
+    #[test]
+    fn invalid_lock_file() {
+        failed(
+            &[
+                ("eclipse.toml", JSON),
+                ("src/main.ecl", "func main() {}"),
+                ("registry/json/1.2.0/src/lib.ecl", ""),
+                (LOCK_FILE, "[[package]]\nname = 1\n"),
+            ],
+            "Invalid lock file",
+        );
+    }
+
+    #[test]
+    fn missing_library_root() {
+        failed(
+            &[("eclipse.toml", APP), ("src/main.ecl", "func main() {}")],
+            "Dependency 'math' has no library root",
+        );
+    }
+
+    const MATH_META: &str = "name = \"math\"\nversion = \"1.2.0\"\nlibrary = \"libmath.a\"\n\n[[modules]]\npath = \"src/lib.ecl\"\nimports = [{ name = \"ops\", path = \"src/ops.ecl\" }]\nfunctions = [{ name = \"add\", parameters = [\"a i32\", \"b i32\"], return-type = \"i32\" }]\n\n[[modules]]\npath = \"src/ops.ecl\"\nfunctions = [{ name = \"sub\", parameters = [], return-type = \"void\" }]\n";
+
+    #[test]
+    fn prebuilt_dependency() {
+        let (compiler, collection) = resolved(&[
+            ("eclipse.toml", APP),
+            (
+                "src/main.ecl",
+                "use math::{add, ops::sub}\nfunc main() { add() sub() }",
+            ),
+            ("../math/target/math.meta.toml", MATH_META),
+        ]);
+
+        assert_eq!(
+            compiler.libraries(),
+            &[PathBuf::from("../math/target/libmath.a")]
+        );
+        assert_eq!(
+            collection.modules[&PathBuf::from("../math/src/lib.ecl")].to_string(),
+            "pub extern \"eclipse\" func add(a i32, b i32) i32 {}"
+        );
+        assert!(
+            compiler
+                .read(&PathBuf::from(LOCK_FILE))
+                .unwrap()
+                .contains("version = \"1.2.0\"")
+        );
+    }
+
+    #[test]
+    fn prebuilt_private_item() {
+        failed(
+            &[
+                ("eclipse.toml", APP),
+                ("src/main.ecl", "func main() { math::hidden() }"),
+                ("../math/target/math.meta.toml", MATH_META),
+            ],
+            "'hidden' not found in module 'math'",
+        );
+    }
+
+    #[test]
+    fn invalid_metadata() {
+        failed(
+            &[
+                ("eclipse.toml", APP),
+                ("src/main.ecl", "func main() {}"),
+                ("../math/target/math.meta.toml", "modules = 1"),
+            ],
+            "Invalid metadata",
+        );
+    }
+
+    #[test]
+    fn private_dependency_item() {
+        failed(
+            &[
+                ("eclipse.toml", APP),
+                ("src/main.ecl", "func main() { math::add() }"),
+                ("../math/src/lib.ecl", "func add() {}"),
+            ],
+            "'add' is private to module 'math'",
+        );
+    }
+}
@@ -0,0 +1,215 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use context::{
+        CompilerCtx,
+        files::{MockResolver, StdResolver},
+    };
+    use resolver::resolve_modules;
+
+    fn resolve(files: &[(&str, &str)]) -> (CompilerCtx, Vec<PathBuf>) {
+        let mut compiler = CompilerCtx::builder()
+            .project_path(PathBuf::new())
+            .resolver(MockResolver::new())
+            .build();
+        for (path, source) in files {
+            compiler.write(&PathBuf::from(path), source);
+        }
+
+        let collection = resolve_modules(&mut compiler, &CompilerCtx::entry());
+        let mut paths: Vec<PathBuf> = collection
+            .modules
+            .into_keys()
+            .filter(|path| !StdResolver::contains(path))
+            .collect();
+        paths.sort();
+        (compiler, paths)
+    }
+
+    fn resolved(files: &[(&str, &str)], expected: &[&str]) {
+        let (compiler, paths) = resolve(files);
+        if compiler.diagnostics.has_errors() {
+            compiler.diagnostics.display();
+            panic!("Expected to not fail\nFILES:\n{files:#?}")
+        }
+
+        let expected: Vec<PathBuf> = expected.iter().map(PathBuf::from).collect();
+        assert_eq!(paths, expected);
+    }
+
+    fn failed(files: &[(&str, &str)]) {
+        let (compiler, _) = resolve(files);
+        assert!(
+            compiler.diagnostics.has_errors(),
+            "Expected to fail\nFILES:\n{files:#?}"
+        );
+    }
+
+    #[test]
+    fn sibling_file() {
+        resolved(
+            &[("src/main.ecl", "import foo"), ("src/foo.ecl", "")],
+            &["src/foo.ecl", "src/main.ecl"],
+        );
+    }
+
+    #[test]
+    fn sibling_mod_file() {
+        resolved(
+            &[("src/main.ecl", "import foo"), ("src/foo/mod.ecl", "")],
+            &["src/foo/mod.ecl", "src/main.ecl"],
+        );
+    }
+
+    #[test]
+    fn mod_file_imports_siblings() {
+        resolved(
+            &[
+                ("src/main.ecl", "import foo"),
+                ("src/foo/mod.ecl", "import bar"),
+                ("src/foo/bar.ecl", ""),
+            ],
+            &["src/foo/bar.ecl", "src/foo/mod.ecl", "src/main.ecl"],
+        );
+    }
+
+    #[test]
+    fn file_imports_into_own_directory() {
+        resolved(
+            &[
+                ("src/main.ecl", "import foo"),
+                ("src/foo.ecl", "import bar import baz"),
+                ("src/foo/bar.ecl", ""),
+                ("src/foo/baz/mod.ecl", ""),
+            ],
+            &[
+                "src/foo/bar.ecl",
+                "src/foo/baz/mod.ecl",
+                "src/foo.ecl",
+                "src/main.ecl",
+            ],
+        );
+    }
+
+    #[test]
+    fn missing_module() {
+        failed(&[("src/main.ecl", "import foo")]);
+    }
+
+    #[test]
+    fn directory_without_mod_file() {
+        let (compiler, _) = resolve(&[("src/main.ecl", "import foo"), ("src/foo/bar.ecl", "")]);
+        assert!(
+            compiler
+                .diagnostics
+                .render()
+                .contains("directory \"src/foo\" has no \"src/foo/mod.ecl\"")
+        );
+    }
+
+    #[test]
+    fn file_does_not_import_siblings() {
+        failed(&[
+            ("src/main.ecl", "import foo"),
+            ("src/foo.ecl", "import bar"),
+            ("src/bar.ecl", ""),
+        ]);
+    }
+
+    #[test]
+    fn ambiguous_module() {
+        failed(&[
+            ("src/main.ecl", "import foo"),
+            ("src/foo.ecl", ""),
+            ("src/foo/mod.ecl", ""),
+        ]);
+    }
+
+    #[test]
+    fn duplicate_import_loaded_once() {
+        let (compiler, paths) = resolve(&[
+            ("src/main.ecl", "import foo import foo"),
+            ("src/foo.ecl", ""),
+        ]);
+        assert!(!compiler.diagnostics.has_errors());
+        assert_eq!(paths.len(), 2);
+    }
+
+    #[test]
+    fn dependency_graph() {
+        let mut compiler = CompilerCtx::builder()
+            .project_path(PathBuf::new())
+            .resolver(MockResolver::new())
+            .build();
+        compiler.write(&CompilerCtx::entry(), "import foo");
+        compiler.write(&PathBuf::from("src/foo/mod.ecl"), "");
+
+        let collection = resolve_modules(&mut compiler, &CompilerCtx::entry());
+        let dependencies = &collection.dependencies[&CompilerCtx::entry()];
+
+        assert_eq!(dependencies.len(), 1);
+        assert_eq!(dependencies[0].name, "foo");
+        assert_eq!(dependencies[0].path, PathBuf::from("src/foo/mod.ecl"));
+        assert!(collection.dependencies[&dependencies[0].path].is_empty());
+    }
+
+    #[test]
+    fn self_import_cycle() {
+        failed(&[("src/main.ecl", "import main")]);
+    }
+
+    #[test]
+    fn mod_import_cycle() {
+        failed(&[
+            ("src/main.ecl", "import foo"),
+            ("src/foo/mod.ecl", "import mod"),
+        ]);
+    }
+
+    fn module_tree(width: usize, broken: bool) -> Vec<(String, String)> {
+        let imports: Vec<String> = (0..width).map(|i| format!("import m{i}")).collect();
+        let mut files = vec![("src/main.ecl".to_string(), imports.join("\n"))];
+        for i in 0..width {
+            let leaves: Vec<String> = (0..width).map(|j| format!("import l{j}")).collect();
+            files.push((format!("src/m{i}.ecl"), leaves.join("\n")));
+            for j in 0..width {
+                let source = if broken { "func (" } else { "func f() {}" };
+                files.push((format!("src/m{i}/l{j}.ecl"), source.to_string()));
+            }
+        }
+        files
+    }
+
+    fn as_files(files: &[(String, String)]) -> Vec<(&str, &str)> {
+        files
+            .iter()
+            .map(|(p, s)| (p.as_str(), s.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn many_modules() {
+        let files = module_tree(12, false);
+        let (compiler, paths) = resolve(&as_files(&files));
+
+        assert!(!compiler.diagnostics.has_errors());
+        assert_eq!(paths.len(), files.len());
+    }
+
+    #[test]
+    fn deterministic_diagnostics() {
+        let files = module_tree(6, true);
+        let (compiler, _) = resolve(&as_files(&files));
+        let rendered = compiler.diagnostics.render();
+
+        for _ in 0..5 {
+            let (compiler, _) = resolve(&as_files(&files));
+            assert_eq!(compiler.diagnostics.render(), rendered);
+        }
+
+        let first = rendered.find("src/m0/l0.ecl").unwrap();
+        let last = rendered.find("src/m5/l5.ecl").unwrap();
+        assert!(first < last);
+    }
+}
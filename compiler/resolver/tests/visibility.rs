@@ -0,0 +1,112 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use context::{CompilerCtx, files::MockResolver};
+    use resolver::{resolve_modules, resolve_names};
+
+    fn resolve(files: &[(&str, &str)]) -> CompilerCtx {
+        let mut compiler = CompilerCtx::builder()
+            .project_path(PathBuf::new())
+            .resolver(MockResolver::new())
+            .build();
+        for (path, source) in files {
+            compiler.write(&PathBuf::from(path), source);
+        }
+
+        let mut collection = resolve_modules(&mut compiler, &CompilerCtx::entry());
+        resolve_names(&mut compiler, &mut collection);
+        compiler
+    }
+
+    fn visible(files: &[(&str, &str)]) {
+        let compiler = resolve(files);
+        if compiler.diagnostics.has_errors() {
+            compiler.diagnostics.display();
+            panic!("Expected to not fail\nFILES:\n{files:#?}")
+        }
+    }
+
+    fn private(files: &[(&str, &str)], messages: &[&str]) {
+        let compiler = resolve(files);
+        let rendered = compiler.diagnostics.render();
+        for message in messages {
+            assert!(
+                rendered.contains(message),
+                "Expected an error containing {message:?}\nFILES:\n{files:#?}\nGOT:\n{rendered}"
+            );
+        }
+    }
+
+    #[test]
+    fn public_function() {
+        visible(&[
+            ("src/main.ecl", "import foo\nfunc main() { foo::bar() }"),
+            ("src/foo.ecl", "pub func bar() {}"),
+        ]);
+    }
+
+    #[test]
+    fn private_function() {
+        private(
+            &[
+                ("src/main.ecl", "import foo\nfunc main() { foo::bar() }"),
+                ("src/foo.ecl", "func bar() {}"),
+            ],
+            &[
+                "'bar' is private to module 'foo'",
+                "--> \"src/main.ecl\":2:20",
+                "'bar' is defined here without 'pub'\n\t--> \"src/foo.ecl\":1:6",
+            ],
+        );
+    }
+
+    #[test]
+    fn private_use() {
+        private(
+            &[
+                ("src/main.ecl", "import foo\nuse foo::bar"),
+                ("src/foo.ecl", "func bar() {}"),
+            ],
+            &["'bar' is private to module 'foo'"],
+        );
+    }
+
+    #[test]
+    fn private_import() {
+        private(
+            &[
+                ("src/main.ecl", "import foo\nuse foo::baz::qux"),
+                ("src/foo.ecl", "import baz"),
+                ("src/foo/baz.ecl", "pub func qux() {}"),
+            ],
+            &["'baz' is private to module 'foo'"],
+        );
+    }
+
+    #[test]
+    fn reexport() {
+        visible(&[
+            ("src/main.ecl", "import foo\nuse foo::qux"),
+            ("src/foo.ecl", "import baz\npub use baz::qux"),
+            ("src/foo/baz.ecl", "pub func qux() {}"),
+        ]);
+    }
+
+    #[test]
+    fn private_use_is_not_reexported() {
+        private(
+            &[
+                ("src/main.ecl", "import foo\nuse foo::qux"),
+                ("src/foo.ecl", "import baz\nuse baz::qux"),
+                ("src/foo/baz.ecl", "pub func qux() {}"),
+            ],
+            &["'qux' is private to module 'foo'"],
+        );
+    }
+
+    #[test]
+    fn same_module() {
+        visible(&[("src/main.ecl", "func bar() {}\nfunc main() { bar() }")]);
+    }
+}
@@ -1,31 +1,119 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, process::Command};
 
-use common::cmd::execute;
-use context::CompilerCtx;
+use context::{Backend, CompilerCtx, OutputKind};
+use diagnostics::{DiagnosticData, DiagnosticSpan};
 
-pub fn to_binary(compiler: &CompilerCtx, source: String) -> PathBuf {
-    let target = compiler.resolve_path(&PathBuf::from("target"));
-    let build_file_path = target.join("build.ll");
-    let final_path = target.join("build");
+pub fn to_binary(compiler: &mut CompilerCtx, source: String) -> Option<PathBuf> {
+    if compiler.diagnostics.has_errors() {
+        return None;
+    }
+
+    let target = PathBuf::from("target");
+    let build_file_path = match compiler.backend() {
+        Backend::Llvm => target.join("build.ll"),
+        Backend::C => target.join("build.c"),
+    };
 
-    let _ = std::fs::create_dir_all(&target);
+    let name = compiler.config.package.name.clone();
+    let output = compiler.output();
+    if output == OutputKind::Executable && compiler.is_library() {
+        compiler.diagnostics.insert(
+            &CompilerCtx::library_entry(),
+            DiagnosticData::error().title(format!(
+                "Cannot build library '{name}' as an executable, expected {:?}",
+                CompilerCtx::entry()
+            )),
+        );
+        return None;
+    }
 
-    // let _ = std::fs::remove_file(&build_file_path);
-    let _ = std::fs::remove_file(&final_path);
+    let final_path = target.join(output.file_name(&name));
 
-    let _ = std::fs::write(&build_file_path, source);
+    let _ = std::fs::create_dir_all(compiler.resolve_path(&target));
+    let _ = std::fs::remove_file(compiler.resolve_path(&final_path));
+    let _ = std::fs::write(compiler.resolve_path(&build_file_path), source);
 
-    let build_command = format!(
-        "clang -O1 {} -o {}",
-        build_file_path.to_string_lossy(),
-        final_path.to_string_lossy()
-    );
+    let mut command = match compiler.backend() {
+        Backend::Llvm => {
+            let mut command = Command::new("clang");
+            command.arg(format!("--target={}", compiler.target().triple()));
+            command
+        }
+        Backend::C => {
+            let mut command = Command::new(std::env::var("CC").unwrap_or_else(|_| "cc".into()));
+            command.arg("-std=c99");
+            command
+        }
+    };
+    command
+        .current_dir(compiler.resolve_path(&PathBuf::new()))
+        .arg(compiler.opt_level().flag())
+        .arg(&build_file_path);
 
-    let output = execute(build_command);
+    match output {
+        OutputKind::Executable => {
+            command.arg("-o").arg(&final_path);
+            link_arguments(compiler, &mut command);
+            run(compiler, &build_file_path, command)?;
+        }
+        OutputKind::SharedLib => {
+            command.args(["-shared", "-fPIC", "-o"]).arg(&final_path);
+            link_arguments(compiler, &mut command);
+            run(compiler, &build_file_path, command)?;
+        }
+        OutputKind::StaticLib => {
+            let object_path = target.join(format!("{name}.o"));
+            command.args(["-c", "-o"]).arg(&object_path);
+            run(compiler, &build_file_path, command)?;
 
-    if !output.status.success() {
-        panic!("{}", String::from_utf8(output.stderr).unwrap());
+            let mut archive = Command::new("ar");
+            archive
+                .current_dir(compiler.resolve_path(&PathBuf::new()))
+                .arg("rcs")
+                .arg(&final_path)
+                .arg(&object_path);
+            run(compiler, &build_file_path, archive)?;
+        }
     }
 
-    compiler.resolve_path(&final_path)
+    Some(compiler.resolve_path(&final_path))
+}
+
+fn link_arguments(compiler: &CompilerCtx, command: &mut Command) {
+    command.args(compiler.libraries());
+
+    let build = &compiler.config.build;
+    for path in &build.library_paths {
+        command.arg(format!("-L{path}"));
+    }
+    for library in &build.libraries {
+        command.arg(format!("-l{library}"));
+    }
+}
+
+fn run(compiler: &mut CompilerCtx, relative_path: &PathBuf, mut command: Command) -> Option<()> {
+    let program = command.get_program().to_string_lossy().to_string();
+
+    let output = match command.output() {
+        Ok(output) => output,
+        Err(error) => {
+            compiler.diagnostics.insert(
+                relative_path,
+                DiagnosticData::error().title(format!("Failed to run '{program}': {error}")),
+            );
+            return None;
+        }
+    };
+    if output.status.success() {
+        return Some(());
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    compiler.diagnostics.insert(
+        relative_path,
+        DiagnosticData::error()
+            .title(format!("'{program}' failed with {}", output.status))
+            .span(DiagnosticSpan::new(stderr.trim_end())),
+    );
+    None
 }
@@ -0,0 +1,45 @@
+use std::collections::HashSet;
+
+use analyzer::{analyze, analyze_changed};
+use context::CompilerCtx;
+use incremental::{input_hashes, load_hir, store_hir};
+use syntax::{ast, hir};
+
+pub fn analyze_cached(
+    compiler: &mut CompilerCtx,
+    collection: ast::ModuleCollection,
+) -> hir::ModuleCollection {
+    if !compiler.is_incremental() {
+        return analyze(compiler, collection);
+    }
+
+    let inputs = input_hashes(compiler, &collection);
+    let mut cached = Vec::new();
+    for (path, hash) in &inputs {
+        if let Some((module, warnings)) = load_hir(compiler, path, *hash) {
+            for warning in warnings {
+                compiler.diagnostics.insert(&module.path, warning);
+            }
+            cached.push(module);
+        }
+    }
+
+    let unchanged: HashSet<_> = cached.iter().map(|m| m.path.clone()).collect();
+    let mut result = analyze_changed(compiler, collection, &unchanged);
+    if !compiler.diagnostics.has_errors() {
+        for module in &result.modules {
+            let warnings = compiler.diagnostics.warnings(&module.path);
+            store_hir(
+                compiler,
+                &module.path,
+                inputs[&module.path],
+                module,
+                &warnings,
+            );
+        }
+    }
+
+    result.modules.extend(cached);
+    result.modules.sort_by(|a, b| a.path.cmp(&b.path));
+    result
+}
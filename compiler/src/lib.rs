@@ -1,27 +1,114 @@
 use std::path::PathBuf;
 
-use analyzer::analyze;
+use analyzer::{Signatures, Structs, Typeck, recursive_structs, resolved};
 use borrowcheck::borrow_check;
 use build::to_binary;
-pub use context::CompilerCtx;
+use cache::analyze_cached;
+use common::metadata::Metadata;
+use context::files::StdResolver;
+pub use context::{Arch, Backend, CompilerCtx, Emit, OptLevel, OutputKind};
 use lowering::lower_to_mir;
-use resolver::resolve_modules;
+use metadata::{library_metadata, write_metadata};
+use resolver::{resolve_modules, resolve_names};
+use syntax::mir;
 
 mod build;
+mod cache;
+mod metadata;
 
-pub fn compile(compiler: &mut CompilerCtx) -> PathBuf {
-    let entry = CompilerCtx::entry();
+pub fn compile(compiler: &mut CompilerCtx) -> Option<PathBuf> {
+    let (source, metadata) = generate(compiler);
+    let path = to_binary(compiler, source)?;
+    if let Some(metadata) = metadata {
+        write_metadata(compiler, &metadata);
+    }
+    Some(path)
+}
+
+pub fn check(compiler: &mut CompilerCtx) {
+    compiler.load_config();
+
+    let resolved = resolved(compiler);
+    for (relative_path, data) in &resolved.diagnostics {
+        compiler.diagnostics.insert(relative_path, data.clone());
+    }
+
+    let mut structs = Vec::new();
+    for path in &resolved.modules {
+        let table = compiler.query::<Structs>(path.clone());
+        for data in &table.diagnostics {
+            compiler.diagnostics.insert(path, data.clone());
+        }
+        structs.extend(table.structs.iter().cloned());
+    }
+    for (path, data) in recursive_structs(&structs) {
+        compiler.diagnostics.insert(&path, data);
+    }
+
+    for path in resolved
+        .modules
+        .iter()
+        .filter(|path| !StdResolver::contains(path) && resolved.metadata(path).is_none())
+    {
+        let signatures = compiler.query::<Signatures>(path.clone());
+        for signature in signatures.iter() {
+            let typed = compiler.query::<Typeck>((path.clone(), signature.name.clone()));
+            for data in &typed.diagnostics {
+                compiler.diagnostics.insert(path, data.clone());
+            }
+        }
+    }
+}
+
+pub fn generate_ir(compiler: &mut CompilerCtx) -> String {
+    generate(compiler).0
+}
 
-    let collection = resolve_modules(compiler, &entry);
-    let collection = analyze(compiler, collection);
+fn generate(compiler: &mut CompilerCtx) -> (String, Option<Metadata>) {
+    compiler.load_config();
+
+    let entry = compiler.root();
+
+    let mut collection = resolve_modules(compiler, &entry);
+    resolve_names(compiler, &mut collection);
+    compiler.emit(Emit::Ast, || collection.to_string());
+
+    let metadata = compiler
+        .is_library()
+        .then(|| library_metadata(compiler, &collection));
+
+    let collection = analyze_cached(compiler, collection);
     let collection = borrow_check(compiler, collection);
+    compiler.emit(Emit::Hir, || collection.to_string());
 
     let module = lower_to_mir(compiler, collection);
-    println!("{module:#?}");
+    if !compiler.diagnostics.has_errors() {
+        validate_mir(&module);
+    }
+    compiler.emit(Emit::Mir, || module.to_string());
 
-    let source = codegen::generate(compiler, module);
-
-    println!("{source}");
+    let source = match compiler.backend() {
+        Backend::Llvm => {
+            let source = codegen::generate(compiler, module);
+            compiler.emit(Emit::LlvmIr, || source.clone());
+            source
+        }
+        Backend::C => {
+            let source = codegen::c::generate(compiler, module);
+            compiler.emit(Emit::C, || source.clone());
+            source
+        }
+    };
+    (source, metadata)
+}
 
-    to_binary(compiler, source)
+#[cfg(debug_assertions)]
+fn validate_mir(module: &mir::Module) {
+    if let Err(errors) = mir::validate(module) {
+        let errors: Vec<String> = errors.iter().map(|e| e.to_string()).collect();
+        panic!("{}\n\n{module}", errors.join("\n"));
+    }
 }
+
+#[cfg(not(debug_assertions))]
+fn validate_mir(_module: &mir::Module) {}
@@ -0,0 +1,103 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    path::PathBuf,
+};
+
+use common::metadata::{FunctionMetadata, ImportMetadata, Metadata, ModuleMetadata};
+use context::CompilerCtx;
+use diagnostics::{DiagnosticData, DiagnosticResult};
+use syntax::ast;
+
+pub fn library_metadata(
+    compiler: &mut CompilerCtx,
+    collection: &ast::ModuleCollection,
+) -> Metadata {
+    let name = compiler.config.package.name.clone();
+    let mut metadata = Metadata {
+        library: compiler.output().file_name(&name),
+        version: compiler.config.package.version.clone(),
+        name,
+        modules: Vec::new(),
+    };
+
+    let root = match collection.crates.first() {
+        Some(root) => root.root.clone(),
+        None => return metadata,
+    };
+
+    let mut queue = VecDeque::from([root.clone()]);
+    let mut visited = HashSet::from([root]);
+    while let Some(path) = queue.pop_front() {
+        let module = match collection.modules.get(&path) {
+            Some(module) => module,
+            None => continue,
+        };
+
+        let mut imports = Vec::new();
+        for dependency in collection.dependencies.get(&path).into_iter().flatten() {
+            if !dependency.public {
+                continue;
+            }
+            if visited.insert(dependency.path.clone()) {
+                queue.push_back(dependency.path.clone());
+            }
+            imports.push(ImportMetadata {
+                name: dependency.name.clone(),
+                path: dependency.path.display().to_string(),
+            });
+        }
+
+        let mut functions = Vec::new();
+        for node in module.nodes.iter().filter(|node| node.raw.is_public()) {
+            match function(node) {
+                Ok(Some(function)) => functions.push(function),
+                Ok(None) => {}
+                Err(data) => compiler.diagnostics.insert(&path, data),
+            }
+        }
+        metadata.modules.push(ModuleMetadata {
+            path: path.display().to_string(),
+            imports,
+            functions,
+        });
+    }
+
+    metadata
+}
+
+pub fn write_metadata(compiler: &mut CompilerCtx, metadata: &Metadata) {
+    let path = PathBuf::from("target").join(Metadata::file_name(&metadata.name));
+    compiler.write(&path, &metadata.to_toml());
+}
+
+fn function(node: &ast::Node) -> DiagnosticResult<Option<FunctionMetadata>> {
+    match node.raw.item() {
+        ast::RawNode::Function { name, generics, .. } if !generics.is_empty() => {
+            DiagnosticData::error()
+                .title(format!(
+                    "Generic function '{}' cannot be exported from a library",
+                    name.raw
+                ))
+                .position(name.position)
+                .to_err()
+        }
+        ast::RawNode::Struct { name, .. } => DiagnosticData::error()
+            .title(format!(
+                "Struct '{}' cannot be exported from a library",
+                name.raw
+            ))
+            .position(name.position)
+            .to_err(),
+        ast::RawNode::Function {
+            name,
+            parameters,
+            return_type,
+            ..
+        } => Ok(Some(FunctionMetadata {
+            name: name.raw.clone(),
+            parameters: parameters.iter().map(|p| p.raw.to_string()).collect(),
+            return_type: return_type.raw.to_string(),
+        })),
+        _ => Ok(None),
+    }
+}
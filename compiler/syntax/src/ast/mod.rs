@@ -1,24 +1,31 @@
+use serde::{Deserialize, Serialize};
 mod module;
+mod names;
 mod node;
 mod types;
 
 pub use module::*;
+pub use names::*;
 pub use node::*;
 pub use types::*;
 
 use common::position::Span;
+use std::fmt::Display;
+
+use crate::format::join;
 
 pub type Node = Span<RawNode>;
 
 pub type Location = Span<()>;
 pub type Type = Span<types::RawType>;
 pub type Parameter = Span<RawParameter>;
+pub type Generic = Span<RawGeneric>;
 pub type Modifier = Span<RawModifier>;
 pub type Attribute = Span<RawAttribute>;
 
 pub type Identifier = Span<String>;
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct RawParameter {
     pub reference: Option<Span>,
     pub mutable: Option<Span>,
@@ -26,14 +33,36 @@ pub struct RawParameter {
     pub data_type: Type,
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct RawGeneric {
+    pub name: Identifier,
+    pub bounds: Vec<Identifier>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum UsePath {
     Ident(Identifier),
     Extend(Identifier, Box<UsePath>),
     List(Vec<UsePath>),
 }
+impl UsePath {
+    pub fn extract_paths(&self) -> Vec<Vec<Identifier>> {
+        match self {
+            UsePath::Ident(name) => vec![vec![name.clone()]],
+            UsePath::Extend(name, path) => path
+                .extract_paths()
+                .into_iter()
+                .map(|mut path| {
+                    path.insert(0, name.clone());
+                    path
+                })
+                .collect(),
+            UsePath::List(list) => list.iter().flat_map(|p| p.extract_paths()).collect(),
+        }
+    }
+}
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum RawModifier {
     Pub,
     Static,
@@ -41,3 +70,54 @@ pub enum RawModifier {
     Unsafe,
     Extern(Identifier),
 }
+
+impl Display for RawParameter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let reference = if self.reference.is_some() { "&" } else { "" };
+        let mutable = if self.mutable.is_some() { "mut " } else { "" };
+        if self.name.raw == "self" && self.data_type.raw == RawType::SelfType {
+            return write!(f, "{reference}{mutable}self");
+        }
+        write!(
+            f,
+            "{reference}{mutable}{} {}",
+            self.name.raw, self.data_type.raw
+        )
+    }
+}
+
+impl Display for RawGeneric {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.bounds.is_empty() {
+            true => write!(f, "{}", self.name.raw),
+            false => write!(
+                f,
+                "{}: {}",
+                self.name.raw,
+                join(self.bounds.iter().map(|b| &b.raw), " + ")
+            ),
+        }
+    }
+}
+
+impl Display for UsePath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UsePath::Ident(name) => write!(f, "{}", name.raw),
+            UsePath::Extend(name, path) => write!(f, "{}::{path}", name.raw),
+            UsePath::List(list) => write!(f, "{{{}}}", join(list, ", ")),
+        }
+    }
+}
+
+impl Display for RawModifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RawModifier::Pub => write!(f, "pub"),
+            RawModifier::Static => write!(f, "static"),
+            RawModifier::Async => write!(f, "async"),
+            RawModifier::Unsafe => write!(f, "unsafe"),
+            RawModifier::Extern(abi) => write!(f, "extern {:?}", abi.raw),
+        }
+    }
+}
@@ -1,6 +1,9 @@
-use std::{collections::HashMap, path::PathBuf};
+use std::{collections::HashMap, fmt::Display, path::PathBuf};
 
-use super::Node;
+use common::{metadata::Metadata, position::PositionRange};
+
+use super::{Names, Node};
+use crate::format::join;
 
 #[derive(Debug)]
 pub struct Module {
@@ -12,7 +15,46 @@ impl Module {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct Dependency {
+    pub name: String,
+    pub path: PathBuf,
+    pub position: PositionRange,
+    pub public: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct Crate {
+    pub name: String,
+    pub root: PathBuf,
+    pub dependencies: Vec<(String, PathBuf)>,
+    pub metadata: Option<Metadata>,
+}
+
 #[derive(Debug, Default)]
 pub struct ModuleCollection {
+    pub crates: Vec<Crate>,
     pub modules: HashMap<PathBuf, Module>,
+    pub dependencies: HashMap<PathBuf, Vec<Dependency>>,
+    pub hashes: HashMap<PathBuf, u64>,
+    pub names: Names,
+}
+
+impl Display for Module {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", join(self.nodes.iter().map(|n| &n.raw), "\n"))
+    }
+}
+
+impl Display for ModuleCollection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut paths: Vec<&PathBuf> = self.modules.keys().collect();
+        paths.sort();
+
+        let modules = paths
+            .into_iter()
+            .map(|path| format!("// {}\n{}", path.display(), self.modules[path]));
+
+        write!(f, "{}", join(modules, "\n\n"))
+    }
 }
@@ -0,0 +1,64 @@
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+};
+
+use common::position::PositionRange;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DefinitionId(pub usize);
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DefinitionKind {
+    Module(PathBuf),
+    Function,
+    Trait,
+    Struct,
+    Parameter,
+    Variable,
+}
+
+#[derive(Debug, Clone)]
+pub struct Definition {
+    pub name: String,
+    pub kind: DefinitionKind,
+    pub path: PathBuf,
+    pub position: PositionRange,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Symbol {
+    pub id: DefinitionId,
+    pub public: bool,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Names {
+    pub definitions: Vec<Definition>,
+    pub modules: HashMap<PathBuf, HashMap<String, Symbol>>,
+    pub bindings: HashMap<PathBuf, HashMap<PositionRange, DefinitionId>>,
+}
+impl Names {
+    pub fn define(&mut self, definition: Definition) -> DefinitionId {
+        self.definitions.push(definition);
+        DefinitionId(self.definitions.len() - 1)
+    }
+    pub fn definition(&self, id: DefinitionId) -> &Definition {
+        &self.definitions[id.0]
+    }
+    pub fn symbol(&self, module: &Path, name: &str) -> Option<Symbol> {
+        self.modules.get(module)?.get(name).copied()
+    }
+    pub fn lookup(&self, module: &Path, name: &str) -> Option<DefinitionId> {
+        Some(self.symbol(module, name)?.id)
+    }
+    pub fn bind(&mut self, path: &Path, position: PositionRange, id: DefinitionId) {
+        self.bindings
+            .entry(path.to_path_buf())
+            .or_default()
+            .insert(position, id);
+    }
+    pub fn binding(&self, path: &Path, position: &PositionRange) -> Option<DefinitionId> {
+        self.bindings.get(path)?.get(position).copied()
+    }
+}
@@ -1,16 +1,36 @@
-use super::{Attribute, Identifier, Location, Modifier, Node, Parameter, Type, UsePath};
-use crate::operators::{EqualsOperation, Operator};
+use super::{
+    Attribute, Generic, Identifier, Location, Modifier, Node, Parameter, RawModifier, Type, UsePath,
+};
+use crate::{
+    format::{block, join},
+    operators::{EqualsOperation, Operator},
+};
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum RawNode {
     Modifiers(Vec<Modifier>, Box<Node>),
     Function {
         name: Identifier,
+        generics: Vec<Generic>,
         parameters: Vec<Parameter>,
         return_type: Type,
         node: Box<Node>,
     },
+    Trait {
+        name: Identifier,
+        methods: Vec<Node>,
+    },
+    Struct {
+        name: Identifier,
+        fields: Vec<(Identifier, Type)>,
+    },
+    Impl {
+        trait_name: Option<Identifier>,
+        data_type: Type,
+        methods: Vec<Node>,
+    },
     SetPath {
         path: Identifier,
         operation: EqualsOperation,
@@ -33,6 +53,15 @@ pub enum RawNode {
         right: Box<Node>,
         operator: Operator,
     },
+    Construct {
+        name: Identifier,
+        fields: Vec<(Identifier, Node)>,
+    },
+    Closure {
+        moves: Option<Location>,
+        parameters: Vec<(Identifier, Option<Type>)>,
+        body: Box<Node>,
+    },
     While {
         condition: Box<Node>,
         body: Box<Node>,
@@ -48,10 +77,14 @@ pub enum RawNode {
     Import(Identifier),
     Identifier(String),
     Path(Vec<Identifier>),
+    TypeArguments(Box<Node>, Vec<Type>),
     String(String),
     Bool(bool),
     Integer(String),
     Minus(Box<Node>),
+    Deref(Box<Node>),
+    Try(Box<Node>),
+    Cast(Box<Node>, Type),
     Float(String),
     Tuple(Vec<Node>),
     Wrapped(Option<Box<Node>>),
@@ -60,13 +93,24 @@ pub enum RawNode {
     //     name: Identifier,
     //     items: Vec<Identifier>,
     // },
-    // Struct {
-    //     name: Identifier,
-    //     fields: Vec<(Identifier, Type)>,
-    // },
 }
 
-#[derive(Debug, PartialEq)]
+impl RawNode {
+    pub fn item(&self) -> &RawNode {
+        match self {
+            RawNode::Modifiers(_, node) => &node.raw,
+            raw => raw,
+        }
+    }
+    pub fn is_public(&self) -> bool {
+        match self {
+            RawNode::Modifiers(modifiers, _) => modifiers.iter().any(|m| m.raw == RawModifier::Pub),
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum RawAttribute {
     Simple(Identifier),
 }
@@ -76,20 +120,170 @@ impl Display for RawNode {
         use RawNode::*;
 
         let string = match &self {
+            Modifiers(modifiers, node) => {
+                format!(
+                    "{} {}",
+                    join(modifiers.iter().map(|m| &m.raw), " "),
+                    node.raw
+                )
+            }
+            Function {
+                name,
+                generics,
+                parameters,
+                return_type,
+                node,
+            } => format!(
+                "func {}{}({}) {} {}",
+                name.raw,
+                generic_list(generics),
+                join(parameters.iter().map(|p| &p.raw), ", "),
+                return_type.raw,
+                node.raw
+            ),
+            Trait { name, methods } => format!(
+                "trait {} {}",
+                name.raw,
+                block(methods.iter().map(|m| &m.raw))
+            ),
+            Struct { name, fields } => {
+                let fields = fields
+                    .iter()
+                    .map(|(name, data_type)| format!("{} {}", name.raw, data_type.raw));
+                format!("struct {} {}", name.raw, block(fields))
+            }
+            Impl {
+                trait_name,
+                data_type,
+                methods,
+            } => {
+                let trait_name = match trait_name {
+                    Some(name) => format!("{} for ", name.raw),
+                    None => std::string::String::new(),
+                };
+                format!(
+                    "impl {trait_name}{} {}",
+                    data_type.raw,
+                    block(methods.iter().map(|m| &m.raw))
+                )
+            }
+            SetPath {
+                path,
+                operation,
+                value,
+            } => format!("{} {operation} {}", path.raw, value.raw),
+            Declare {
+                mutable,
+                name,
+                data_type,
+                node,
+            } => {
+                let mutable = if mutable.is_some() { "mut " } else { "" };
+                let data_type = match data_type {
+                    Some(t) => format!(": {}", t.raw),
+                    None => std::string::String::new(),
+                };
+                format!("var {mutable}{}{data_type} = {}", name.raw, node.raw)
+            }
+            Conditional {
+                condition,
+                body,
+                conditions,
+                else_condition,
+            } => {
+                let mut string = format!("if {} {}", condition.raw, body.raw);
+                for (condition, body) in conditions {
+                    string.push_str(&format!(" elseif {} {}", condition.raw, body.raw));
+                }
+                if let Some(body) = else_condition {
+                    string.push_str(&format!(" else {}", body.raw));
+                }
+                string
+            }
             Operation {
                 left,
                 right,
                 operator,
-            } => format!("{left} {operator} {right}"),
-            Field(node, field) => format!("{node}.{field}"),
+            } => format!("{} {operator} {}", left.raw, right.raw),
+            Construct { name, fields } => {
+                let fields = fields
+                    .iter()
+                    .map(|(name, value)| format!("{}: {}", name.raw, value.raw));
+                format!("{} {{ {} }}", name.raw, join(fields, ", "))
+            }
+            Closure {
+                moves,
+                parameters,
+                body,
+            } => {
+                let moves = if moves.is_some() { "move " } else { "" };
+                let parameters = parameters.iter().map(|(name, data_type)| match data_type {
+                    Some(t) => format!("{} {}", name.raw, t.raw),
+                    None => name.raw.clone(),
+                });
+                format!("{moves}|{}| {}", join(parameters, ", "), body.raw)
+            }
+            While { condition, body } => format!("while {} {}", condition.raw, body.raw),
+            Attribute(attribute) => format!("#[{}]", attribute.raw),
+            Field(node, field) => format!("{}.{}", node.raw, field.raw),
+            Call(node, arguments) => format!(
+                "{}({})",
+                node.raw,
+                join(arguments.iter().map(|a| &a.raw), ", ")
+            ),
+            Return(value) => keyword("return", value),
+            Break(value) => keyword("break", value),
+            Continue(value) => keyword("continue", value),
+            Loop(body) => format!("loop {}", body.raw),
+            Use(path) => format!("use {path}"),
+            Import(name) => format!("import {}", name.raw),
+            Path(path) => join(path.iter().map(|p| &p.raw), "::"),
+            TypeArguments(node, types) => format!(
+                "{}::<{}>",
+                node.raw,
+                join(types.iter().map(|t| &t.raw), ", ")
+            ),
+            String(s) => format!("{s:?}"),
+            Bool(b) => b.to_string(),
             Integer(s) | Identifier(s) | Float(s) => s.into(),
-            s => format!("{s:?}"),
+            Minus(node) => format!("-{}", node.raw),
+            Deref(node) => format!("*{}", node.raw),
+            Try(node) => format!("{}?", node.raw),
+            Cast(node, data_type) => format!("{} as {}", node.raw, data_type.raw),
+            Tuple(items) => format!("({})", join(items.iter().map(|i| &i.raw), ", ")),
+            Wrapped(node) => match node {
+                Some(node) => format!("({})", node.raw),
+                None => "()".into(),
+            },
+            Block(body) => block(body.iter().map(|n| &n.raw)),
         };
 
         write!(f, "{string}")
     }
 }
 
+pub fn generic_list(generics: &[Generic]) -> std::string::String {
+    match generics.is_empty() {
+        true => std::string::String::new(),
+        false => format!("<{}>", join(generics.iter().map(|g| &g.raw), ", ")),
+    }
+}
+
+fn keyword(keyword: &str, value: &Option<Box<Node>>) -> std::string::String {
+    match value {
+        Some(value) => format!("{keyword} {}", value.raw),
+        None => keyword.into(),
+    }
+}
+
+impl Display for RawAttribute {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RawAttribute::Simple(key) => write!(f, "{}", key.raw),
+        }
+    }
+}
+
 impl Into<Box<Node>> for RawNode {
     fn into(self) -> Box<Node> {
         Box::new(self.into())
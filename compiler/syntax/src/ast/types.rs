@@ -1,8 +1,9 @@
+use serde::{Deserialize, Serialize};
 use std::fmt::{Debug, Display};
 
 use super::{Identifier, Type};
 
-#[derive(Default, PartialEq, Clone)]
+#[derive(Default, PartialEq, Clone, Serialize, Deserialize)]
 pub enum RawType {
     #[default]
     Void,
@@ -26,13 +27,47 @@ pub enum RawType {
     Ref(Option<Identifier>, Box<Type>),
     RefMut(Option<Identifier>, Box<Type>),
 
-    // Box(Box<Type>),
+    Box(Box<Type>),
+    Result(Box<Type>, Box<Type>),
     Tuple(Vec<Type>),
     Array(Box<Type>, Identifier),
     Slice(Box<Type>),
+    Function {
+        parameters: Vec<Type>,
+        return_type: Box<Type>,
+    },
 
     Other(Vec<Identifier>),
 }
+impl RawType {
+    pub fn primitive(name: &str) -> Option<RawType> {
+        use RawType::*;
+
+        Some(match name {
+            "i64" => Int(64),
+            "i32" => Int(32),
+            "i16" => Int(16),
+            "i8" => Int(8),
+
+            "u64" => UInt(64),
+            "u32" => UInt(32),
+            "u16" => UInt(16),
+            "u8" => UInt(8),
+
+            "f32" => Float32,
+            "f64" => Float64,
+
+            "void" => Void,
+            "bool" => Boolean,
+            "never" => Never,
+            "str" => String,
+            "char" => Char,
+            "isize" => ISize,
+            "usize" => USize,
+            _ => return None,
+        })
+    }
+}
 impl Into<Box<Type>> for RawType {
     fn into(self) -> Box<Type> {
         Box::new(self.into())
@@ -69,7 +104,7 @@ impl Display for RawType {
             Other(path) => {
                 return write!(
                     f,
-                    "({})",
+                    "{}",
                     path.iter()
                         .map(|dt| format!("{}", dt.raw))
                         .collect::<Vec<std::string::String>>()
@@ -78,8 +113,24 @@ impl Display for RawType {
             }
             Array(data_type, amount) => return write!(f, "[{}; {}]", data_type.raw, amount.raw),
             Slice(data_type) => return write!(f, "[{}]", data_type.raw),
-            // Box(data_type) => return write!(f, "Box<{}>", data_type.raw),
-            SelfType => "self",
+            Function {
+                parameters,
+                return_type,
+            } => {
+                return write!(
+                    f,
+                    "func({}) {}",
+                    parameters
+                        .iter()
+                        .map(|dt| format!("{}", dt.raw))
+                        .collect::<Vec<std::string::String>>()
+                        .join(", "),
+                    return_type.raw
+                );
+            }
+            Box(data_type) => return write!(f, "Box<{}>", data_type.raw),
+            Result(ok, err) => return write!(f, "result<{}, {}>", ok.raw, err.raw),
+            SelfType => "Self",
             Void => "void",
             Never => "never",
             Boolean => "bool",
@@ -0,0 +1,29 @@
+use std::fmt::Display;
+
+pub const INDENT: &str = "    ";
+
+pub fn indent(body: &str) -> String {
+    body.lines()
+        .map(|line| match line.is_empty() {
+            true => String::new(),
+            false => format!("{INDENT}{line}"),
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+pub fn join<T: Display>(items: impl IntoIterator<Item = T>, separator: &str) -> String {
+    items
+        .into_iter()
+        .map(|item| item.to_string())
+        .collect::<Vec<String>>()
+        .join(separator)
+}
+
+pub fn block<T: Display>(items: impl IntoIterator<Item = T>) -> String {
+    let body = join(items, "\n");
+    match body.is_empty() {
+        true => "{}".into(),
+        false => format!("{{\n{}\n}}", indent(&body)),
+    }
+}
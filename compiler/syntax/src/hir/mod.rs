@@ -1,3 +1,4 @@
+use serde::{Deserialize, Serialize};
 mod module;
 mod node;
 mod types;
@@ -6,9 +7,30 @@ pub use module::*;
 pub use node::*;
 pub use types::*;
 
-#[derive(Debug)]
+use std::fmt::Display;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Parameter {
-    pointer: bool,
-    name: String,
-    data_type: Type,
+    pub mutable: bool,
+    pub name: String,
+    pub data_type: Type,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Capture {
+    pub name: String,
+    pub data_type: Type,
+}
+
+impl Display for Parameter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mutable = if self.mutable { "mut " } else { "" };
+        write!(f, "{mutable}{} {}", self.name, self.data_type)
+    }
+}
+
+impl Display for Capture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.name, self.data_type)
+    }
 }
@@ -1,21 +1,182 @@
+use common::position::PositionRange;
+use serde::{Deserialize, Serialize};
+use std::{fmt::Display, path::PathBuf};
+
 use super::{Node, Parameter, Type};
+use crate::format::{block, join};
 
-#[derive(Debug)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Module {
+    pub path: PathBuf,
     pub imports: Vec<String>,
+    pub structs: Vec<Struct>,
     pub functions: Vec<Function>,
+    pub impls: Vec<Impl>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Struct {
+    pub name: String,
+    pub symbol: String,
+    pub path: PathBuf,
+    pub fields: Vec<(String, Type)>,
+    pub position: PositionRange,
+}
+
+impl Struct {
+    pub fn field(&self, name: &str) -> Option<(usize, &Type)> {
+        self.fields
+            .iter()
+            .enumerate()
+            .find_map(|(index, (field, data_type))| (field == name).then_some((index, data_type)))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Generic {
+    pub name: String,
+    pub bounds: Vec<String>,
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Function {
     pub name: String,
+    pub symbol: String,
+    pub path: PathBuf,
+    pub generics: Vec<Generic>,
     pub parameters: Vec<Parameter>,
     pub return_type: Type,
     pub body: Vec<Node>,
+    pub external: bool,
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signature {
+    pub name: String,
+    pub symbol: String,
+    pub generics: Vec<Generic>,
+    pub parameters: Vec<Type>,
+    pub return_type: Type,
+    pub receiver: bool,
+    pub external: bool,
+}
+
+impl Signature {
+    pub fn is_generic(&self, name: &str) -> bool {
+        self.generics.iter().any(|g| g.name == name)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Impl {
+    pub trait_name: Option<String>,
+    pub self_type: Type,
+    pub methods: Vec<Function>,
+}
 
 #[derive(Debug, Default)]
 pub struct ModuleCollection {
     pub modules: Vec<Module>,
 }
+
+impl Display for Function {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.external {
+            return write!(
+                f,
+                "extern func {}{}({}) {}",
+                self.name,
+                generic_list(&self.generics),
+                join(&self.parameters, ", "),
+                self.return_type
+            );
+        }
+
+        write!(
+            f,
+            "func {}{}({}) {} {}",
+            self.name,
+            generic_list(&self.generics),
+            join(&self.parameters, ", "),
+            self.return_type,
+            block(&self.body)
+        )
+    }
+}
+
+impl Display for Struct {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let fields = self
+            .fields
+            .iter()
+            .map(|(name, data_type)| format!("{name} {data_type}"));
+        write!(f, "struct {} {}", self.name, block(fields))
+    }
+}
+
+impl Display for Signature {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let external = if self.external { "extern " } else { "" };
+        write!(
+            f,
+            "{external}func {}{}({}) {}",
+            self.name,
+            generic_list(&self.generics),
+            join(&self.parameters, ", "),
+            self.return_type
+        )
+    }
+}
+
+impl Display for Generic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.bounds.is_empty() {
+            true => write!(f, "{}", self.name),
+            false => write!(f, "{}: {}", self.name, join(&self.bounds, " + ")),
+        }
+    }
+}
+
+impl Display for Impl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let trait_name = match &self.trait_name {
+            Some(name) => format!("{name} for "),
+            None => String::new(),
+        };
+        write!(
+            f,
+            "impl {trait_name}{} {}",
+            self.self_type,
+            block(&self.methods)
+        )
+    }
+}
+
+fn generic_list(generics: &[Generic]) -> String {
+    match generics.is_empty() {
+        true => String::new(),
+        false => format!("<{}>", join(generics, ", ")),
+    }
+}
+
+impl Display for Module {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let imports = self.imports.iter().map(|i| format!("import {i}"));
+        let structs = self.structs.iter().map(|s| s.to_string());
+        let functions = self.functions.iter().map(|f| f.to_string());
+        let impls = self.impls.iter().map(|i| i.to_string());
+
+        write!(
+            f,
+            "// {}\n{}",
+            self.path.display(),
+            join(imports.chain(structs).chain(functions).chain(impls), "\n")
+        )
+    }
+}
+
+impl Display for ModuleCollection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", join(&self.modules, "\n\n"))
+    }
+}
@@ -1,6 +1,14 @@
-use super::Type;
+use common::position::PositionRange;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
 
-#[derive(Debug)]
+use super::{Capture, Parameter, Type};
+use crate::{
+    format::{block, join},
+    operators::Operator,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Node {
     DeclareVariable {
         name: String,
@@ -8,8 +16,283 @@ pub enum Node {
         data_type: Type,
         value: Box<Node>,
     },
+    Assign {
+        name: String,
+        data_type: Type,
+        value: Box<Node>,
+        position: PositionRange,
+    },
     Block(Vec<Node>),
     Return(Option<Box<Node>>),
+    Call {
+        function: String,
+        symbol: String,
+        generics: Vec<Type>,
+        arguments: Vec<Node>,
+        parameters: Vec<Type>,
+        return_type: Type,
+    },
+    MethodCall {
+        trait_name: Option<String>,
+        self_type: Type,
+        method: String,
+        arguments: Vec<Node>,
+        parameters: Vec<Type>,
+        return_type: Type,
+    },
+    Invoke {
+        callee: Box<Node>,
+        arguments: Vec<Node>,
+        parameters: Vec<Type>,
+        return_type: Type,
+    },
+    Binary {
+        left: Box<Node>,
+        right: Box<Node>,
+        operator: Operator,
+        data_type: Type,
+        position: PositionRange,
+    },
+    Function {
+        name: String,
+        symbol: String,
+        data_type: Type,
+    },
+    Closure {
+        moves: bool,
+        parameters: Vec<Parameter>,
+        return_type: Type,
+        captures: Vec<Capture>,
+        body: Vec<Node>,
+        position: PositionRange,
+    },
+    Boxed {
+        value: Box<Node>,
+        data_type: Type,
+    },
+    Deref(Box<Node>, Type),
+    Construct {
+        fields: Vec<(String, usize, Node)>,
+        data_type: Type,
+    },
+    Field {
+        value: Box<Node>,
+        field: String,
+        index: usize,
+        data_type: Type,
+    },
+    Variant {
+        variant: Variant,
+        value: Box<Node>,
+        data_type: Type,
+    },
+    Try {
+        value: Box<Node>,
+        data_type: Type,
+    },
+    Cast {
+        value: Box<Node>,
+        from: Type,
+        data_type: Type,
+    },
+    Builtin {
+        builtin: Builtin,
+        arguments: Vec<Node>,
+    },
+    AsStr(Box<Node>),
+    Variable {
+        name: String,
+        data_type: Type,
+        position: PositionRange,
+    },
+    Borrow {
+        name: String,
+        mutable: bool,
+        position: PositionRange,
+    },
     Integer(String),
+    Float(String),
     Boolean(bool),
+    String(String),
+}
+
+pub const INTRINSICS: &str = "intrinsics";
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Builtin {
+    StringNew,
+    StringFrom,
+    Push,
+    Concat,
+    Length,
+    Print,
+    PrintLine,
+    ReadLine,
+}
+impl Builtin {
+    pub fn intrinsic(name: &str) -> Option<Builtin> {
+        match name {
+            "print" => Some(Builtin::Print),
+            "println" => Some(Builtin::PrintLine),
+            "read_line" => Some(Builtin::ReadLine),
+            _ => None,
+        }
+    }
+    pub fn return_type(&self) -> Type {
+        match self {
+            Builtin::StringNew | Builtin::StringFrom | Builtin::Concat | Builtin::ReadLine => {
+                Type::String
+            }
+            Builtin::Length => Type::USize,
+            Builtin::Push | Builtin::Print | Builtin::PrintLine => Type::Void,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Variant {
+    Ok,
+    Err,
+}
+impl Variant {
+    pub fn constructor(name: &str) -> Option<Variant> {
+        match name {
+            "Ok" => Some(Variant::Ok),
+            "Err" => Some(Variant::Err),
+            _ => None,
+        }
+    }
+    pub fn payload<'a>(&self, data_type: &'a Type) -> Option<&'a Type> {
+        match (self, data_type) {
+            (Variant::Ok, Type::Result(ok, _)) => Some(ok),
+            (Variant::Err, Type::Result(_, err)) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Node {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Node::*;
+
+        match self {
+            DeclareVariable {
+                name,
+                mutable,
+                data_type,
+                value,
+            } => {
+                let mutable = if *mutable { "mut " } else { "" };
+                write!(f, "var {mutable}{name}: {data_type} = {value}")
+            }
+            Assign { name, value, .. } => write!(f, "{name} = {value}"),
+            Block(body) => write!(f, "{}", block(body)),
+            Return(value) => match value {
+                Some(value) => write!(f, "return {value}"),
+                None => write!(f, "return"),
+            },
+            Call {
+                function,
+                generics,
+                arguments,
+                ..
+            } => match generics.is_empty() {
+                true => write!(f, "{function}({})", join(arguments, ", ")),
+                false => write!(
+                    f,
+                    "{function}::<{}>({})",
+                    join(generics, ", "),
+                    join(arguments, ", ")
+                ),
+            },
+            MethodCall {
+                trait_name,
+                self_type,
+                method,
+                arguments,
+                ..
+            } => match trait_name {
+                Some(trait_name) => write!(
+                    f,
+                    "<{self_type} as {trait_name}>::{method}({})",
+                    join(arguments, ", ")
+                ),
+                None => write!(f, "{self_type}::{method}({})", join(arguments, ", ")),
+            },
+            Invoke {
+                callee, arguments, ..
+            } => write!(f, "({callee})({})", join(arguments, ", ")),
+            Binary {
+                left,
+                right,
+                operator,
+                ..
+            } => write!(f, "({left} {operator} {right})"),
+            Function { name, .. } => write!(f, "{name}"),
+            Closure {
+                moves,
+                parameters,
+                return_type,
+                captures,
+                body,
+                ..
+            } => {
+                let moves = if *moves { "move " } else { "" };
+                write!(f, "{moves}|{}| {return_type} ", join(parameters, ", "))?;
+                if !captures.is_empty() {
+                    write!(f, "[{}] ", join(captures, ", "))?;
+                }
+                write!(f, "{}", block(body))
+            }
+            Boxed { value, .. } => write!(f, "Box::new({value})"),
+            Deref(value, _) => write!(f, "*{value}"),
+            Construct { fields, data_type } => {
+                let fields = fields
+                    .iter()
+                    .map(|(name, _, value)| format!("{name}: {value}"));
+                write!(f, "{data_type} {{ {} }}", join(fields, ", "))
+            }
+            Field { value, field, .. } => write!(f, "{value}.{field}"),
+            Variant { variant, value, .. } => write!(f, "{variant}({value})"),
+            Try { value, .. } => write!(f, "{value}?"),
+            Cast {
+                value, data_type, ..
+            } => write!(f, "({value} as {data_type})"),
+            Builtin { builtin, arguments } => write!(f, "{builtin}({})", join(arguments, ", ")),
+            AsStr(value) => write!(f, "{value} as str"),
+            Variable { name, .. } => write!(f, "{name}"),
+            Borrow { name, mutable, .. } => match mutable {
+                true => write!(f, "&mut {name}"),
+                false => write!(f, "&{name}"),
+            },
+            Integer(value) | Float(value) => write!(f, "{value}"),
+            Boolean(value) => write!(f, "{value}"),
+            String(value) => write!(f, "{value:?}"),
+        }
+    }
+}
+
+impl Display for Builtin {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Builtin::StringNew => "String::new",
+            Builtin::StringFrom => "String::from",
+            Builtin::Push => "String::push",
+            Builtin::Concat => "str::concat",
+            Builtin::Length => "str::len",
+            Builtin::Print => "intrinsics::print",
+            Builtin::PrintLine => "intrinsics::println",
+            Builtin::ReadLine => "intrinsics::read_line",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl Display for Variant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Variant::Ok => write!(f, "Ok"),
+            Variant::Err => write!(f, "Err"),
+        }
+    }
 }
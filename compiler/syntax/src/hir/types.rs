@@ -1,6 +1,110 @@
-#[derive(Debug)]
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fmt::Display};
+
+use crate::format::join;
+
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub enum Type {
     Void,
     Int(u8),
+    UInt(u8),
+    Float(u8),
+    USize,
+    ISize,
     Boolean,
+    Str,
+    String,
+    Parameter(String),
+    Ref(Box<Type>),
+    RefMut(Box<Type>),
+    Box(Box<Type>),
+    Result(Box<Type>, Box<Type>),
+    Struct(String),
+    Function {
+        parameters: Vec<Type>,
+        return_type: Box<Type>,
+    },
+}
+impl Type {
+    pub fn is_integer(&self) -> bool {
+        matches!(
+            self,
+            Type::Int(_) | Type::UInt(_) | Type::USize | Type::ISize
+        )
+    }
+    pub fn is_float(&self) -> bool {
+        matches!(self, Type::Float(_))
+    }
+    pub fn is_numeric(&self) -> bool {
+        self.is_integer() || self.is_float()
+    }
+    pub fn is_signed(&self) -> bool {
+        matches!(self, Type::Int(_) | Type::ISize)
+    }
+    pub fn is_reference(&self) -> bool {
+        matches!(self, Type::Ref(_) | Type::RefMut(_))
+    }
+    pub fn is_owned(&self) -> bool {
+        match self {
+            Type::Box(_) | Type::String => true,
+            Type::Result(ok, err) => ok.is_owned() || err.is_owned(),
+            _ => false,
+        }
+    }
+    pub fn dereference(&self) -> &Type {
+        match self {
+            Type::Ref(data_type) | Type::RefMut(data_type) => data_type,
+            data_type => data_type,
+        }
+    }
+    pub fn substitute(&self, types: &HashMap<String, Type>) -> Type {
+        match self {
+            Type::Parameter(name) => types.get(name).cloned().unwrap_or_else(|| self.clone()),
+            Type::Ref(data_type) => Type::Ref(Box::new(data_type.substitute(types))),
+            Type::RefMut(data_type) => Type::RefMut(Box::new(data_type.substitute(types))),
+            Type::Box(data_type) => Type::Box(Box::new(data_type.substitute(types))),
+            Type::Result(ok, err) => Type::Result(
+                Box::new(ok.substitute(types)),
+                Box::new(err.substitute(types)),
+            ),
+            Type::Function {
+                parameters,
+                return_type,
+            } => Type::Function {
+                parameters: parameters.iter().map(|p| p.substitute(types)).collect(),
+                return_type: Box::new(return_type.substitute(types)),
+            },
+            data_type => data_type.clone(),
+        }
+    }
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Type::Void => write!(f, "void"),
+            Type::Int(bits) => write!(f, "i{bits}"),
+            Type::UInt(bits) => write!(f, "u{bits}"),
+            Type::Float(bits) => write!(f, "f{bits}"),
+            Type::USize => write!(f, "usize"),
+            Type::ISize => write!(f, "isize"),
+            Type::Boolean => write!(f, "bool"),
+            Type::Str => write!(f, "str"),
+            Type::String => write!(f, "String"),
+            Type::Parameter(name) => write!(f, "{name}"),
+            Type::Ref(data_type) => write!(f, "&{data_type}"),
+            Type::RefMut(data_type) => write!(f, "&mut {data_type}"),
+            Type::Box(data_type) => write!(f, "Box<{data_type}>"),
+            Type::Result(ok, err) => write!(f, "result<{ok}, {err}>"),
+            Type::Struct(symbol) => write!(f, "{}", source_name(symbol)),
+            Type::Function {
+                parameters,
+                return_type,
+            } => write!(f, "func({}) {return_type}", join(parameters, ", ")),
+        }
+    }
+}
+
+pub fn source_name(symbol: &str) -> &str {
+    symbol.rsplit('$').next().unwrap_or(symbol)
 }
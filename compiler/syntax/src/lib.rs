@@ -2,4 +2,5 @@ pub mod ast;
 pub mod hir;
 pub mod mir;
 
+pub mod format;
 pub mod operators;
@@ -1,6 +1,189 @@
+use std::fmt::Display;
+
 use super::Type;
+use crate::{
+    format::join,
+    operators::{ArithmeticOperator, Operator},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CastKind {
+    Truncate,
+    SignExtend,
+    ZeroExtend,
+    FloatTruncate,
+    FloatExtend,
+    FloatToSigned,
+    FloatToUnsigned,
+    SignedToFloat,
+    UnsignedToFloat,
+    Bitcast,
+}
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub enum Expression {
     Integer(Type, String),
+    Float(Type, String),
+    Boolean(bool),
+    Null,
+    Local(Type, String),
+    Reference(String),
+    Call {
+        function: String,
+        arguments: Vec<Expression>,
+        data_type: Type,
+    },
+    Invoke {
+        callee: Box<Expression>,
+        arguments: Vec<Expression>,
+        data_type: Type,
+    },
+    Function {
+        function: String,
+        environment: Option<Box<Expression>>,
+    },
+    FieldPointer {
+        structure: String,
+        base: Box<Expression>,
+        index: usize,
+    },
+    Load(Type, Box<Expression>),
+    Str {
+        constant: String,
+        length: Box<Expression>,
+    },
+    Slice {
+        data: Box<Expression>,
+        length: Box<Expression>,
+    },
+    Extract {
+        value: Box<Expression>,
+        index: usize,
+        data_type: Type,
+    },
+    Offset {
+        base: Box<Expression>,
+        offset: Box<Expression>,
+    },
+    Binary {
+        operator: Operator,
+        left: Box<Expression>,
+        right: Box<Expression>,
+        data_type: Type,
+    },
+    Overflow {
+        operator: ArithmeticOperator,
+        left: Box<Expression>,
+        right: Box<Expression>,
+    },
+    Cast {
+        kind: CastKind,
+        value: Box<Expression>,
+        data_type: Type,
+    },
+}
+
+impl Expression {
+    pub fn data_type(&self) -> Type {
+        match self {
+            Expression::Integer(data_type, _) | Expression::Float(data_type, _) => {
+                data_type.clone()
+            }
+            Expression::Boolean(_) => Type::Boolean,
+            Expression::Null => Type::Pointer,
+            Expression::Local(data_type, _) => data_type.clone(),
+            Expression::Reference(_) => Type::Pointer,
+            Expression::Call { data_type, .. } => data_type.clone(),
+            Expression::Invoke { data_type, .. } => data_type.clone(),
+            Expression::Function { .. } => Type::Function,
+            Expression::FieldPointer { .. } => Type::Pointer,
+            Expression::Load(data_type, _) => data_type.clone(),
+            Expression::Str { length, .. } | Expression::Slice { length, .. } => {
+                match length.data_type() {
+                    Type::UInt(bits) => Type::Slice(bits),
+                    data_type => panic!("Expected an integer length, got {data_type}"),
+                }
+            }
+            Expression::Extract { data_type, .. } => data_type.clone(),
+            Expression::Offset { .. } => Type::Pointer,
+            Expression::Binary { data_type, .. } => data_type.clone(),
+            Expression::Overflow { .. } => Type::Boolean,
+            Expression::Cast { data_type, .. } => data_type.clone(),
+        }
+    }
+}
+
+impl Display for Expression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Expression::Integer(data_type, value) | Expression::Float(data_type, value) => {
+                write!(f, "{data_type} {value}")
+            }
+            Expression::Boolean(value) => write!(f, "{} {value}", Type::Boolean),
+            Expression::Null => write!(f, "{} null", Type::Pointer),
+            Expression::Local(data_type, name) => write!(f, "{data_type} {name}"),
+            Expression::Reference(name) => write!(f, "{} &{name}", Type::Pointer),
+            Expression::Call {
+                function,
+                arguments,
+                data_type,
+            } => write!(f, "call {data_type} {function}({})", join(arguments, ", ")),
+            Expression::Invoke {
+                callee,
+                arguments,
+                data_type,
+            } => write!(f, "invoke {data_type} {callee}({})", join(arguments, ", ")),
+            Expression::Function {
+                function,
+                environment,
+            } => match environment {
+                Some(environment) => write!(f, "func {function} with {environment}"),
+                None => write!(f, "func {function}"),
+            },
+            Expression::FieldPointer {
+                structure,
+                base,
+                index,
+            } => write!(f, "field {structure}.{index} {base}"),
+            Expression::Load(data_type, pointer) => write!(f, "load {data_type} {pointer}"),
+            Expression::Str { constant, length } => write!(f, "str {constant} {length}"),
+            Expression::Slice { data, length } => write!(f, "slice {data}, {length}"),
+            Expression::Extract { value, index, .. } => write!(f, "extract {index} {value}"),
+            Expression::Offset { base, offset } => write!(f, "offset {base}, {offset}"),
+            Expression::Binary {
+                operator,
+                left,
+                right,
+                ..
+            } => write!(f, "({left} {operator} {right})"),
+            Expression::Overflow {
+                operator,
+                left,
+                right,
+            } => write!(f, "overflow ({left} {operator} {right})"),
+            Expression::Cast {
+                kind,
+                value,
+                data_type,
+            } => write!(f, "{kind} {value} to {data_type}"),
+        }
+    }
+}
+
+impl Display for CastKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self {
+            CastKind::Truncate => "trunc",
+            CastKind::SignExtend => "sext",
+            CastKind::ZeroExtend => "zext",
+            CastKind::FloatTruncate => "fptrunc",
+            CastKind::FloatExtend => "fpext",
+            CastKind::FloatToSigned => "fptosi",
+            CastKind::FloatToUnsigned => "fptoui",
+            CastKind::SignedToFloat => "sitofp",
+            CastKind::UnsignedToFloat => "uitofp",
+            CastKind::Bitcast => "bitcast",
+        };
+        write!(f, "{kind}")
+    }
 }
@@ -2,11 +2,26 @@ mod expression;
 mod module;
 mod node;
 mod types;
+mod validate;
 
 pub use expression::*;
 pub use module::*;
 pub use node::*;
 pub use types::*;
+pub use validate::*;
+
+use std::fmt::Display;
+
+use crate::format::{indent, join};
+
+pub const MALLOC: &str = "malloc";
+pub const FREE: &str = "free";
+pub const REALLOC: &str = "realloc";
+pub const MEMCPY: &str = "memcpy";
+pub const WRITE: &str = "write";
+pub const READ: &str = "read";
+pub const EXIT: &str = "exit";
+pub const RUNTIME: [&str; 7] = [MALLOC, FREE, REALLOC, MEMCPY, WRITE, READ, EXIT];
 
 #[derive(Debug)]
 pub struct Parameter {
@@ -17,8 +32,103 @@ pub struct Parameter {
 
 #[derive(Debug)]
 pub struct Function {
+    pub internal: bool,
+    pub name: String,
+    pub parameters: Vec<Parameter>,
+    pub return_type: Type,
+    pub blocks: Vec<Block>,
+}
+
+#[derive(Debug)]
+pub struct Struct {
+    pub name: String,
+    pub fields: Vec<Type>,
+}
+
+#[derive(Debug)]
+pub struct Constant {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug)]
+pub struct Declaration {
     pub name: String,
     pub parameters: Vec<Parameter>,
     pub return_type: Type,
+}
+
+#[derive(Debug)]
+pub struct Block {
+    pub label: String,
     pub body: Vec<Node>,
 }
+impl Block {
+    pub fn new(label: impl ToString) -> Self {
+        Self {
+            label: label.to_string(),
+            body: Vec::new(),
+        }
+    }
+    pub fn terminator(&self) -> Option<&Node> {
+        self.body.last().filter(|node| node.is_terminator())
+    }
+}
+
+impl Display for Parameter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pointer = if self.pointer { "&" } else { "" };
+        write!(f, "{pointer}{}: {}", self.name, self.data_type)
+    }
+}
+
+impl Display for Function {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.internal {
+            write!(f, "internal ")?;
+        }
+        write!(
+            f,
+            "func {}({}) {} {{\n{}\n}}",
+            self.name,
+            join(&self.parameters, ", "),
+            self.return_type,
+            join(&self.blocks, "\n")
+        )
+    }
+}
+
+impl Display for Struct {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "type %{} = {{ {} }}",
+            self.name,
+            join(&self.fields, ", ")
+        )
+    }
+}
+
+impl Display for Constant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "const {} = {:?}", self.name, self.value)
+    }
+}
+
+impl Display for Declaration {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "declare func {}({}) {}",
+            self.name,
+            join(&self.parameters, ", "),
+            self.return_type
+        )
+    }
+}
+
+impl Display for Block {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:\n{}", self.label, indent(&join(&self.body, "\n")))
+    }
+}
@@ -1,6 +1,32 @@
-use super::Function;
+use std::fmt::Display;
+
+use super::{Constant, Declaration, Function, Struct};
+use crate::format::join;
 
 #[derive(Debug)]
 pub struct Module {
+    pub structs: Vec<Struct>,
+    pub constants: Vec<Constant>,
+    pub declarations: Vec<Declaration>,
     pub functions: Vec<Function>,
 }
+
+impl Display for Module {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let structs = self.structs.iter().map(|s| s.to_string());
+        let constants = self.constants.iter().map(|c| c.to_string());
+        let declarations = self.declarations.iter().map(|d| d.to_string());
+        let functions = self.functions.iter().map(|f| f.to_string());
+        write!(
+            f,
+            "{}",
+            join(
+                structs
+                    .chain(constants)
+                    .chain(declarations)
+                    .chain(functions),
+                "\n\n"
+            )
+        )
+    }
+}
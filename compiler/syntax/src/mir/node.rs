@@ -1,4 +1,6 @@
-use super::{Expression, Parameter, Type};
+use std::fmt::Display;
+
+use super::{Expression, Type};
 
 #[derive(Debug)]
 pub enum Node {
@@ -7,7 +9,63 @@ pub enum Node {
         data_type: Type,
         value: Expression,
     },
+    Store {
+        pointer: Expression,
+        data_type: Type,
+        value: Expression,
+    },
     Goto(String),
-    Allocate(Type),
+    Branch {
+        condition: Expression,
+        then: String,
+        otherwise: String,
+    },
+    Allocate {
+        name: String,
+        data_type: Type,
+    },
     Return(Option<Expression>),
+    Unreachable,
+    Expression(Expression),
+}
+
+impl Node {
+    pub fn is_terminator(&self) -> bool {
+        matches!(
+            self,
+            Node::Goto(_) | Node::Branch { .. } | Node::Return(_) | Node::Unreachable
+        )
+    }
+}
+
+impl Display for Node {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        use Node::*;
+
+        match self {
+            Set {
+                name,
+                data_type,
+                value,
+            } => write!(f, "set {name}: {data_type} = {value}"),
+            Store {
+                pointer,
+                data_type,
+                value,
+            } => write!(f, "store {data_type} {value} -> {pointer}"),
+            Goto(label) => write!(f, "goto {label}"),
+            Branch {
+                condition,
+                then,
+                otherwise,
+            } => write!(f, "branch {condition}, {then}, {otherwise}"),
+            Allocate { name, data_type } => write!(f, "alloc {name}: {data_type}"),
+            Return(value) => match value {
+                Some(value) => write!(f, "return {value}"),
+                None => write!(f, "return"),
+            },
+            Unreachable => write!(f, "unreachable"),
+            Expression(value) => write!(f, "{value}"),
+        }
+    }
 }
@@ -1,7 +1,32 @@
-#[derive(Debug)]
+use std::fmt::Display;
+
+#[derive(Debug, PartialEq, Clone)]
 pub enum Type {
     Void,
     Bytes(usize),
     Boolean,
     Int(u8),
+    UInt(u8),
+    Float(u8),
+    Pointer,
+    Function,
+    Struct(String),
+    Slice(u8),
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Type::Void => write!(f, "void"),
+            Type::Bytes(bytes) => write!(f, "[{bytes} x u8]"),
+            Type::Boolean => write!(f, "bool"),
+            Type::Int(bits) => write!(f, "i{bits}"),
+            Type::UInt(bits) => write!(f, "u{bits}"),
+            Type::Float(bits) => write!(f, "f{bits}"),
+            Type::Pointer => write!(f, "ptr"),
+            Type::Function => write!(f, "func"),
+            Type::Struct(name) => write!(f, "%{name}"),
+            Type::Slice(bits) => write!(f, "{{ ptr, u{bits} }}"),
+        }
+    }
 }
@@ -0,0 +1,451 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
+
+use super::{Block, CastKind, Expression, Function, Module, Node, Struct, Type};
+use crate::operators::ArithmeticOperator;
+
+#[derive(Debug, PartialEq)]
+pub struct ValidationError {
+    pub function: String,
+    pub message: String,
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "internal compiler error: invalid MIR in function '{}': {}",
+            self.function, self.message
+        )
+    }
+}
+
+pub fn validate(module: &Module) -> Result<(), Vec<ValidationError>> {
+    let mut errors = Vec::new();
+
+    for function in &module.functions {
+        let mut validator = Validator {
+            function,
+            structs: &module.structs,
+            errors: &mut errors,
+        };
+        validator.validate();
+    }
+
+    match errors.is_empty() {
+        true => Ok(()),
+        false => Err(errors),
+    }
+}
+
+struct Validator<'a> {
+    function: &'a Function,
+    structs: &'a [Struct],
+    errors: &'a mut Vec<ValidationError>,
+}
+
+impl Validator<'_> {
+    fn error(&mut self, message: impl ToString) {
+        self.errors.push(ValidationError {
+            function: self.function.name.clone(),
+            message: message.to_string(),
+        });
+    }
+    fn validate(&mut self) {
+        if self.function.blocks.is_empty() {
+            self.error("function has no blocks");
+            return;
+        }
+
+        let mut labels: HashSet<&String> = HashSet::new();
+        for block in &self.function.blocks {
+            if !labels.insert(&block.label) {
+                self.error(format!("duplicate block label '{}'", block.label));
+            }
+        }
+
+        let mut locals: HashMap<&String, &Type> = HashMap::new();
+        for block in &self.function.blocks {
+            for node in &block.body {
+                if let Node::Allocate { name, data_type } = node
+                    && locals.insert(name, data_type).is_some()
+                {
+                    self.error(format!("local '{name}' is allocated more than once"));
+                }
+            }
+        }
+        for parameter in &self.function.parameters {
+            locals
+                .entry(&parameter.name)
+                .or_insert(&parameter.data_type);
+        }
+
+        for block in &self.function.blocks {
+            self.terminator(block, &labels);
+            self.types(block, &locals);
+        }
+
+        self.allocations();
+    }
+    fn terminator(&mut self, block: &Block, labels: &HashSet<&String>) {
+        let label = &block.label;
+        let last = block.body.len().saturating_sub(1);
+
+        for (index, node) in block.body.iter().enumerate() {
+            if node.is_terminator() && index != last {
+                self.error(format!(
+                    "block '{label}' has a terminator before its end: {node}"
+                ));
+            }
+        }
+
+        match block.terminator() {
+            Some(terminator) => {
+                for target in successors(terminator) {
+                    if !labels.contains(target) {
+                        self.error(format!("block '{label}' jumps to unknown block '{target}'"));
+                    }
+                }
+            }
+            None => self.error(format!("block '{label}' has no terminator")),
+        }
+    }
+    fn types(&mut self, block: &Block, locals: &HashMap<&String, &Type>) {
+        let return_type = &self.function.return_type;
+
+        for node in &block.body {
+            match node {
+                Node::Set { value, .. }
+                | Node::Return(Some(value))
+                | Node::Expression(value)
+                | Node::Branch {
+                    condition: value, ..
+                } => self.expression(value, locals),
+                Node::Store { pointer, value, .. } => {
+                    self.expression(pointer, locals);
+                    self.expression(value, locals);
+                }
+                _ => {}
+            }
+
+            match node {
+                Node::Set {
+                    name,
+                    data_type,
+                    value,
+                } => {
+                    if let Some(allocated) = locals.get(name)
+                        && *allocated != data_type
+                    {
+                        self.error(format!(
+                            "set of '{name}' with type {data_type}, but it was allocated as {allocated}"
+                        ));
+                    }
+                    let found = value.data_type();
+                    if &found != data_type {
+                        self.error(format!(
+                            "set of '{name}' with type {data_type}, but the value has type {found}"
+                        ));
+                    }
+                }
+                Node::Store {
+                    pointer,
+                    data_type,
+                    value,
+                } => {
+                    self.pointer(pointer);
+                    let found = value.data_type();
+                    if &found != data_type {
+                        self.error(format!(
+                            "store of type {data_type}, but the value has type {found}"
+                        ));
+                    }
+                }
+                Node::Return(Some(value)) => {
+                    let found = value.data_type();
+                    if &found != return_type {
+                        self.error(format!(
+                            "return of type {found} in function returning {return_type}"
+                        ));
+                    }
+                }
+                Node::Branch { condition, .. } => {
+                    let found = condition.data_type();
+                    if found != Type::Boolean {
+                        self.error(format!("branch on a condition with type {found}"));
+                    }
+                }
+                Node::Return(None) if return_type != &Type::Void => {
+                    self.error(format!(
+                        "return without a value in function returning {return_type}"
+                    ));
+                }
+                _ => {}
+            }
+        }
+    }
+    fn expression(&mut self, expression: &Expression, locals: &HashMap<&String, &Type>) {
+        match expression {
+            Expression::Local(data_type, name) => match locals.get(name) {
+                Some(local) if *local != data_type => self.error(format!(
+                    "read of '{name}' with type {data_type}, but it was allocated as {local}"
+                )),
+                Some(_) => {}
+                None => self.error(format!("read of unknown local '{name}'")),
+            },
+            Expression::Reference(name) if !locals.contains_key(name) => {
+                self.error(format!("reference to unknown local '{name}'"))
+            }
+            Expression::Call { arguments, .. } => {
+                for argument in arguments {
+                    self.expression(argument, locals);
+                }
+            }
+            Expression::Invoke {
+                callee, arguments, ..
+            } => {
+                self.expression(callee, locals);
+                let found = callee.data_type();
+                if found != Type::Function {
+                    self.error(format!("invoke of a value with type {found}"));
+                }
+                for argument in arguments {
+                    self.expression(argument, locals);
+                }
+            }
+            Expression::Function {
+                environment: Some(environment),
+                ..
+            } => {
+                self.expression(environment, locals);
+                self.pointer(environment);
+            }
+            Expression::FieldPointer { base: pointer, .. } | Expression::Load(_, pointer) => {
+                self.expression(pointer, locals);
+                self.pointer(pointer);
+            }
+            Expression::Str { length, .. } => self.length(length),
+            Expression::Slice { data, length } => {
+                self.expression(data, locals);
+                self.pointer(data);
+                self.expression(length, locals);
+                self.length(length);
+            }
+            Expression::Extract {
+                value,
+                index,
+                data_type,
+            } => {
+                self.expression(value, locals);
+                let found = value.data_type();
+                let field = match &found {
+                    Type::Slice(bits) => [Type::Pointer, Type::UInt(*bits)].get(*index).cloned(),
+                    Type::Function => [Type::Pointer, Type::Pointer].get(*index).cloned(),
+                    Type::Struct(name) => self
+                        .structs
+                        .iter()
+                        .find(|s| &s.name == name)
+                        .and_then(|s| s.fields.get(*index).cloned()),
+                    _ => None,
+                };
+                match field {
+                    Some(field) if &field != data_type => self.error(format!(
+                        "extract of field {index} with type {data_type}, but it has type {field}"
+                    )),
+                    Some(_) => {}
+                    None => self.error(format!(
+                        "extract of field {index} from a value with type {found}"
+                    )),
+                }
+            }
+            Expression::Offset { base, offset } => {
+                self.expression(base, locals);
+                self.pointer(base);
+                self.expression(offset, locals);
+                self.length(offset);
+            }
+            Expression::Binary { left, right, .. } => {
+                self.expression(left, locals);
+                self.expression(right, locals);
+                let (left, right) = (left.data_type(), right.data_type());
+                if left != right {
+                    self.error(format!(
+                        "binary operation on mismatched types {left} and {right}"
+                    ));
+                }
+            }
+            Expression::Overflow {
+                operator,
+                left,
+                right,
+            } => {
+                self.expression(left, locals);
+                self.expression(right, locals);
+                let (left, right) = (left.data_type(), right.data_type());
+                if left != right {
+                    self.error(format!(
+                        "overflow check on mismatched types {left} and {right}"
+                    ));
+                }
+                if !matches!(left, Type::Int(_) | Type::UInt(_)) {
+                    self.error(format!("overflow check on a value with type {left}"));
+                }
+                if !matches!(
+                    operator,
+                    ArithmeticOperator::Plus
+                        | ArithmeticOperator::Subtract
+                        | ArithmeticOperator::Multiply
+                ) {
+                    self.error(format!("overflow check on operator '{operator}'"));
+                }
+            }
+            Expression::Cast {
+                kind,
+                value,
+                data_type,
+            } => {
+                self.expression(value, locals);
+                let found = value.data_type();
+                if !is_valid_cast(*kind, &found, data_type) {
+                    self.error(format!("invalid {kind} cast from {found} to {data_type}"));
+                }
+            }
+            Expression::Function {
+                environment: None, ..
+            } => {}
+            Expression::Integer(..)
+            | Expression::Float(..)
+            | Expression::Boolean(_)
+            | Expression::Null
+            | Expression::Reference(_) => {}
+        }
+    }
+    fn pointer(&mut self, expression: &Expression) {
+        let found = expression.data_type();
+        if found != Type::Pointer {
+            self.error(format!("expected a pointer, got {found}: {expression}"));
+        }
+    }
+    fn length(&mut self, expression: &Expression) {
+        let found = expression.data_type();
+        if !matches!(found, Type::UInt(_)) {
+            self.error(format!(
+                "expected an integer length, got {found}: {expression}"
+            ));
+        }
+    }
+    fn allocations(&mut self) {
+        let blocks = &self.function.blocks;
+        let indices: HashMap<&String, usize> = blocks
+            .iter()
+            .enumerate()
+            .map(|(index, block)| (&block.label, index))
+            .collect();
+
+        let allocated_after = |block: &Block, mut allocated: HashSet<String>| {
+            for node in &block.body {
+                if let Node::Allocate { name, .. } = node {
+                    allocated.insert(name.clone());
+                }
+            }
+            allocated
+        };
+
+        let mut entries: Vec<Option<HashSet<String>>> = vec![None; blocks.len()];
+        entries[0] = Some(
+            self.function
+                .parameters
+                .iter()
+                .map(|parameter| parameter.name.clone())
+                .collect(),
+        );
+
+        let mut queue = vec![0];
+        while let Some(index) = queue.pop() {
+            let block = &blocks[index];
+            let allocated = allocated_after(block, entries[index].clone().unwrap_or_default());
+
+            let Some(terminator) = block.terminator() else {
+                continue;
+            };
+            for target in successors(terminator) {
+                let Some(&successor) = indices.get(target) else {
+                    continue;
+                };
+
+                let merged = match &entries[successor] {
+                    Some(existing) => existing.intersection(&allocated).cloned().collect(),
+                    None => allocated.clone(),
+                };
+                if entries[successor].as_ref() != Some(&merged) {
+                    entries[successor] = Some(merged);
+                    queue.push(successor);
+                }
+            }
+        }
+
+        for (block, entry) in blocks.iter().zip(entries) {
+            let Some(mut allocated) = entry else {
+                continue;
+            };
+
+            for node in &block.body {
+                match node {
+                    Node::Allocate { name, .. } => {
+                        allocated.insert(name.clone());
+                    }
+                    Node::Set { name, .. } if !allocated.contains(name) => {
+                        self.error(format!(
+                            "local '{name}' is used in block '{}' before it is allocated",
+                            block.label
+                        ));
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+fn is_valid_cast(kind: CastKind, from: &Type, to: &Type) -> bool {
+    let integer = |data_type: &Type| match data_type {
+        Type::Boolean => Some(1),
+        Type::Int(bits) | Type::UInt(bits) => Some(*bits),
+        _ => None,
+    };
+    let float = |data_type: &Type| match data_type {
+        Type::Float(bits) => Some(*bits),
+        _ => None,
+    };
+
+    match kind {
+        CastKind::Truncate => matches!((integer(from), integer(to)), (Some(a), Some(b)) if a > b),
+        CastKind::SignExtend | CastKind::ZeroExtend => {
+            matches!((integer(from), integer(to)), (Some(a), Some(b)) if a < b)
+        }
+        CastKind::FloatTruncate => matches!((float(from), float(to)), (Some(a), Some(b)) if a > b),
+        CastKind::FloatExtend => matches!((float(from), float(to)), (Some(a), Some(b)) if a < b),
+        CastKind::FloatToSigned | CastKind::FloatToUnsigned => {
+            float(from).is_some() && integer(to).is_some()
+        }
+        CastKind::SignedToFloat | CastKind::UnsignedToFloat => {
+            integer(from).is_some() && float(to).is_some()
+        }
+        CastKind::Bitcast => match (integer(from), integer(to)) {
+            (Some(a), Some(b)) => a == b,
+            _ => float(from).is_some() && float(from) == float(to),
+        },
+    }
+}
+
+fn successors(terminator: &Node) -> Vec<&String> {
+    match terminator {
+        Node::Goto(target) => vec![target],
+        Node::Branch {
+            then, otherwise, ..
+        } => vec![then, otherwise],
+        _ => Vec::new(),
+    }
+}
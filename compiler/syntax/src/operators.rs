@@ -1,9 +1,10 @@
+use serde::{Deserialize, Serialize};
 use std::fmt::Display;
 
 #[derive(Debug)]
 pub struct ConversionError;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum CompareOperator {
     NotEquals,
     Compare,
@@ -15,8 +16,7 @@ pub enum CompareOperator {
     Or,
 }
 
-
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum ArithmeticOperator {
     Plus,
     Subtract,
@@ -27,8 +27,7 @@ pub enum ArithmeticOperator {
     RightBitshift,
 }
 
-
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum EqualsOperation {
     Equals,
     PlusEquals,
@@ -38,13 +37,12 @@ pub enum EqualsOperation {
     RemainderEquals,
 }
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum Operator {
     Arithmetic(ArithmeticOperator),
     Compare(CompareOperator),
 }
 
-
 impl Display for Operator {
     #[allow(unused, non_snake_case)]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -102,7 +100,6 @@ impl Display for ArithmeticOperator {
 }
 
 impl Display for CompareOperator {
-    #[allow(unused, non_snake_case)]
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         use CompareOperator::*;
 
@@ -110,12 +107,14 @@ impl Display for CompareOperator {
             f,
             "{}",
             match self {
-                Equals => "=",
-                PlusEquals => "+=",
-                SubtractEquals => "-=",
-                MultiplyEquals => "*=",
-                DivideEquals => "/=",
-                RemainderEquals => "%=",
+                NotEquals => "!=",
+                Compare => "==",
+                GreaterThan => ">",
+                GreaterThanOrEquals => ">=",
+                LessThan => "<",
+                LessThanOrEquals => "<=",
+                And => "&&",
+                Or => "||",
             }
         )
     }
@@ -0,0 +1,419 @@
+#[cfg(test)]
+mod tests {
+    use syntax::{
+        mir::{Block, CastKind, Expression, Function, Module, Node, Struct, Type, validate},
+        operators::ArithmeticOperator,
+    };
+
+    macro_rules! valid_test {
+        ($name:ident, $return_type:expr, $blocks:expr) => {
+            #[test]
+            fn $name() {
+                valid($return_type, $blocks);
+            }
+        };
+    }
+    macro_rules! invalid_test {
+        ($name:ident, $return_type:expr, $blocks:expr, $message:expr) => {
+            #[test]
+            fn $name() {
+                invalid($return_type, $blocks, $message);
+            }
+        };
+    }
+
+    fn module(return_type: Type, blocks: Vec<Block>) -> Module {
+        Module {
+            structs: Vec::new(),
+            constants: Vec::new(),
+            declarations: Vec::new(),
+            functions: vec![Function {
+                internal: false,
+                name: "main".into(),
+                parameters: Vec::new(),
+                return_type,
+                blocks,
+            }],
+        }
+    }
+
+    fn valid(return_type: Type, blocks: Vec<Block>) {
+        let module = module(return_type, blocks);
+        if let Err(errors) = validate(&module) {
+            panic!("Expected valid MIR\n{module}\n{errors:#?}")
+        }
+    }
+    fn invalid(return_type: Type, blocks: Vec<Block>, message: &str) {
+        let module = module(return_type, blocks);
+        let errors = validate(&module).expect_err("Expected invalid MIR");
+
+        assert!(
+            errors
+                .iter()
+                .any(|e| e.function == "main" && e.message.contains(message)),
+            "Expected an error containing {message:?}\n{module}\n{errors:#?}"
+        );
+    }
+
+    fn block(label: &str, body: Vec<Node>) -> Block {
+        Block {
+            label: label.into(),
+            body,
+        }
+    }
+    fn int(value: &str) -> Expression {
+        Expression::Integer(Type::Int(32), value.into())
+    }
+    fn allocate(name: &str, data_type: Type) -> Node {
+        Node::Allocate {
+            name: name.into(),
+            data_type,
+        }
+    }
+    fn set(name: &str, value: Expression) -> Node {
+        Node::Set {
+            name: name.into(),
+            data_type: value.data_type(),
+            value,
+        }
+    }
+    fn goto(label: &str) -> Node {
+        Node::Goto(label.into())
+    }
+    fn extract(name: &str, index: usize, data_type: Type) -> Expression {
+        Expression::Extract {
+            value: Box::new(Expression::Local(Type::Struct("pair".into()), name.into())),
+            index,
+            data_type,
+        }
+    }
+    fn overflow(operator: ArithmeticOperator, left: Expression, right: Expression) -> Expression {
+        Expression::Overflow {
+            operator,
+            left: Box::new(left),
+            right: Box::new(right),
+        }
+    }
+    fn branch(condition: Expression, then: &str, otherwise: &str) -> Node {
+        Node::Branch {
+            condition,
+            then: then.into(),
+            otherwise: otherwise.into(),
+        }
+    }
+
+    valid_test!(
+        return_void,
+        Type::Void,
+        vec![block("start", vec![Node::Return(None)])]
+    );
+    valid_test!(
+        allocate_and_set,
+        Type::Int(32),
+        vec![block(
+            "start",
+            vec![
+                allocate("x", Type::Int(32)),
+                set("x", int("5")),
+                Node::Return(Some(int("0")))
+            ]
+        )]
+    );
+    valid_test!(
+        allocated_in_dominating_block,
+        Type::Void,
+        vec![
+            block("start", vec![allocate("x", Type::Boolean), goto("next")]),
+            block(
+                "next",
+                vec![set("x", Expression::Boolean(true)), Node::Return(None)]
+            ),
+        ]
+    );
+    valid_test!(
+        branch_to_both_blocks,
+        Type::Void,
+        vec![
+            block(
+                "start",
+                vec![
+                    allocate("x", Type::Int(32)),
+                    branch(Expression::Boolean(true), "then", "otherwise")
+                ]
+            ),
+            block("then", vec![set("x", int("1")), Node::Return(None)]),
+            block("otherwise", vec![set("x", int("2")), Node::Return(None)]),
+        ]
+    );
+    valid_test!(
+        unreachable_block,
+        Type::Void,
+        vec![
+            block("start", vec![Node::Return(None)]),
+            block("bb1", vec![Node::Return(None)]),
+        ]
+    );
+
+    invalid_test!(
+        missing_terminator,
+        Type::Void,
+        vec![block("start", vec![allocate("x", Type::Boolean)])],
+        "block 'start' has no terminator"
+    );
+    invalid_test!(
+        terminator_before_end,
+        Type::Void,
+        vec![block(
+            "start",
+            vec![Node::Return(None), allocate("x", Type::Boolean)]
+        )],
+        "terminator before its end"
+    );
+    invalid_test!(
+        unknown_goto_target,
+        Type::Void,
+        vec![block("start", vec![goto("nowhere")])],
+        "unknown block 'nowhere'"
+    );
+    invalid_test!(
+        unknown_branch_target,
+        Type::Void,
+        vec![block(
+            "start",
+            vec![branch(Expression::Boolean(true), "start", "nowhere")]
+        ),],
+        "unknown block 'nowhere'"
+    );
+    invalid_test!(
+        branch_on_integer,
+        Type::Void,
+        vec![
+            block("start", vec![branch(int("1"), "end", "end")]),
+            block("end", vec![Node::Return(None)]),
+        ],
+        "branch on a condition with type i32"
+    );
+    valid_test!(
+        overflow_check,
+        Type::Int(32),
+        vec![
+            block(
+                "start",
+                vec![branch(
+                    overflow(ArithmeticOperator::Plus, int("1"), int("2")),
+                    "panic",
+                    "end"
+                )]
+            ),
+            block("panic", vec![Node::Unreachable]),
+            block("end", vec![Node::Return(Some(int("3")))]),
+        ]
+    );
+    invalid_test!(
+        overflow_check_on_division,
+        Type::Void,
+        vec![block(
+            "start",
+            vec![
+                Node::Expression(overflow(ArithmeticOperator::Division, int("1"), int("2"))),
+                Node::Unreachable
+            ]
+        )],
+        "overflow check on operator '/'"
+    );
+    invalid_test!(
+        overflow_check_on_boolean,
+        Type::Void,
+        vec![block(
+            "start",
+            vec![
+                Node::Expression(overflow(
+                    ArithmeticOperator::Plus,
+                    Expression::Boolean(true),
+                    Expression::Boolean(false)
+                )),
+                Node::Unreachable
+            ]
+        )],
+        "overflow check on a value with type bool"
+    );
+    invalid_test!(
+        unreachable_before_end,
+        Type::Void,
+        vec![block("start", vec![Node::Unreachable, Node::Return(None)])],
+        "block 'start' has a terminator before its end: unreachable"
+    );
+    invalid_test!(
+        allocated_on_one_branch,
+        Type::Void,
+        vec![
+            block(
+                "start",
+                vec![branch(Expression::Boolean(true), "then", "end")]
+            ),
+            block("then", vec![allocate("x", Type::Int(32)), goto("end")]),
+            block("end", vec![set("x", int("1")), Node::Return(None)]),
+        ],
+        "used in block 'end' before it is allocated"
+    );
+    invalid_test!(
+        duplicate_label,
+        Type::Void,
+        vec![
+            block("start", vec![goto("start")]),
+            block("start", vec![Node::Return(None)]),
+        ],
+        "duplicate block label"
+    );
+    invalid_test!(
+        return_type_mismatch,
+        Type::Boolean,
+        vec![block("start", vec![Node::Return(Some(int("1")))])],
+        "return of type i32"
+    );
+    invalid_test!(
+        return_without_value,
+        Type::Int(32),
+        vec![block("start", vec![Node::Return(None)])],
+        "return without a value"
+    );
+    invalid_test!(
+        set_type_mismatch,
+        Type::Void,
+        vec![block(
+            "start",
+            vec![
+                allocate("x", Type::Boolean),
+                set("x", int("1")),
+                Node::Return(None)
+            ]
+        )],
+        "allocated as bool"
+    );
+    invalid_test!(
+        set_before_allocate,
+        Type::Void,
+        vec![block(
+            "start",
+            vec![
+                set("x", int("1")),
+                allocate("x", Type::Int(32)),
+                Node::Return(None)
+            ]
+        )],
+        "before it is allocated"
+    );
+    invalid_test!(
+        allocated_on_one_path,
+        Type::Void,
+        vec![
+            block("start", vec![goto("loop")]),
+            block("loop", vec![set("x", int("1")), goto("body")]),
+            block("body", vec![allocate("x", Type::Int(32)), goto("loop")]),
+        ],
+        "used in block 'loop' before it is allocated"
+    );
+    invalid_test!(
+        allocated_twice,
+        Type::Void,
+        vec![block(
+            "start",
+            vec![
+                allocate("x", Type::Int(32)),
+                allocate("x", Type::Int(32)),
+                Node::Return(None)
+            ]
+        )],
+        "allocated more than once"
+    );
+    invalid_test!(
+        extract_from_unknown_struct,
+        Type::Boolean,
+        vec![block(
+            "start",
+            vec![
+                allocate("p", Type::Struct("pair".into())),
+                Node::Return(Some(extract("p", 1, Type::Boolean)))
+            ]
+        )],
+        "extract of field 1 from a value with type %pair"
+    );
+    invalid_test!(no_blocks, Type::Void, Vec::new(), "function has no blocks");
+
+    fn cast(kind: CastKind, value: Expression, data_type: Type) -> Expression {
+        Expression::Cast {
+            kind,
+            value: Box::new(value),
+            data_type,
+        }
+    }
+
+    valid_test!(
+        casts,
+        Type::Float(64),
+        vec![block(
+            "start",
+            vec![
+                allocate("x", Type::UInt(8)),
+                set("x", cast(CastKind::Truncate, int("300"), Type::UInt(8))),
+                Node::Return(Some(cast(
+                    CastKind::UnsignedToFloat,
+                    Expression::Local(Type::UInt(8), "x".into()),
+                    Type::Float(64)
+                )))
+            ]
+        )]
+    );
+    invalid_test!(
+        widening_truncate,
+        Type::Int(64),
+        vec![block(
+            "start",
+            vec![Node::Return(Some(cast(
+                CastKind::Truncate,
+                int("1"),
+                Type::Int(64)
+            )))]
+        )],
+        "invalid trunc cast from i32 to i64"
+    );
+    invalid_test!(
+        float_extend_from_integer,
+        Type::Float(64),
+        vec![block(
+            "start",
+            vec![Node::Return(Some(cast(
+                CastKind::FloatExtend,
+                int("1"),
+                Type::Float(64)
+            )))]
+        )],
+        "invalid fpext cast from i32 to f64"
+    );
+
+    fn pair(index: usize, data_type: Type) -> Module {
+        let mut module = module(
+            Type::Boolean,
+            vec![block(
+                "start",
+                vec![
+                    allocate("p", Type::Struct("pair".into())),
+                    Node::Return(Some(extract("p", index, data_type))),
+                ],
+            )],
+        );
+        module.structs.push(Struct {
+            name: "pair".into(),
+            fields: vec![Type::Int(32), Type::Boolean],
+        });
+        module
+    }
+
+    #[test]
+    fn extract_struct_field() {
+        assert_eq!(validate(&pair(1, Type::Boolean)), Ok(()));
+
+        let errors = validate(&pair(0, Type::Boolean)).expect_err("Expected invalid MIR");
+        assert!(errors[0].message.contains("but it has type i32"));
+    }
+}
@@ -0,0 +1,172 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use compiler::Emit;
+
+    use crate::common::{failed, generate, position, run};
+
+    #[test]
+    fn allocation() {
+        let mir = generate(
+            "func main() i32 { var b = Box::new(3) return *b }",
+            Emit::Mir,
+        );
+
+        assert!(mir.contains("declare func malloc(size: u64) ptr"));
+        assert!(mir.contains("declare func free(pointer: ptr) void"));
+        assert!(mir.contains("set $value0: ptr = call ptr malloc(u64 4)"));
+        assert!(mir.contains("store i32 i32 3 -> ptr $value0"));
+        assert!(mir.contains("set $value1: i32 = load i32 ptr b"));
+        assert!(
+            position(&mir, "call void $drop$Box$i32(ptr b)") < position(&mir, "return i32 $value1")
+        );
+    }
+
+    #[test]
+    fn drop_glue() {
+        let mir = generate(
+            "func main() { var b = Box::new(Box::new(true)) }",
+            Emit::Mir,
+        );
+
+        assert!(mir.contains("func $drop$Box$Box$bool(value: ptr) void"));
+        assert!(mir.contains("call void $drop$Box$bool(load ptr ptr value)"));
+        assert!(mir.contains("call void $drop$Box$Box$bool(ptr b)"));
+    }
+
+    #[test]
+    fn drops_in_reverse_order() {
+        let mir = generate(
+            "func main() { var a = Box::new(1) { var b = Box::new(2) } var c = Box::new(3) }",
+            Emit::Mir,
+        );
+
+        let b = position(&mir, "$drop$Box$i32(ptr b)");
+        let c = position(&mir, "$drop$Box$i32(ptr c)");
+        let a = position(&mir, "$drop$Box$i32(ptr a)");
+        assert!(b < c && c < a);
+    }
+
+    #[test]
+    fn early_return_drops() {
+        let mir = generate(
+            "func f() i32 { var a = Box::new(1) { var b = Box::new(2) return *a + *b } return 0 }",
+            Emit::Mir,
+        );
+
+        let b = position(&mir, "$drop$Box$i32(ptr b)");
+        let a = position(&mir, "$drop$Box$i32(ptr a)");
+        assert!(b < a && a < position(&mir, "return i32 $value4"));
+    }
+
+    #[test]
+    fn moved_values_are_not_dropped() {
+        let mir = generate(
+            "func take(value Box<i32>) {}\nfunc main() { var a = Box::new(1) take(a) var b = Box::new(2) var c = b }",
+            Emit::Mir,
+        );
+
+        assert!(!mir.contains("$drop$Box$i32(ptr b)"));
+        assert!(!mir.contains("$drop$Box$i32(ptr a)"));
+        assert!(mir.contains("call void $drop$Box$i32(ptr c)"));
+    }
+
+    #[test]
+    fn reassignment_drops_old_value() {
+        let mir = generate(
+            "func main() { var mut a = Box::new(1) a = Box::new(*a + 1) }",
+            Emit::Mir,
+        );
+
+        let drop = position(&mir, "call void $drop$Box$i32(ptr a)");
+        assert!(position(&mir, "store i32 (i32 $value1 + i32 1)") < drop);
+        assert!(drop < position(&mir, "set a: ptr = ptr $value2"));
+    }
+
+    #[test]
+    fn generic_argument() {
+        failed(
+            "func id<T>(value T) T { return value }\nfunc main() { var b = id(Box::new(4)) }",
+            "Type 'Box<i32>' cannot be used for 'T' of 'id', it is not copyable",
+        );
+    }
+
+    #[test]
+    fn use_after_move() {
+        failed(
+            "func take(b Box<i32>) {}\nfunc main() { var a = Box::new(1) take(a) take(a) }",
+            "Use of moved value 'a'",
+        );
+        failed(
+            "func main() i32 { var a = Box::new(1) var b = a return *a }",
+            "Use of moved value 'a'",
+        );
+        failed(
+            "func read(&b Box<i32>) {}\nfunc main() { var a = Box::new(1) var b = a read(a) }",
+            "Borrow of moved value 'a'",
+        );
+    }
+
+    #[test]
+    fn invalid_moves() {
+        failed(
+            "func take(b Box<i32>) {}\nfunc f(&b Box<i32>) { take(b) }",
+            "Cannot move out of 'b', which is behind a reference",
+        );
+        failed(
+            "func main() { var a = Box::new(Box::new(1)) var b = *a }",
+            "Cannot move out of a box, '*a' has type 'Box<i32>'",
+        );
+        failed(
+            "func main() { var a = Box::new(1) var f = move || *a }",
+            "Cannot move 'a' of type 'Box<i32>' into a closure",
+        );
+        failed(
+            "func take(b Box<i32>) {}\nfunc main() { var a = Box::new(1) var f = || take(a) }",
+            "Cannot move 'a' out of the environment of a closure",
+        );
+    }
+
+    #[test]
+    fn reinitialized_after_move() {
+        generate(
+            "func take(b Box<i32>) {}\nfunc main() i32 { var mut a = Box::new(1) take(a) a = Box::new(2) return *a }",
+            Emit::Mir,
+        );
+    }
+
+    #[test]
+    fn unsupported_owners() {
+        failed(
+            "func main() { var a = Box::new(1) loop { break } }",
+            "Unsupported statement",
+        );
+        failed(
+            "struct Node { value i32, next Box<Node> }",
+            "Field 'next' of struct 'Node' cannot have type 'Box<Node>'",
+        );
+    }
+
+    #[test]
+    fn run_boxes() {
+        run(
+            "func make(value i32) Box<i32> { return Box::new(value) }
+func read(&b Box<i32>) i32 { return *b }
+func consume(b Box<i32>) i32 { return *b + 1 }
+func nested() i32 {
+    var outer = Box::new(Box::new(7))
+    return **outer
+}
+func main() i32 {
+    var mut a = make(3)
+    a = Box::new(*a + 1)
+    var b = a
+    var total = read(b) + consume(b) + nested()
+    make(6)
+    return total
+}",
+            16,
+        );
+    }
+}
@@ -0,0 +1,216 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use compiler::compile;
+    use context::{Backend, CompilerCtx, OptLevel, OutputKind};
+
+    use crate::common::TempProject;
+
+    fn init(config: &str, output: Option<OutputKind>) -> (TempProject, CompilerCtx) {
+        let project = TempProject::new();
+        let mut compiler = CompilerCtx::builder()
+            .project_path(project.path().to_path_buf())
+            .backend(Backend::C)
+            .output(output)
+            .build();
+        compiler.write(&CompilerCtx::entry(), "func main() i32 { return 7 }");
+        compiler.write(&PathBuf::from("eclipse.toml"), config);
+        (project, compiler)
+    }
+
+    #[test]
+    fn executable_named_after_package() {
+        let (_project, mut compiler) = init("[package]\nname = \"app\"", None);
+        let executable = compile(&mut compiler).unwrap();
+
+        assert_eq!(executable.file_name().unwrap(), "app");
+        let status = std::process::Command::new(executable).status().unwrap();
+        assert_eq!(status.code(), Some(7));
+    }
+
+    #[test]
+    fn static_library() {
+        let (_project, mut compiler) =
+            init("[package]\nname = \"app\"", Some(OutputKind::StaticLib));
+        let library = compile(&mut compiler).unwrap();
+
+        assert_eq!(library.file_name().unwrap(), "libapp.a");
+        assert!(library.exists());
+    }
+
+    #[test]
+    fn shared_library_from_config() {
+        let (_project, mut compiler) = init(
+            "[package]\nname = \"app\"\n[build]\noutput = \"shared\"",
+            None,
+        );
+        let library = compile(&mut compiler).unwrap();
+
+        assert_eq!(library.file_name().unwrap(), "libapp.so");
+        assert!(library.exists());
+    }
+
+    #[test]
+    fn opt_level() {
+        let compiler = CompilerCtx::builder().project_path(PathBuf::new()).build();
+        assert_eq!(compiler.opt_level(), OptLevel::O0);
+
+        let compiler = CompilerCtx::builder()
+            .project_path(PathBuf::new())
+            .release(true)
+            .build();
+        assert_eq!(compiler.opt_level(), OptLevel::O3);
+
+        let (_project, mut compiler) = init("[build]\nopt-level = 2", None);
+        compile(&mut compiler).unwrap();
+        assert_eq!(compiler.opt_level(), OptLevel::O2);
+    }
+
+    #[test]
+    fn link_failure_is_diagnostic() {
+        let (_project, mut compiler) = init("[build]\nlibraries = [\"missing\"]", None);
+
+        assert!(compile(&mut compiler).is_none());
+        assert!(compiler.diagnostics.has_errors());
+    }
+
+    fn library() -> (TempProject, CompilerCtx) {
+        let (project, mut compiler) = init("[package]\nname = \"math\"\nversion = \"1.0.0\"", None);
+        std::fs::remove_file(compiler.resolve_path(&CompilerCtx::entry())).unwrap();
+        compiler.write(
+            &CompilerCtx::library_entry(),
+            "pub import ops\npub func seven() i32 { return 7 }\nfunc hidden() {}",
+        );
+        compiler.write(
+            &PathBuf::from("src/ops.ecl"),
+            "pub func two(a i32) i32 { return a + 1 }",
+        );
+        (project, compiler)
+    }
+
+    #[test]
+    fn library_with_metadata() {
+        let (_project, mut compiler) = library();
+        let library = compile(&mut compiler).unwrap();
+        assert_eq!(library.file_name().unwrap(), "libmath.a");
+
+        let metadata = compiler
+            .read(&PathBuf::from("target/math.meta.toml"))
+            .unwrap();
+        assert_eq!(
+            metadata,
+            "name = \"math\"
+version = \"1.0.0\"
+library = \"libmath.a\"
+
+[[modules]]
+path = \"src/lib.ecl\"
+
+[[modules.imports]]
+name = \"ops\"
+path = \"src/ops.ecl\"
+
+[[modules.functions]]
+name = \"seven\"
+parameters = []
+return-type = \"i32\"
+
+[[modules]]
+path = \"src/ops.ecl\"
+imports = []
+
+[[modules.functions]]
+name = \"two\"
+parameters = [\"a i32\"]
+return-type = \"i32\"
+"
+        );
+    }
+
+    #[test]
+    fn generic_library_function() {
+        let (_project, mut compiler) = library();
+        compiler.write(
+            &PathBuf::from("src/ops.ecl"),
+            "pub func two(a i32) i32 { return 2 }\npub func id<T>(value T) T { return value }",
+        );
+
+        assert!(compile(&mut compiler).is_none());
+        assert!(
+            compiler
+                .diagnostics
+                .render()
+                .contains("Generic function 'id' cannot be exported from a library")
+        );
+    }
+
+    #[test]
+    fn library_struct() {
+        let (_project, mut compiler) = library();
+        compiler.write(
+            &PathBuf::from("src/ops.ecl"),
+            "pub func two(a i32) i32 { return 2 }\npub struct Pair { a i32, b i32 }",
+        );
+
+        assert!(compile(&mut compiler).is_none());
+        assert!(
+            compiler
+                .diagnostics
+                .render()
+                .contains("Struct 'Pair' cannot be exported from a library")
+        );
+    }
+
+    #[test]
+    fn library_is_not_executable() {
+        let (_project, math) = library();
+        let project = math.resolve_path(&PathBuf::new());
+        let mut compiler = CompilerCtx::builder()
+            .project_path(project)
+            .backend(Backend::C)
+            .output(Some(OutputKind::Executable))
+            .build();
+
+        assert!(compile(&mut compiler).is_none());
+        assert!(
+            compiler
+                .diagnostics
+                .render()
+                .contains("Cannot build library 'math' as an executable")
+        );
+    }
+
+    #[test]
+    fn link_prebuilt_library() {
+        let (_library, mut math) = library();
+        compile(&mut math).unwrap();
+        let math_path = math.resolve_path(&PathBuf::new());
+        std::fs::remove_dir_all(math_path.join("src")).unwrap();
+
+        let config = format!(
+            "[package]\nname = \"app\"\n\n[dependencies]\nmath = {{ path = {:?} }}",
+            math_path.display().to_string()
+        );
+        let (_app, mut compiler) = init(&config, None);
+        compiler.write(
+            &CompilerCtx::entry(),
+            "use math::{seven, ops::two}\nfunc main() i32 { return seven() * two(1) - 7 }",
+        );
+        let executable = compile(&mut compiler).unwrap();
+
+        assert!(compiler.libraries()[0].ends_with("target/libmath.a"));
+        let status = std::process::Command::new(executable).status().unwrap();
+        assert_eq!(status.code(), Some(7));
+    }
+
+    #[test]
+    fn unknown_output_kind() {
+        let (_project, mut compiler) = init("[build]\noutput = \"dll\"", None);
+
+        assert!(compile(&mut compiler).is_none());
+        assert!(compiler.diagnostics.has_errors());
+    }
+}
@@ -0,0 +1,79 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use compiler::generate_ir;
+    use context::CompilerCtx;
+
+    use crate::common::{mock, run};
+
+    macro_rules! c_test {
+        ($name:ident, $input:expr, $expected:expr) => {
+            #[test]
+            fn $name() {
+                generated_eq($input, $expected);
+            }
+        };
+    }
+    macro_rules! run_test {
+        ($name:ident, $input:expr, $code:expr) => {
+            #[test]
+            fn $name() {
+                run($input, $code);
+            }
+        };
+    }
+
+    fn generated_eq(input: &'static str, expected: &'static str) {
+        let mut compiler = mock().build();
+        compiler.write(&CompilerCtx::entry(), input);
+
+        let source = generate_ir(&mut compiler);
+        assert!(
+            source == expected,
+            "INPUT: {input}\n----------------------------\nEXPECTED:\n{expected}\n----------------------------\nRESULT:\n{source}"
+        );
+    }
+
+    c_test!(
+        main_return,
+        "func main() i32 { return 0 }",
+        "#include <stdbool.h>
+#include <stdint.h>
+
+int main(void);
+
+int main(void) {
+start:
+    return 0;
+}
+"
+    );
+    c_test!(
+        variables,
+        "func main() { var x: i64 = 5 var int = true } func other(a i32) i32 { return 1 }",
+        "#include <stdbool.h>
+#include <stdint.h>
+
+int main(void);
+int32_t ETest_Smain_Sother(int32_t a);
+
+int main(void) {
+    int64_t x;
+    bool int_;
+start:
+    x = 5;
+    int_ = true;
+    return 0;
+}
+
+int32_t ETest_Smain_Sother(int32_t a) {
+start:
+    return 1;
+}
+"
+    );
+
+    run_test!(run_exit_code, "func main() i32 { return 3 }", 3);
+    run_test!(run_void_main, "func main() { var x = 1 }", 0);
+}
@@ -0,0 +1,128 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use compiler::{Backend, Emit};
+
+    use crate::common::{build, generate, mock, run};
+
+    #[test]
+    fn integer_casts() {
+        let mir = generate(
+            "func f(a i32, b u8, c i64) i64 {
+    var d = b as i64 + c
+    var e = a as u32
+    return a as i64 + d + (c as i16) as i64 + e as i64
+}",
+            Emit::Mir,
+        );
+
+        assert!(mir.contains("zext u8 b to i64"));
+        assert!(mir.contains("sext i32 a to i64"));
+        assert!(mir.contains("trunc i64 c to i16"));
+        assert!(mir.contains("bitcast i32 a to u32"));
+        assert!(mir.contains("zext u32 e to i64"));
+    }
+
+    #[test]
+    fn float_casts() {
+        let mir = generate(
+            "func f(a f32, b i32, c u16) f64 {
+    var d = a as f64 + b as f64
+    var e = (d as f32) as i32 + (a as u8) as i32
+    return c as f64 + e as f64
+}",
+            Emit::Mir,
+        );
+
+        assert!(mir.contains("fpext f32 a to f64"));
+        assert!(mir.contains("sitofp i32 b to f64"));
+        assert!(mir.contains("fptrunc f64 d to f32"));
+        assert!(mir.contains("set $value0: f32 = fptrunc f64 d to f32"));
+        assert!(mir.contains("fptosi f32 $value0 to i32"));
+        assert!(mir.contains("fptoui f32 a to u8"));
+        assert!(mir.contains("uitofp u16 c to f64"));
+    }
+
+    #[test]
+    fn same_type_cast() {
+        let mir = generate("func f(a i32) i32 { return a as i32 }", Emit::Mir);
+
+        assert!(mir.contains("return i32 a"));
+        assert!(!mir.contains("bitcast"));
+    }
+
+    #[test]
+    fn llvm_instructions() {
+        let (_, source) = build(
+            mock().backend(Backend::Llvm),
+            "func f(a u32, b u32, x f64) bool {
+    var c = a / b >> 1
+    var y = x * 2.5 - c as f64
+    var less = a < b
+    return y > 1.0
+}",
+        );
+
+        assert!(source.contains("udiv i32"));
+        assert!(source.contains("lshr i32"));
+        assert!(source.contains("fmul double"));
+        assert!(source.contains("0x4004000000000000"));
+        assert!(source.contains("uitofp i32"));
+        assert!(source.contains("fsub double"));
+        assert!(source.contains("icmp ult i32"));
+        assert!(source.contains("fcmp ogt double"));
+    }
+
+    #[test]
+    fn saturating_float_casts() {
+        let (_, source) = build(
+            mock().backend(Backend::Llvm),
+            "func f(x f64, y f32) u8 { return (x as i32) as u8 + y as u8 }",
+        );
+
+        assert!(source.contains("%.t2 = call i32 @llvm.fptosi.sat.i32.f64(double %.t1)"));
+        assert!(source.contains("declare i8 @llvm.fptoui.sat.i8.f32(float)"));
+    }
+
+    #[test]
+    fn run_saturating_float_casts() {
+        run(
+            "func main() i32 {
+    var zero = 0.0
+    var a = 300.0 as u8
+    var b = (0.0 - 5.5) as u8
+    var c = ((0.0 - 3000000000.0) as i32 == 0 - 2147483647 - 1)
+    var d = (zero / zero) as i32
+    var e = (5000000000.0 as i32 == 2147483647)
+    return (a / 5) as i32 + b as i32 + c as i32 + d + e as i32
+}",
+            53,
+        );
+    }
+
+    #[test]
+    fn run_unsigned_sizes() {
+        run(
+            "func main() i32 {
+    var big = (0 - 1) as usize
+    return (big >> 62) as i32 + (big > 1) as i32 * 3
+}",
+            6,
+        );
+    }
+
+    #[test]
+    fn run_casts() {
+        run(
+            "func main() i32 {
+    var big = 4000000000 as u32
+    var half = big / 2 >> 1
+    var f = 2.75 as f32 * 2.0
+    var neg = (0 - 1) as u8
+    return (big as u64 / 1000000) as i32 + f as i32 + (half > 999999999) as i32 + neg as i32
+}",
+            (4000 + 5 + 1 + 255) % 256,
+        );
+    }
+}
@@ -0,0 +1,169 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use compiler::{Backend, Emit};
+
+    use crate::common::{MAIN, build, emitted, execute, failed, mock};
+
+    fn generate(input: &str, release: bool) -> String {
+        let (compiler, _) = build(mock().emit(vec![Emit::Mir]).release(release), input);
+        emitted(&compiler, Emit::Mir)
+    }
+
+    fn run(input: &str, code: i32, stderr: &str) {
+        let output = execute(&[(MAIN, input)], "");
+        assert_eq!(output.status.code(), Some(code), "INPUT: {input}");
+        assert_eq!(String::from_utf8_lossy(&output.stderr), stderr);
+    }
+
+    #[test]
+    fn overflow_checks() {
+        let mir = generate("func add(a i32, b i32) i32 {\n    return a + b\n}", false);
+
+        assert!(mir.contains("branch overflow (i32 a + i32 b), bb1, bb2"));
+        assert!(mir.contains(
+            "const $str1 = \"panic at src/main.ecl:2:12: attempt to add with overflow\""
+        ));
+        assert!(mir.contains("call void $panic(str $str1 u64 56)\n    unreachable"));
+        assert!(mir.contains("call void exit(i32 101)"));
+        assert!(mir.contains("internal func $panic("));
+        assert!(!mir.contains("internal func Test$main$add("));
+    }
+
+    #[test]
+    fn operands_are_evaluated_once() {
+        let mir = generate(
+            "func one() i32 { return 1 }\nfunc main() i32 { return one() * 3 }",
+            false,
+        );
+
+        assert_eq!(mir.matches("call i32 Test$main$one()").count(), 1);
+        assert!(mir.contains("branch overflow (i32 $value0 * i32 3), bb1, bb2"));
+        assert!(mir.contains("return (i32 $value0 * i32 3)"));
+    }
+
+    #[test]
+    fn division_checks() {
+        let mir = generate("func div(a i32, b i32) i32 { return a % b }", false);
+
+        assert!(mir.contains("branch (i32 b == i32 0), bb1, bb2"));
+        assert!(mir.contains("branch ((i32 b == i32 -1) && overflow (i32 0 - i32 a)), bb3, bb4"));
+        assert!(mir.contains("with a divisor of zero"));
+    }
+
+    #[test]
+    fn constant_operands() {
+        let mir = generate(
+            "func f(a i32) i32 { return (a / 2) >> 1 }\nfunc g(a i32) i32 { return a << 32 }",
+            false,
+        );
+
+        assert!(!mir.contains("attempt to divide"));
+        assert!(!mir.contains("attempt to shift right"));
+        assert!(mir.contains("branch ((i32 32 < i32 0) || (i32 32 >= i32 32)), bb1, bb2"));
+    }
+
+    #[test]
+    fn no_indexable_types() {
+        failed(
+            "func first(values [i32]) i32 { return 0 }",
+            "Unsupported type: [i32]",
+        );
+        failed(
+            "func first(values [i32; N]) i32 { return 0 }",
+            "Unsupported type: [i32; N]",
+        );
+    }
+
+    #[test]
+    fn release_has_no_checks() {
+        let mir = generate("func add(a i32, b i32) i32 { return a / b + 1 }", true);
+
+        assert!(!mir.contains("branch"));
+        assert!(!mir.contains("$panic"));
+        assert!(mir.contains("return ((i32 a / i32 b) + i32 1)"));
+    }
+
+    #[test]
+    fn llvm_intrinsics() {
+        let (_, source) = build(
+            mock().backend(Backend::Llvm),
+            "func add(a i64, b i64) i64 { return a * b - 1 }",
+        );
+
+        assert!(
+            source.contains("call { i64, i1 } @llvm.smul.with.overflow.i64(i64 %.t1, i64 %.t2)")
+        );
+        assert!(source.contains("declare { i64, i1 } @llvm.smul.with.overflow.i64(i64, i64)"));
+        assert!(source.contains("declare { i64, i1 } @llvm.ssub.with.overflow.i64(i64, i64)"));
+        assert!(source.contains("  unreachable"));
+    }
+
+    #[test]
+    fn portable_c() {
+        let (_, source) = build(
+            mock(),
+            "func mul(a u32, b i64) i64 { return (a * 2) as i64 - b * 3 }\nfunc main() {}",
+        );
+
+        assert!(!source.contains("__builtin"));
+        assert!(!source.contains('$'));
+        assert!(source.contains("(2 != 0 && a > UINT32_MAX / 2)"));
+        assert!(source.contains("for (;;) {}"));
+    }
+
+    #[test]
+    fn run_overflow() {
+        run(
+            "func add(a i32, b i32) i32 {
+    return a + b
+}
+func main() i32 {
+    println(\"before\")
+    return add(2147483647, 1)
+}",
+            101,
+            "panic at src/main.ecl:2:12: attempt to add with overflow\n",
+        );
+    }
+
+    #[test]
+    fn run_multiply_bounds() {
+        let input = "func mul(a i32, b i32) i32 {
+    return a * b
+}
+func main() i32 {
+    var a = mul(0 - 65536, 32768) / 65536 + 32768
+    var b = mul(0 - 1, 0 - 2147483647) / 2147483647
+    return a + b + mul(0 - 3, 0 - 2) + mul(0, 0 - 5)
+}";
+        run(input, 7, "");
+        run(
+            &input.replace("mul(0, 0 - 5)", "mul(65536, 32768)"),
+            101,
+            "panic at src/main.ecl:2:12: attempt to multiply with overflow\n",
+        );
+    }
+
+    #[test]
+    fn run_division_by_zero() {
+        run(
+            "func main() i32 {
+    var zero = 0
+    return 7 / zero
+}",
+            101,
+            "panic at src/main.ecl:3:12: attempt to divide by zero\n",
+        );
+    }
+
+    #[test]
+    fn run_in_range() {
+        run(
+            "func main() i32 { return (0 - 2147483647 - 1) / 2 % 7 + (1 << 4) }",
+            15,
+            "",
+        );
+    }
+}
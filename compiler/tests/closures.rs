@@ -0,0 +1,158 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use compiler::Emit;
+
+    use crate::common::{failed, generate, run};
+
+    const APPLY: &str = "func apply(f func(i32) i32, x i32) i32 { return f(x) }
+func add_one(x i32) i32 { return x + 1 }
+";
+
+    #[test]
+    fn capturing_closure() {
+        let input = format!("{APPLY}func main() i32 {{ var y = 2 return apply(|x| x + y, 1) }}");
+        let mir = generate(&input, Emit::Mir);
+
+        assert!(mir.contains("type %main$closure0$env = { ptr }"));
+        assert!(mir.contains("func main$closure0($env: ptr, x: i32) i32"));
+        assert!(mir.contains("return invoke i32 func f(i32 x)"));
+    }
+
+    #[test]
+    fn hir_shows_captures() {
+        let input =
+            format!("{APPLY}func main() i32 {{ var y = 2 return apply(move |x| x + y, 1) }}");
+        let hir = generate(&input, Emit::Hir);
+
+        assert!(hir.contains("move |x i32| i32 [y i32]"));
+    }
+
+    #[test]
+    fn function_value_thunk() {
+        let input = format!("{APPLY}func main() i32 {{ return apply(add_one, 1) }}");
+        let mir = generate(&input, Emit::Mir);
+
+        assert!(mir.contains("func Test$main$add_one$value($env: ptr, a0: i32) i32"));
+        assert!(mir.contains("return call i32 Test$main$add_one(i32 a0)"));
+        assert!(mir.contains("call i32 Test$main$apply(func Test$main$add_one$value, i32 1)"));
+    }
+
+    #[test]
+    fn returned_closure() {
+        failed(
+            "func f() func() i32 { var x = 1 return || x }",
+            "Closure may outlive the current function, but it borrows 'x', which is owned by the current function",
+        );
+        failed(
+            "func f() func() i32 { var x = 1 var g = move || x return g }",
+            "Cannot return a closure that captures 'x', its environment is stored in the current function",
+        );
+    }
+
+    #[test]
+    fn assign_immutable_capture() {
+        failed(
+            "func main() { var x = 1 var f = || { x = 2 } f() }",
+            "Cannot assign twice to immutable variable 'x'",
+        );
+    }
+
+    #[test]
+    fn conflicting_capture() {
+        failed(
+            "func f(&mut a i32, g func() i32) {}\nfunc main() { var mut x = 1 f(x, || x) }",
+            "Cannot borrow 'x' as immutable because it is also borrowed as mutable",
+        );
+    }
+
+    #[test]
+    fn run_closures() {
+        run(
+            &format!(
+                "{APPLY}func counter(&mut count i32, step func(i32) i32) {{ count = step(count) }}
+func main() i32 {{
+    var mut total = 10
+    var offset = 5
+    var add = |x i32| x + offset
+    var bump = || {{ total = total * 2 }}
+    bump()
+    var moved = move |x i32| x - offset
+    var mut c = 0
+    counter(c, add_one)
+    counter(c, |v| v * 3)
+    return apply(add, apply(add_one, moved(total))) + c
+}}"
+            ),
+            24,
+        );
+    }
+
+    const ADDER: &str =
+        "func adder(n i32) Box<func(i32) i32> { return Box::new(move |x| x + n) }\n";
+
+    #[test]
+    fn boxed_closure() {
+        let mir = generate(
+            &format!("{ADDER}func main() i32 {{ var add = adder(2) return add(1) }}"),
+            Emit::Mir,
+        );
+
+        assert!(mir.contains("type %Test$main$adder$closure0$env = { func, i32 }"));
+        assert!(mir.contains("set $value0: ptr = call ptr malloc(u64 24)"));
+        assert!(mir.contains(
+            "store func func Test$main$adder$closure0$drop with ptr $value0 -> field Test$main$adder$closure0$env.0 ptr $value0"
+        ));
+        assert!(mir.contains("internal func Test$main$adder$closure0$drop($env: ptr) void"));
+        assert!(mir.contains("invoke void load func extract 1 load func ptr value()"));
+        assert!(mir.contains("set $value1: i32 = invoke i32 func $value0(i32 1)"));
+        assert!(mir.contains("call void $drop$Box$func1$i32$i32(ptr add)"));
+    }
+
+    #[test]
+    fn boxed_closure_captures() {
+        let mir = generate(
+            "func main() { var a = Box::new(1) var f = Box::new(move || *a) }",
+            Emit::Mir,
+        );
+
+        assert!(
+            mir.contains("call void $drop$Box$i32(load ptr field main$closure0$env.1 ptr $env)")
+        );
+        assert!(!mir.contains("call void $drop$Box$i32(ptr a)"));
+
+        failed(
+            "func main() { var a = Box::new(1) var f = Box::new(move || *a) var b = a }",
+            "Use of moved value 'a'",
+        );
+        failed(
+            "func f() Box<func() i32> { var x = 1 return Box::new(|| x) }",
+            "Closure may outlive the current function, but it borrows 'x'",
+        );
+        failed(
+            "func f(b Box<func() i32>) func() i32 { return *b }",
+            "Cannot move out of a box, '*b' has type 'func() i32'",
+        );
+        failed(
+            "func main() { var f = || 1 var b = Box::new(f) }",
+            "Cannot box a value of type 'func() i32', only closures and functions can be boxed",
+        );
+    }
+
+    #[test]
+    fn run_boxed_closures() {
+        run(
+            &format!(
+                "{APPLY}{ADDER}func owner(value Box<i32>) Box<func() i32> {{ return Box::new(move || *value * 2) }}
+func main() i32 {{
+    var add = adder(3)
+    var double = owner(Box::new(4))
+    var named = Box::new(add_one)
+    return add(4) + double() + adder(10)(1) + named(0)
+}}"
+            ),
+            27,
+        );
+    }
+}
@@ -0,0 +1,117 @@
+#![allow(dead_code)]
+
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    process::{Command, Output, Stdio},
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use compiler::{Backend, Emit, compile, generate_ir};
+use context::{CompilerBuilder, CompilerCtx, files::MockResolver};
+
+pub const MAIN: &str = "src/main.ecl";
+
+pub fn mock() -> CompilerBuilder {
+    CompilerCtx::builder()
+        .project_path(PathBuf::new())
+        .resolver(MockResolver::new())
+        .backend(Backend::C)
+}
+
+pub fn build(builder: CompilerBuilder, input: &str) -> (CompilerCtx, String) {
+    let mut compiler = builder.build();
+    compiler.write(&CompilerCtx::entry(), input);
+
+    let source = generate_ir(&mut compiler);
+    (compiler, source)
+}
+
+pub fn init(input: &str, emit: Emit) -> CompilerCtx {
+    build(mock().emit(vec![emit]), input).0
+}
+
+pub fn emitted(compiler: &CompilerCtx, emit: Emit) -> String {
+    if compiler.diagnostics.has_errors() {
+        panic!("{}", compiler.diagnostics.render());
+    }
+    let path = PathBuf::from("target/build").with_extension(emit.extension());
+    compiler.read(&path).unwrap()
+}
+
+pub fn generate(input: &str, emit: Emit) -> String {
+    emitted(&init(input, emit), emit)
+}
+
+pub fn failed(input: &str, message: &str) {
+    let compiler = init(input, Emit::Hir);
+    let rendered = compiler.diagnostics.render();
+    assert!(rendered.contains(message), "INPUT: {input}\n{rendered}");
+}
+
+pub fn position(source: &str, line: &str) -> usize {
+    source
+        .find(line)
+        .unwrap_or_else(|| panic!("Expected '{line}' in:\n{source}"))
+}
+
+pub struct TempProject(PathBuf);
+impl TempProject {
+    pub fn new() -> Self {
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let count = COUNT.fetch_add(1, Ordering::Relaxed);
+        let name = format!("eclipse-{}-{count}", std::process::id());
+        Self(std::env::temp_dir().join(name))
+    }
+    pub fn path(&self) -> &Path {
+        &self.0
+    }
+    pub fn compiler(&self) -> CompilerCtx {
+        CompilerCtx::builder()
+            .project_path(self.0.clone())
+            .backend(Backend::C)
+            .build()
+    }
+}
+impl Drop for TempProject {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+pub fn execute(files: &[(&str, &str)], stdin: &str) -> Output {
+    let project = TempProject::new();
+    let mut compiler = project.compiler();
+    for (path, source) in files {
+        compiler.write(&PathBuf::from(path), source);
+    }
+
+    let executable = match compile(&mut compiler) {
+        Some(executable) => executable,
+        None => panic!("{}", compiler.diagnostics.render()),
+    };
+    let mut child = Command::new(executable)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(stdin.as_bytes())
+        .unwrap();
+    child.wait_with_output().unwrap()
+}
+
+pub fn run(input: &str, code: i32) -> Output {
+    let output = execute(&[(MAIN, input)], "");
+    assert_eq!(output.status.code(), Some(code), "INPUT: {input}");
+    output
+}
+
+pub fn run_stdout(input: &str, code: i32, stdout: &str) {
+    let output = run(input, code);
+    assert_eq!(String::from_utf8_lossy(&output.stdout), stdout);
+}
@@ -0,0 +1,79 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use compiler::Emit;
+
+    use crate::common::{generate, run};
+
+    const IDENTITY: &str = "func id<T>(value T) T { return value }\n";
+
+    #[test]
+    fn monomorphized_instances() {
+        let input = format!(
+            "{IDENTITY}func main() i32 {{ var a: i64 = id(5) var b = id::<bool>(true) var c = id(a) return id(7) }}"
+        );
+        let mir = generate(&input, Emit::Mir);
+
+        assert!(mir.contains("func Test$main$id$i64(value: i64) i64"));
+        assert!(mir.contains("func Test$main$id$bool(value: bool) bool"));
+        assert!(mir.contains("func Test$main$id$i32(value: i32) i32"));
+        assert_eq!(mir.matches("func Test$main$id$i64(").count(), 1);
+        assert!(!mir.contains("func Test$main$id("));
+        assert!(mir.contains("set a: i64 = call i64 Test$main$id$i64(i64 5)"));
+    }
+
+    #[test]
+    fn deterministic_names() {
+        let input = format!(
+            "{IDENTITY}func twice<T>(value T) T {{ return id(id(value)) }}\nfunc main() {{ var a = twice(true) var b = twice::<usize>(1) }}"
+        );
+        let first = generate(&input, Emit::C);
+        assert_eq!(generate(&input, Emit::C), first);
+
+        let instances: Vec<&str> = first
+            .lines()
+            .filter(|line| !line.starts_with(' ') && line.ends_with(';') && line.contains("ETest"))
+            .collect();
+        assert_eq!(
+            instances,
+            vec![
+                "static bool ETest_Smain_Stwice_Sbool(bool value);",
+                "static uint64_t ETest_Smain_Stwice_Susize(uint64_t value);",
+                "static bool ETest_Smain_Sid_Sbool(bool value);",
+                "static uint64_t ETest_Smain_Sid_Susize(uint64_t value);",
+            ]
+        );
+    }
+
+    const VALUES: &str = "func double(a i32) i32 { return a * 2 }\nfunc keep<T>(value T) T { return value }\nfunc main() i32 { var f = keep(double) var g = keep(f) return g(3) }";
+
+    #[test]
+    fn function_instances() {
+        let c = generate(VALUES, Emit::C);
+
+        assert!(c.contains("ETest_Smain_Skeep_Sfunc1_Si32_Si32("));
+        assert!(!c.contains("keep_Sfunc("));
+    }
+
+    #[test]
+    fn run_function_instances() {
+        run(VALUES, 6);
+    }
+
+    #[test]
+    fn nested_generic_calls() {
+        let input = "func first<T>(a T, b T) T { return a }\nfunc pick<A, B>(a A, b B) B { return first(b, b) }\nfunc main() i32 { return pick(true, 4) }";
+        let mir = generate(input, Emit::Mir);
+        assert!(mir.contains("func Test$main$pick$bool$i32(a: bool, b: i32) i32"));
+        assert!(mir.contains("func Test$main$first$i32(a: i32, b: i32) i32"));
+    }
+
+    #[test]
+    fn run_generic_function() {
+        run(
+            "func first<T>(a T, b T) T { return a }\nfunc main() i32 { var x: i64 = first(9, 2) var ok = first(true, false) return first::<i32>(5, 6) }",
+            5,
+        );
+    }
+}
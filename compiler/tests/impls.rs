@@ -0,0 +1,110 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use compiler::Emit;
+
+    use crate::common::{failed, generate, run};
+
+    const CELL: &str = "impl i32 {
+    func new(value i32) Self { return value }
+    func get(&self) i32 { return self }
+    func set(&mut self, value i32) { self = value }
+}
+";
+
+    #[test]
+    fn inherent_methods() {
+        let input =
+            format!("{CELL}func main() i32 {{ var mut x = i32::new(1) x.set(9) return x.get() }}");
+        let mir = generate(&input, Emit::Mir);
+
+        assert!(mir.contains("func i32$new(value: i32) i32"));
+        assert!(mir.contains("func i32$get(&self: i32) i32"));
+        assert!(mir.contains("set x: i32 = call i32 i32$new(i32 1)"));
+        assert!(mir.contains("call void i32$set(ptr &x, i32 9)"));
+        assert!(mir.contains("set self: i32 = i32 value"));
+    }
+
+    #[test]
+    fn hir_shows_borrows() {
+        let input = format!("{CELL}func main() i32 {{ var mut x = 1 x.set(2) return x.get() }}");
+        let hir = generate(&input, Emit::Hir);
+
+        assert!(hir.contains("i32::set(&mut x, 2)"));
+        assert!(hir.contains("return i32::get(&x)"));
+        assert!(hir.contains("func set(self &mut i32, value i32) void"));
+    }
+
+    #[test]
+    fn assign_immutable_variable() {
+        failed(
+            "func main() { var x = 1 x = 2 }",
+            "Cannot assign twice to immutable variable 'x'",
+        );
+    }
+
+    #[test]
+    fn assign_immutable_parameter() {
+        failed(
+            "func f(value i32) { value = 2 }",
+            "Cannot assign to immutable parameter 'value'",
+        );
+    }
+
+    #[test]
+    fn assign_through_shared_reference() {
+        failed(
+            "impl i32 { func set(&self) { self = 2 } }",
+            "Cannot assign to 'self', which is behind a '&' reference",
+        );
+    }
+
+    #[test]
+    fn mutable_borrow_of_immutable_variable() {
+        failed(
+            &format!("{CELL}func main() {{ var x = 1 x.set(2) }}"),
+            "Cannot borrow 'x' as mutable, as it is not declared as mutable",
+        );
+    }
+
+    #[test]
+    fn mutable_borrow_behind_shared_reference() {
+        failed(
+            &format!("{CELL}func f(&value i32) {{ value.set(2) }}"),
+            "Cannot borrow 'value' as mutable, as it is behind a '&' reference",
+        );
+    }
+
+    #[test]
+    fn conflicting_borrows() {
+        failed(
+            "impl i32 { func swap(&mut self, &mut other i32) {} }\nfunc main() { var mut x = 1 x.swap(x) }",
+            "Cannot borrow 'x' as mutable more than once at a time",
+        );
+        failed(
+            "impl i32 { func copy(&mut self, &other i32) {} }\nfunc main() { var mut x = 1 x.copy(x) }",
+            "Cannot borrow 'x' as immutable because it is also borrowed as mutable",
+        );
+    }
+
+    #[test]
+    fn mutable_parameters() {
+        generate(
+            &format!(
+                "{CELL}func f(mut value i32, &mut other i32) {{ value = 2 other = 3 other.set(value) }}"
+            ),
+            Emit::Hir,
+        );
+    }
+
+    #[test]
+    fn run_inherent_methods() {
+        run(
+            &format!(
+                "{CELL}func bump(&mut value i32) {{ value.set(value.get()) }}\nfunc main() i32 {{ var mut x = i32::new(1) x.set(9) bump(x) return x.get() }}"
+            ),
+            9,
+        );
+    }
+}
@@ -0,0 +1,188 @@
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use compiler::generate_ir;
+    use context::{Backend, CompilerCtx, files::MockResolver};
+    use diagnostics::Diagnostics;
+    use incremental::entry_path;
+
+    struct Project {
+        compiler: CompilerCtx,
+    }
+    impl Project {
+        fn new(files: &[(&str, &str)]) -> Self {
+            let mut compiler = CompilerCtx::builder()
+                .project_path(PathBuf::new())
+                .resolver(MockResolver::new())
+                .backend(Backend::C)
+                .incremental(true)
+                .build();
+            for (path, source) in files {
+                compiler.write(&PathBuf::from(path), source);
+            }
+            Self { compiler }
+        }
+        fn build(&mut self) -> String {
+            let source = generate_ir(&mut self.compiler);
+            if self.compiler.diagnostics.has_errors() {
+                panic!("{}", self.compiler.diagnostics.render());
+            }
+            source
+        }
+        fn write(&mut self, path: &str, source: &str) {
+            self.compiler.write(&PathBuf::from(path), source);
+        }
+        fn entry(&self, path: &str, kind: &str) -> String {
+            self.compiler
+                .read(&entry_path(Path::new(path), kind))
+                .unwrap()
+        }
+        fn tamper(&mut self, path: &str, kind: &str, from: &str, to: &str) {
+            let entry = self.entry(path, kind);
+            assert!(entry.contains(from));
+            self.compiler
+                .write(&entry_path(Path::new(path), kind), &entry.replace(from, to));
+        }
+    }
+
+    const MAIN: &str = "import foo\nfunc main() i32 { return 7 }";
+
+    #[test]
+    fn analysis_is_reused() {
+        let mut project = Project::new(&[("src/main.ecl", MAIN), ("src/foo.ecl", "")]);
+        assert!(project.build().contains("return 7;"));
+
+        project.tamper("src/main.ecl", "hir", "\"7\"", "\"5\"");
+        assert!(project.build().contains("return 5;"));
+    }
+
+    #[test]
+    fn cached_warnings_are_replayed() {
+        let main = "func main() i32 { return (300 as u8) as i32 }";
+        let mut project = Project::new(&[("src/main.ecl", main)]);
+        project.build();
+        let warning = "Lossy cast of constant '300' to 'u8'";
+        assert!(project.compiler.diagnostics.render().contains(warning));
+
+        project.compiler.diagnostics = Diagnostics::new();
+        project.tamper("src/main.ecl", "hir", "\"300\"", "\"44\"");
+        assert!(project.build().contains("44"));
+        assert!(project.compiler.diagnostics.render().contains(warning));
+    }
+
+    #[test]
+    fn parse_is_reused() {
+        let mut project = Project::new(&[("src/main.ecl", MAIN), ("src/foo.ecl", "")]);
+        project.build();
+
+        project.tamper("src/main.ecl", "ast", "\"7\"", "\"5\"");
+        project.tamper("src/main.ecl", "hir", "\"hash\":", "\"hash\":1");
+        assert!(project.build().contains("return 5;"));
+    }
+
+    #[test]
+    fn source_change_invalidates() {
+        let mut project = Project::new(&[("src/main.ecl", MAIN), ("src/foo.ecl", "")]);
+        project.build();
+
+        project.write("src/main.ecl", "import foo\nfunc main() i32 { return 8 }");
+        assert!(project.build().contains("return 8;"));
+    }
+
+    #[test]
+    fn private_change_keeps_dependents() {
+        let mut project = Project::new(&[
+            ("src/main.ecl", MAIN),
+            ("src/foo.ecl", "pub func f() i32 { return 1 }"),
+        ]);
+        project.build();
+        let main = project.entry("src/main.ecl", "hir");
+        let foo = project.entry("src/foo.ecl", "hir");
+
+        project.write("src/foo.ecl", "pub func f() i32 { return 2 }\nfunc g() {}");
+        project.build();
+        assert_eq!(project.entry("src/main.ecl", "hir"), main);
+        assert_ne!(project.entry("src/foo.ecl", "hir"), foo);
+    }
+
+    #[test]
+    fn signature_change_invalidates_dependents() {
+        let mut project = Project::new(&[
+            ("src/main.ecl", MAIN),
+            ("src/foo.ecl", "pub func f() i32 { return 1 }"),
+        ]);
+        project.build();
+        let main = project.entry("src/main.ecl", "hir");
+
+        project.write("src/foo.ecl", "pub func f(a i32) i32 { return 1 }");
+        project.build();
+        assert_ne!(project.entry("src/main.ecl", "hir"), main);
+    }
+
+    #[test]
+    fn reexported_signature_change_invalidates_dependents() {
+        let mut project = Project::new(&[
+            ("src/main.ecl", MAIN),
+            ("src/foo.ecl", "pub import bar"),
+            ("src/foo/bar.ecl", "pub func f() {}"),
+        ]);
+        project.build();
+        let main = project.entry("src/main.ecl", "hir");
+
+        project.write("src/foo/bar.ecl", "pub func f() {}\npub func g() {}");
+        project.build();
+        assert_ne!(project.entry("src/main.ecl", "hir"), main);
+    }
+
+    #[test]
+    fn impl_change_invalidates_all_modules() {
+        let show = "trait Show { func show(self) i32 }\n";
+        let mut project = Project::new(&[
+            ("src/main.ecl", MAIN),
+            (
+                "src/foo.ecl",
+                &format!("{show}impl Show for i32 {{ func show(self) i32 {{ return 1 }} }}"),
+            ),
+        ]);
+        project.build();
+        let main = project.entry("src/main.ecl", "hir");
+
+        project.write(
+            "src/foo.ecl",
+            &format!("{show}impl Show for i32 {{ func show(self) i32 {{ return 2 }} }}"),
+        );
+        project.build();
+        assert_eq!(project.entry("src/main.ecl", "hir"), main);
+
+        project.write(
+            "src/foo.ecl",
+            &format!(
+                "{show}impl Show for i32 {{ func show(self) i32 {{ return 2 }} }}\nimpl Show for bool {{ func show(self) i32 {{ return 3 }} }}"
+            ),
+        );
+        project.build();
+        assert_ne!(project.entry("src/main.ecl", "hir"), main);
+    }
+
+    #[test]
+    fn build_options_invalidate() {
+        let mut project = Project::new(&[("src/main.ecl", MAIN), ("src/foo.ecl", "")]);
+        project.build();
+        let mut main = project.entry("src/main.ecl", "hir");
+
+        for config in [
+            "[package]\nname = \"Other\"",
+            "[package]\nname = \"Other\"\n[build]\nopt-level = 2",
+            "[package]\nname = \"Other\"\n[build]\nopt-level = 2\ntarget = \"x86\"",
+        ] {
+            project.write("eclipse.toml", config);
+            project.compiler.load_config();
+            project.build();
+
+            let entry = project.entry("src/main.ecl", "hir");
+            assert_ne!(entry, main, "{config}");
+            main = entry;
+        }
+    }
+}
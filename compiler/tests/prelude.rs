@@ -0,0 +1,109 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use compiler::Emit;
+
+    use crate::common::{MAIN, execute, failed, generate};
+
+    fn run(input: &str, stdin: &str, code: i32, stdout: &str) {
+        let output = execute(&[(MAIN, input)], stdin);
+        assert_eq!(output.status.code(), Some(code), "INPUT: {input}");
+        assert_eq!(String::from_utf8_lossy(&output.stdout), stdout);
+    }
+
+    #[test]
+    fn unused_library_functions() {
+        let mir = generate("func main() { println(\"hi\") }", Emit::Mir);
+
+        assert!(mir.contains("func std$io$println(text: { ptr, u64 }) void"));
+        assert!(!mir.contains("func std$io$print(text"));
+        assert!(!mir.contains("func std$math$clamp("));
+        assert!(!mir.contains("func $read_line()"));
+    }
+
+    #[test]
+    fn shadowed_library_function() {
+        let mir = generate(
+            "func max(a i32) i32 { return a }\nfunc main() i32 { return max(1) }",
+            Emit::Mir,
+        );
+
+        assert!(mir.contains("func Test$main$max(a: i32) i32"));
+        assert!(mir.contains("return call i32 Test$main$max(i32 1)"));
+        assert!(!mir.contains("func std$math$max("));
+    }
+
+    #[test]
+    fn intrinsics() {
+        failed(
+            "func main() { intrinsics::print(\"a\") }",
+            "Intrinsic 'intrinsics::print' can only be used in the standard library",
+        );
+        failed(
+            "func main() { intrinsics::exit(1) }",
+            "Intrinsic 'intrinsics::exit' can only be used in the standard library",
+        );
+    }
+
+    #[test]
+    fn run_math() {
+        run(
+            "func main() i32 {
+    var a = abs(0 - 3) + abs(4)
+    var b = min(4, 9) + max(2, 0 - 8)
+    var c = clamp(50, 1, 10) + clamp(0 - 5, 1, 10) + clamp(5, 1, 10)
+    var d = sign(0 - 7) + sign(0) + sign(12)
+    return a + b + c + d + std::math::abs(0 - 1)
+}",
+            "",
+            30,
+            "",
+        );
+    }
+
+    #[test]
+    fn run_math_extremes() {
+        run(
+            "func main() i32 {
+    var big = 2147483647
+    var small = 0 - big - 1
+    var a = min(big, 0 - 1) + max(small, 1) + sign(small) + sign(big)
+    var b = clamp(big, 0, 3) + clamp(small, 0, 3)
+    return a + b
+}",
+            "",
+            3,
+            "",
+        );
+    }
+
+    #[test]
+    fn run_library_beside_user_function() {
+        run(
+            "func max(a i32) i32 { return 0 - a }\nfunc main() i32 { return clamp(9, 1, 4) + max(1) }",
+            "",
+            3,
+            "",
+        );
+    }
+
+    #[test]
+    fn run_read_line() {
+        run(
+            "func main() i32 {
+    var first = read_line()
+    var second = read_line()
+    var third = read_line()
+    var greeting = join(\"hello\", \", \", first)
+    print(greeting)
+    print(second)
+    var empty = is_empty(third)
+    return 0
+}",
+            "bob\nalice",
+            0,
+            "hello, bob\nalice",
+        );
+    }
+}
@@ -0,0 +1,195 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use analyzer::{Signatures, Typeck};
+    use compiler::check;
+    use context::{CompilerCtx, Query, Source, files::MockResolver};
+    use diagnostics::Diagnostics;
+    use lowering::Mir;
+    use resolver::Parse;
+
+    const MAIN: &str = "src/main.ecl";
+
+    fn init(source: &str) -> CompilerCtx {
+        let mut compiler = CompilerCtx::builder()
+            .project_path(PathBuf::new())
+            .resolver(MockResolver::new())
+            .build();
+        compiler.write(&PathBuf::from(MAIN), source);
+        compiler
+    }
+
+    fn key(name: &str) -> (PathBuf, String) {
+        (PathBuf::from(MAIN), name.to_string())
+    }
+
+    fn mir(compiler: &mut CompilerCtx, name: &str) -> String {
+        let function = compiler.query::<Mir>(key(name));
+        function.as_ref().as_ref().unwrap().to_string()
+    }
+
+    #[test]
+    fn memoized() {
+        let mut compiler = init("func main() i32 { return 7 }");
+        assert!(mir(&mut compiler, "main").contains("return i32 7"));
+        assert!(compiler.executed::<Mir>(&key("main")));
+        assert!(compiler.executed::<Parse>(&PathBuf::from(MAIN)));
+
+        compiler.take_executed();
+        mir(&mut compiler, "main");
+        assert!(compiler.take_executed().is_empty());
+    }
+
+    #[test]
+    fn edit_rechecks_one_function() {
+        let mut compiler = init("func f() i32 { return 1 }\nfunc g() i32 { return 2 }");
+        mir(&mut compiler, "f");
+        mir(&mut compiler, "g");
+        compiler.take_executed();
+
+        compiler.write(
+            &PathBuf::from(MAIN),
+            "func f() i32 { return 1 }\nfunc g() i32 { return 3 }",
+        );
+        mir(&mut compiler, "f");
+        assert!(mir(&mut compiler, "g").contains("return i32 3"));
+
+        assert!(compiler.executed::<Parse>(&PathBuf::from(MAIN)));
+        assert!(!compiler.executed::<Typeck>(&key("f")));
+        assert!(!compiler.executed::<Mir>(&key("f")));
+        assert!(compiler.executed::<Typeck>(&key("g")));
+    }
+
+    #[test]
+    fn unchanged_source_is_cut_off() {
+        let mut compiler = init("func main() {}");
+        mir(&mut compiler, "main");
+        compiler.take_executed();
+
+        compiler.write(&PathBuf::from(MAIN), "func main() {}");
+        mir(&mut compiler, "main");
+        assert!(compiler.executed::<Source>(&PathBuf::from(MAIN)));
+        assert!(!compiler.executed::<Parse>(&PathBuf::from(MAIN)));
+    }
+
+    #[test]
+    fn moved_function_is_rechecked() {
+        let mut compiler = init("func f() bool { return 0 }");
+        let typed = compiler.query::<Typeck>(key("f"));
+        assert_eq!(typed.diagnostics.len(), 1);
+        let before = format!("{:?}", typed.diagnostics);
+
+        compiler.write(&PathBuf::from(MAIN), "\nfunc f() bool { return 0 }");
+        let typed = compiler.query::<Typeck>(key("f"));
+        assert_ne!(format!("{:?}", typed.diagnostics), before);
+    }
+
+    #[test]
+    fn signatures() {
+        let mut compiler = init("func f(a i32) bool { return true }\nextern \"C\" func g() {}");
+        let signatures = compiler.query::<Signatures>(PathBuf::from(MAIN));
+        let signatures: Vec<String> = signatures.iter().map(|s| s.to_string()).collect();
+        assert_eq!(signatures, vec!["func f(i32) bool", "extern func g() void"]);
+
+        compiler.take_executed();
+        compiler.write(
+            &PathBuf::from(MAIN),
+            "func f(a i32) bool { return false }\nextern \"C\" func g() {}",
+        );
+        compiler.query::<Signatures>(PathBuf::from(MAIN));
+        assert!(compiler.executed::<Signatures>(&PathBuf::from(MAIN)));
+    }
+
+    #[test]
+    fn callers_depend_on_signatures() {
+        let mut compiler =
+            init("func main() i32 { return id(1) }\nfunc id<T>(value T) T { return value }");
+        let main = mir(&mut compiler, "main");
+        assert!(main.contains("call i32 Test$main$id$i32(i32 1)"));
+        assert!(main.contains("internal func Test$main$id$i32(value: i32) i32"));
+        compiler.take_executed();
+
+        compiler.write(
+            &PathBuf::from(MAIN),
+            "func main() i32 { return id(1) }\nfunc id<T>(other T) T { return other }",
+        );
+        mir(&mut compiler, "main");
+        assert!(compiler.executed::<Signatures>(&PathBuf::from(MAIN)));
+        assert!(!compiler.executed::<Typeck>(&key("main")));
+
+        compiler.write(
+            &PathBuf::from(MAIN),
+            "func main() i32 { return id(1) }\nfunc id<T>(other T, b bool) T { return other }",
+        );
+        let typed = compiler.query::<Typeck>(key("main"));
+        assert_eq!(typed.diagnostics.len(), 1);
+    }
+
+    #[test]
+    fn missing_function() {
+        let mut compiler = init("func main() {}");
+        let typed = compiler.query::<Typeck>(key("other"));
+        assert!(typed.function.is_none());
+        assert_eq!(typed.diagnostics.len(), 1);
+    }
+
+    #[test]
+    fn resolved_calls() {
+        let mut compiler = init("import foo\nfunc main() { println(\"hi\") foo::x() }");
+        compiler.write(&PathBuf::from("src/foo.ecl"), "pub func x() {}");
+
+        let typed = compiler.query::<Typeck>(key("main"));
+        assert!(typed.diagnostics.is_empty(), "{:?}", typed.diagnostics);
+
+        let main = mir(&mut compiler, "main");
+        assert!(main.contains("call void std$io$println("));
+        assert!(main.contains("call void Test$foo$x()"));
+        assert!(main.contains("internal func std$io$println("));
+        assert!(main.contains("const $str0 = \"hi\""));
+    }
+
+    #[test]
+    fn check_project() {
+        let mut compiler = init("func f() bool { return 0 }\nfunc g() i32 { return h() }");
+        check(&mut compiler);
+        let rendered = compiler.diagnostics.render();
+        assert!(rendered.contains(MAIN));
+        assert!(rendered.contains("'h' not found in this scope"));
+
+        compiler.diagnostics = Diagnostics::new();
+        compiler.take_executed();
+        compiler.write(
+            &PathBuf::from(MAIN),
+            "func f() bool { return 0 }\nfunc g() i32 { return 1 }",
+        );
+        check(&mut compiler);
+        assert!(!compiler.diagnostics.render().contains("'h'"));
+        assert!(compiler.diagnostics.has_errors());
+        assert!(!compiler.executed::<Typeck>(&key("f")));
+        assert!(compiler.executed::<Typeck>(&key("g")));
+    }
+
+    struct Cyclic;
+    impl Query for Cyclic {
+        type Key = u8;
+        type Value = ();
+
+        fn execute(compiler: &mut CompilerCtx, key: &u8) {
+            compiler.query::<Cyclic>((key + 1) % 2);
+        }
+
+        fn cycle(_: &u8) {}
+    }
+
+    #[test]
+    fn query_cycle() {
+        let mut compiler = init("");
+        compiler.query::<Cyclic>(0);
+
+        let rendered = compiler.diagnostics.render();
+        assert!(rendered.contains("Query cycle detected: "), "{rendered}");
+        assert!(rendered.contains("Cyclic(0) -> "), "{rendered}");
+        assert!(rendered.contains("Cyclic(1) -> "), "{rendered}");
+    }
+}
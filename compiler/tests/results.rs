@@ -0,0 +1,139 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use compiler::Emit;
+
+    use crate::common::{failed, generate, run_stdout};
+
+    #[test]
+    fn result_layout() {
+        let mir = generate(
+            "func f(a i32) result<i32, bool> { return Ok(a) }\nfunc main() { var r = f(1) }",
+            Emit::Mir,
+        );
+
+        assert!(mir.contains("type %result$i32$bool = { bool, i32, bool }"));
+        assert!(mir.contains("func Test$main$f(a: i32) %result$i32$bool"));
+        assert!(mir.contains("store bool bool true -> field result$i32$bool.0 ptr &$value0"));
+        assert!(mir.contains("store i32 i32 a -> field result$i32$bool.1 ptr &$value0"));
+    }
+
+    #[test]
+    fn early_return_drops() {
+        let mir = generate(
+            "func f() result<i32, bool> { return Err(true) }
+func g() result<i32, bool> {
+    var s = String::new()
+    var a = f()?
+    return Ok(a)
+}
+func main() { var r = g() }",
+            Emit::Mir,
+        );
+
+        assert!(mir.contains("branch extract 0 %result$i32$bool $value0, bb2, bb1"));
+        assert!(mir.contains(
+            "store bool extract 2 %result$i32$bool $value0 -> field result$i32$bool.2 ptr &$value1"
+        ));
+        assert_eq!(mir.matches("call void $drop$String(ptr s)").count(), 2);
+        assert!(mir.contains("set a: i32 = extract 1 %result$i32$bool $value0"));
+    }
+
+    #[test]
+    fn run_propagation() {
+        run_stdout(
+            "func half(value i32) result<i32, i32> {
+    return Ok(value / 2)
+}
+func fail(code i32) result<i32, i32> {
+    return Err(code)
+}
+func run() result<i32, i32> {
+    var s = String::from(\"kept\")
+    var a = half(8)?
+    println(s)
+    {
+        var t = String::from(\"inner\")
+        fail(a)?
+        println(t)
+    }
+    println(\"unreachable\")
+    return Ok(a)
+}
+func main() i32 {
+    var r = run()
+    println(\"done\")
+    return 0
+}",
+            0,
+            "kept\ndone\n",
+        );
+    }
+
+    #[test]
+    fn owned_payload_drops() {
+        let mir = generate(
+            "func read() result<String, Box<i32>> { return Ok(String::from(\"text\")) }
+func length() result<usize, Box<i32>> {
+    var s = read()?
+    read()?
+    return Ok(s.len())
+}
+func main() { var r = read() var l = length() }",
+            Emit::Mir,
+        );
+
+        assert!(mir.contains("type %result$String$Box$i32 = { bool, ptr, ptr }"));
+        assert!(mir.contains("internal func $drop$result$String$Box$i32(value: ptr) void"));
+        assert!(mir.contains("branch load bool field result$String$Box$i32.0 ptr value, bb2, bb1"));
+        assert!(
+            mir.contains(
+                "call void $drop$Box$i32(load ptr field result$String$Box$i32.2 ptr value)"
+            )
+        );
+        assert!(
+            mir.contains(
+                "call void $drop$String(load ptr field result$String$Box$i32.1 ptr value)"
+            )
+        );
+        assert!(mir.contains("call void $drop$result$String$Box$i32(ptr &r)"));
+        assert!(mir.contains("call void $drop$result$usize$Box$i32(ptr &l)"));
+        assert!(mir.contains("call void $drop$String(ptr s)"));
+        assert!(mir.contains(
+            "set $value4: ptr = extract 1 %result$String$Box$i32 $value2\n    call void $drop$String(ptr $value4)"
+        ));
+    }
+
+    #[test]
+    fn moved_result() {
+        failed(
+            "func f() result<String, bool> { return Err(true) }\nfunc g() result<String, bool> { var r = f() var s = r return r }",
+            "Use of moved value 'r'",
+        );
+    }
+
+    #[test]
+    fn run_owned_payloads() {
+        run_stdout(
+            "func read() result<String, Box<i32>> { return Ok(String::from(\"text\")) }
+func fail() result<String, Box<i32>> { return Err(Box::new(7)) }
+func both() result<String, Box<i32>> {
+    var a = read()?
+    println(a)
+    var b = fail()?
+    println(\"unreachable\")
+    return Ok(b)
+}
+func main() i32 {
+    var first = both()
+    var second = read()
+    fail()
+    println(\"done\")
+    return 0
+}",
+            0,
+            "text\ndone\n",
+        );
+    }
+}
@@ -0,0 +1,75 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use compiler::Emit;
+
+    use crate::common::{failed, generate, run};
+
+    #[test]
+    fn unique_locals() {
+        let mir = generate(
+            "func main() i32 { var x = 1 var x = x + 2 { var x = 5 } return x }",
+            Emit::Mir,
+        );
+
+        assert!(mir.contains("set x: i32 = i32 1"));
+        assert!(mir.contains("set x$1: i32 = (i32 x + i32 2)"));
+        assert!(mir.contains("set x$2: i32 = i32 5"));
+        assert!(mir.contains("return i32 x$1"));
+    }
+
+    #[test]
+    fn diagnostics_use_source_names() {
+        failed(
+            "func main() { var mut s = String::new() var s = String::new() s.push(\"a\") }",
+            "Cannot borrow 's' as mutable, as it is not declared as mutable",
+        );
+    }
+
+    #[test]
+    fn run_same_scope() {
+        run("func main() i32 { var x = 1 var x = x + 2 return x }", 3);
+    }
+
+    #[test]
+    fn run_nested_block() {
+        run("func main() i32 { var x = 1 { var x = 5 } return x }", 1);
+        run(
+            "func main() i32 { var x = 1 { var x = x + 5 var y = x } return x }",
+            1,
+        );
+    }
+
+    #[test]
+    fn run_shadowed_parameter() {
+        run(
+            "func double(a i32) i32 { var a = a * 2 return a }\nfunc main() i32 { return double(4) }",
+            8,
+        );
+    }
+
+    #[test]
+    fn run_shadowed_boxes() {
+        run(
+            "func main() i32 { var b = Box::new(1) var b = Box::new(*b + 1) { var b = Box::new(7) } return *b }",
+            2,
+        );
+    }
+
+    #[test]
+    fn run_shadowed_receiver() {
+        run(
+            "func main() i32 { var mut s = \"ab\".concat(\"c\") var mut s = \"d\".concat(\"\") s.push(\"e\") return s.len() as i32 }",
+            2,
+        );
+    }
+
+    #[test]
+    fn run_shadowed_capture() {
+        run(
+            "func apply(f func(i32) i32) i32 { return f(1) }\nfunc main() i32 { var y = 2 var y = y * 10 return apply(|x| { var y = x + y return y }) }",
+            21,
+        );
+    }
+}
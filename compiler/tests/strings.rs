@@ -0,0 +1,177 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use compiler::Emit;
+
+    use crate::common::{failed, generate, position, run_stdout};
+
+    #[test]
+    fn literal_constants() {
+        let mir = generate(
+            "func main() { print(\"hi\") print(\"a\\tb\") print(\"hi\") }",
+            Emit::Mir,
+        );
+
+        assert!(mir.contains("const $str0 = \"hi\""));
+        assert!(mir.contains("const $str1 = \"a\\tb\""));
+        assert!(!mir.contains("$str2"));
+        assert!(mir.contains("call void std$io$print(str $str1 u64 3)"));
+        assert!(mir.contains("call void $print({ ptr, u64 } text)"));
+        assert!(mir.contains("declare func write(descriptor: i32, data: ptr, size: u64) i64"));
+    }
+
+    #[test]
+    fn string_runtime() {
+        let mir = generate(
+            "func main() { var mut s = String::from(\"a\") s.push(\"b\") }",
+            Emit::Mir,
+        );
+
+        assert!(mir.contains("func $string$from(text: { ptr, u64 }) ptr"));
+        assert!(mir.contains("func $string$push(string: ptr, text: { ptr, u64 }) void"));
+        assert!(mir.contains("set s: ptr = call ptr $string$from(str $str0 u64 1)"));
+        assert!(mir.contains("call void $string$push(load ptr ptr &s, str $str1 u64 1)"));
+        assert!(!mir.contains("$string$concat"));
+    }
+
+    #[test]
+    fn string_views() {
+        let mir = generate(
+            "func f(text str) {}\nfunc main() { var s = String::new() f(s) var n = s.len() }",
+            Emit::Mir,
+        );
+
+        assert!(mir.contains("call void Test$main$f(load { ptr, u64 } load ptr ptr &s)"));
+        assert!(mir.contains("set n: u64 = extract 1 load { ptr, u64 } load ptr ptr &s"));
+        assert!(mir.contains("call void $drop$String(ptr s)"));
+    }
+
+    #[test]
+    fn string_drop_glue() {
+        let mir = generate("func main() { var s = String::new() }", Emit::Mir);
+
+        let data = position(
+            &mir,
+            "call void free(extract 0 load { ptr, u64 } ptr value)",
+        );
+        assert!(data < position(&mir, "call void free(ptr value)"));
+        assert!(mir.contains("call void $drop$String(ptr s)"));
+    }
+
+    #[test]
+    fn temporary_strings_are_dropped() {
+        let mir = generate("func main() { \"a\".concat(\"b\") }", Emit::Mir);
+
+        assert!(mir.contains(
+            "set $value0: ptr = call ptr $string$concat(str $str0 u64 1, str $str1 u64 1)"
+        ));
+        assert!(mir.contains("call void $drop$String(ptr $value0)"));
+    }
+
+    #[test]
+    fn c_output() {
+        let c = generate("func main() { println(\"hi\") }", Emit::C);
+
+        assert!(c.contains("struct slice64 { void* f0; uint64_t f1; };"));
+        assert!(c.contains("static const uint8_t E_Sstr0[] = { 104, 105, 0 };"));
+        assert!(c.contains("    Estd_Sio_Sprintln((struct slice64){ (void*)E_Sstr0, 2 });"));
+        assert!(c.contains("    E_Sprintln(text);"));
+    }
+
+    #[test]
+    fn invalid_borrows() {
+        failed(
+            "func main() { var mut s = String::new() s.push(s) }",
+            "Cannot borrow 's' as immutable because it is also borrowed as mutable",
+        );
+        failed(
+            "func main() { var s = String::new() s.push(\"a\") }",
+            "Cannot borrow 's' as mutable, as it is not declared as mutable",
+        );
+        failed(
+            "func main() { var s = String::new() var t = s println(s) }",
+            "Borrow of moved value 's'",
+        );
+        failed(
+            "func main() { var s = String::new() var f = move || s.len() }",
+            "Cannot move 's' of type 'String' into a closure",
+        );
+    }
+
+    #[test]
+    fn invalid_strings() {
+        failed(
+            "func main() { var s = \"a\" s.push(\"b\") }",
+            "No method 'push' found for type 'str'",
+        );
+        failed(
+            "func main() { println(String::new()) }",
+            "Cannot use a temporary 'String' as 'str', bind it to a variable first",
+        );
+        failed(
+            "func main() { var n = String::from(\"a\").len() }",
+            "Cannot call 'len' on a temporary 'String', bind the value to a variable first",
+        );
+        failed(
+            "func main() { print(\"a\", \"b\") }",
+            "Function 'print' expects 1 argument(s), got 2",
+        );
+        failed(
+            "func main() { var s = \"\\q\" }",
+            "Unknown escape sequence '\\q'",
+        );
+    }
+
+    #[test]
+    fn run_strings() {
+        run_stdout(
+            "func greet(name str) String {
+    var mut s = String::from(\"Hello, \")
+    s.push(name)
+    s.push(\"!\")
+    return s
+}
+func main() i32 {
+    var g = greet(\"world\")
+    println(g)
+    var mut t = String::new()
+    t.push(\"a\\tb\")
+    print(t)
+    print(\"\\n\")
+    var c = \"abc\".concat(\"de\")
+    println(c)
+    \"dropped\".concat(\"\")
+    return 3
+}",
+            3,
+            "Hello, world!\na\tb\nabcde\n",
+        );
+    }
+
+    #[test]
+    fn reserved_runtime_names() {
+        failed(
+            "extern \"C\" func write(value i32) {}\nfunc main() {}",
+            "'write' is reserved by the runtime and cannot be declared as an extern function",
+        );
+    }
+
+    #[test]
+    fn run_user_functions_named_like_the_runtime() {
+        run_stdout(
+            "func write(value i32) i32 { return value }
+func malloc() i32 { return 1 }
+func free(value i32) i32 { return value + 1 }
+func exit() i32 { return 2 }
+func main() i32 {
+    var mut s = String::from(\"a\")
+    s.push(\"b\")
+    println(s)
+    return write(1) + malloc() + free(1) + exit()
+}",
+            6,
+            "ab\n",
+        );
+    }
+}
@@ -0,0 +1,190 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use compiler::{Emit, check};
+
+    use crate::common::{MAIN, execute, failed, generate, mock, run};
+
+    const POINT: &str = "struct Point { x i32, y i32 }
+impl Point {
+    func new(x i32, y i32) Self { return Point { x: x, y: y } }
+    func sum(&self) i32 { return self.x + self.y }
+    func scale(self, factor i32) Point { return Point { x: self.x * factor, y: self.y * factor } }
+}
+";
+
+    #[test]
+    fn declarations() {
+        let input = format!("{POINT}func main() i32 {{ return Point::new(1, 2).x }}");
+        let hir = generate(&input, Emit::Hir);
+        let mir = generate(&input, Emit::Mir);
+
+        assert!(hir.contains("struct Point {\n    x i32\n    y i32\n}"));
+        assert!(hir.contains("return Point { x: x, y: y }"));
+        assert!(hir.contains("func sum(self &Point) i32"));
+        assert!(mir.contains("type %Test$main$Point = { i32, i32 }"));
+        assert!(mir.contains("func Test$main$Point$sum(&self: %Test$main$Point) i32"));
+        assert!(mir.contains("store i32 i32 x -> field Test$main$Point.0 ptr &$value0"));
+        assert!(mir.contains("return extract 0 %Test$main$Point $value0"));
+    }
+
+    #[test]
+    fn run_inherent_impl() {
+        run(
+            &format!(
+                "{POINT}func main() i32 {{ var p = Point::new(2, 3) var q = p.scale(4) return q.sum() + p.x }}"
+            ),
+            22,
+        );
+    }
+
+    #[test]
+    fn run_nested_structs() {
+        run(
+            "struct Point { x i32, y i32 }
+struct Line { from Point, to Point }
+func length(line Line) i32 { return line.to.x - line.from.x + line.to.y - line.from.y }
+func main() i32 {
+    var line = Line { from: Point { x: 1, y: 2 }, to: Point { y: 9, x: 5 } }
+    return length(line)
+}",
+            11,
+        );
+    }
+
+    #[test]
+    fn run_boxed_structs() {
+        run(
+            "struct Pair { flag bool, value i64 }
+func main() i32 { var p = Box::new(Pair { flag: true, value: 9 }) var q = *p return q.value as i32 }",
+            9,
+        );
+    }
+
+    #[test]
+    fn run_module_structs() {
+        let output = execute(
+            &[
+                (
+                    MAIN,
+                    "import geometry\nuse geometry::Point\nfunc main() i32 { var p = Point { x: 4, y: 5 } return p.area() }",
+                ),
+                (
+                    "src/geometry.ecl",
+                    "pub struct Point { x i32, y i32 }\nimpl Point { func area(&self) i32 { return self.x * self.y } }",
+                ),
+            ],
+            "",
+        );
+        assert_eq!(output.status.code(), Some(20));
+    }
+
+    #[test]
+    fn unknown_field() {
+        failed(
+            "struct P { x i32 }\nfunc main() { var p = P { x: 1, y: 2 } }",
+            "Struct 'P' has no field 'y'",
+        );
+    }
+
+    #[test]
+    fn missing_fields() {
+        failed(
+            "struct P { x i32, y i32, z i32 }\nfunc main() { var p = P { y: 1 } }",
+            "Missing field(s) in initializer of struct 'P': 'x', 'z'",
+        );
+    }
+
+    #[test]
+    fn repeated_field() {
+        failed(
+            "struct P { x i32 }\nfunc main() { var p = P { x: 1, x: 2 } }",
+            "Field 'x' is specified multiple times",
+        );
+    }
+
+    #[test]
+    fn no_field() {
+        failed(
+            "struct P { x i32 }\nfunc main() { var p = P { x: 1 } var z = p.z }",
+            "No field 'z' found for type 'P'",
+        );
+        failed(
+            "func main() { var x = 1 var y = x.y }",
+            "No field 'y' found for type 'i32'",
+        );
+    }
+
+    #[test]
+    fn field_types() {
+        failed(
+            "struct P { x i32 }\nfunc main() { var p = P { x: true } }",
+            "Expected type: i32, got: bool",
+        );
+        failed(
+            "struct P { x String }",
+            "Field 'x' of struct 'P' cannot have type 'String', fields must be numbers, booleans or structs",
+        );
+        failed(
+            "struct P { x Box<i32> }",
+            "Field 'x' of struct 'P' cannot have type 'Box<i32>', fields must be numbers, booleans or structs",
+        );
+    }
+
+    #[test]
+    fn recursive_structs() {
+        failed(
+            "struct P { p P }",
+            "Struct 'P' contains itself through field 'p', it would have an infinite size",
+        );
+        failed(
+            "struct P { q Q }\nstruct Q { x i32, p P }",
+            "Struct 'Q' contains itself through field 'p', it would have an infinite size",
+        );
+    }
+
+    #[test]
+    fn invalid_declarations() {
+        failed("struct P {}", "Struct 'P' must have at least one field");
+        failed(
+            "struct P { x i32, x i32 }",
+            "Field 'x' is defined multiple times in struct 'P'",
+        );
+        failed(
+            "struct P { x i32 }\nstruct P { y i32 }",
+            "'P' is defined multiple times",
+        );
+    }
+
+    #[test]
+    fn type_names() {
+        failed("func f(a Missing) {}", "'Missing' not found in this scope");
+        failed("func g() {}\nfunc f(a g) {}", "'g' is not a type");
+    }
+
+    #[test]
+    fn check_structs() {
+        let mut compiler = mock().build();
+        compiler.write(
+            &MAIN.into(),
+            &format!("{POINT}func main() i32 {{ var p = Point::new(1, 2) return p.sum() }}"),
+        );
+        check(&mut compiler);
+        assert!(
+            !compiler.diagnostics.has_errors(),
+            "{}",
+            compiler.diagnostics.render()
+        );
+
+        let mut compiler = mock().build();
+        compiler.write(
+            &MAIN.into(),
+            "struct P { p P }\nstruct Q { x String }\nfunc main() i32 { return 0 }",
+        );
+        check(&mut compiler);
+        let rendered = compiler.diagnostics.render();
+        assert!(rendered.contains("contains itself through field 'p'"));
+        assert!(rendered.contains("Field 'x' of struct 'Q' cannot have type 'String'"));
+    }
+}
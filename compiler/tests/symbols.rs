@@ -0,0 +1,103 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use compiler::{Emit, generate_ir};
+
+    use crate::common::{emitted, execute, mock};
+
+    fn generate(files: &[(&str, &str)]) -> String {
+        let mut compiler = mock().emit(vec![Emit::Mir]).build();
+        for (path, source) in files {
+            compiler.write(&PathBuf::from(path), source);
+        }
+
+        generate_ir(&mut compiler);
+        emitted(&compiler, Emit::Mir)
+    }
+
+    fn run(files: &[(&str, &str)], code: i32) {
+        let output = execute(files, "");
+        assert_eq!(output.status.code(), Some(code), "FILES: {files:#?}");
+    }
+
+    const MODULES: [(&str, &str); 2] = [
+        (
+            "src/main.ecl",
+            "import foo\nfunc helper() i32 { return 1 }\nfunc main() i32 { return helper() + foo::helper() }",
+        ),
+        ("src/foo.ecl", "pub func helper() i32 { return 2 }"),
+    ];
+
+    #[test]
+    fn module_paths() {
+        let mir = generate(&MODULES);
+
+        assert!(mir.contains("func main() i32"));
+        assert!(mir.contains("func Test$main$helper() i32"));
+        assert!(mir.contains("func Test$foo$helper() i32"));
+        assert!(mir.contains("call i32 Test$main$helper()"));
+        assert!(mir.contains("call i32 Test$foo$helper()"));
+    }
+
+    #[test]
+    fn nested_modules() {
+        let mir = generate(&[
+            (
+                "src/main.ecl",
+                "import a\nfunc main() i32 { return a::b::f() }",
+            ),
+            ("src/a/mod.ecl", "pub import b"),
+            ("src/a/b.ecl", "pub func f() i32 { return 1 }"),
+        ]);
+
+        assert!(mir.contains("func Test$a$b$f() i32"));
+    }
+
+    #[test]
+    fn extern_functions_keep_their_name() {
+        let mir = generate(&[(
+            "src/main.ecl",
+            "extern \"C\" func abs(value i32) i32 {}\nfunc main() i32 { return abs(0 - 3) }",
+        )]);
+
+        assert!(mir.contains("declare func abs(value: i32) i32"));
+        assert!(mir.contains("call i32 abs("));
+    }
+
+    const DEPENDENCY: [(&str, &str); 3] = [
+        (
+            "eclipse.toml",
+            "[package]\nname = \"app\"\n\n[dependencies]\nmath = { path = \"deps/math\" }",
+        ),
+        (
+            "src/main.ecl",
+            "func helper() i32 { return 1 }\nfunc main() i32 { return helper() + math::helper() }",
+        ),
+        (
+            "deps/math/src/lib.ecl",
+            "pub func helper() i32 { return 4 }",
+        ),
+    ];
+
+    #[test]
+    fn crate_names() {
+        let mir = generate(&DEPENDENCY);
+
+        assert!(mir.contains("func app$main$helper() i32"));
+        assert!(mir.contains("func math$lib$helper() i32"));
+        assert!(mir.contains("call i32 math$lib$helper()"));
+    }
+
+    #[test]
+    fn run_module_paths() {
+        run(&MODULES, 3);
+    }
+
+    #[test]
+    fn run_crate_names() {
+        run(&DEPENDENCY, 5);
+    }
+}
@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use compiler::generate_ir;
+    use context::{Arch, CompilerCtx, files::MockResolver};
+
+    fn init(config: Option<&str>, target: Option<Arch>, input: &str) -> (CompilerCtx, String) {
+        let mut compiler = CompilerCtx::builder()
+            .project_path(PathBuf::new())
+            .resolver(MockResolver::new())
+            .target(target)
+            .build();
+
+        compiler.write(&CompilerCtx::entry(), input);
+        if let Some(config) = config {
+            compiler.write(&PathBuf::from("eclipse.toml"), config);
+        }
+
+        let source = generate_ir(&mut compiler);
+        (compiler, source)
+    }
+
+    fn emitted(config: Option<&str>, target: Option<Arch>, input: &str) -> String {
+        let (compiler, source) = init(config, target, input);
+        if compiler.diagnostics.has_errors() {
+            compiler.diagnostics.display();
+            panic!("Expected to not fail\nINPUT:\n{input:#?}")
+        }
+        source
+    }
+
+    fn assert_contains(source: &str, expected: &str) {
+        assert!(
+            source.contains(expected),
+            "Expected {expected:?} in:\n{source}"
+        );
+    }
+
+    #[test]
+    fn target_option() {
+        let source = emitted(None, Some(Arch::arm_64), "func main() i32 { return 0 }");
+        assert_contains(&source, "target triple = \"aarch64-unknown-linux-gnu\"");
+        assert_contains(
+            &source,
+            "target datalayout = \"e-m:e-i8:8:32-i16:16:32-i64:64-i128:128-n32:64-S128\"",
+        );
+    }
+
+    #[test]
+    fn target_config() {
+        let config = "[build]\ntarget = \"x86\"";
+        let source = emitted(Some(config), None, "func main() i32 { return 0 }");
+        assert_contains(&source, "target triple = \"i686-unknown-linux-gnu\"");
+    }
+
+    #[test]
+    fn target_option_overrides_config() {
+        let config = "[build]\ntarget = \"x86\"";
+        let source = emitted(
+            Some(config),
+            Some(Arch::arm),
+            "func main() i32 { return 0 }",
+        );
+        assert_contains(&source, "target triple = \"armv7-unknown-linux-gnueabihf\"");
+    }
+
+    #[test]
+    fn pointer_sized_integers() {
+        let input = "func main() i32 { var x: usize = 1 var y: isize = 2 return 0 }";
+
+        let source = emitted(None, Some(Arch::x86_64), input);
+        assert_contains(&source, "%x = alloca i64");
+        assert_contains(&source, "store i64 2, ptr %y");
+
+        let source = emitted(None, Some(Arch::arm), input);
+        assert_contains(&source, "%x = alloca i32");
+        assert_contains(&source, "store i32 2, ptr %y");
+    }
+
+    #[test]
+    fn unknown_target_in_config() {
+        let config = "[build]\ntarget = \"sparc\"";
+        let (compiler, _) = init(Some(config), None, "func main() i32 { return 0 }");
+        assert!(compiler.diagnostics.has_errors());
+        assert!(
+            compiler
+                .diagnostics
+                .render()
+                .contains("unknown target 'sparc'")
+        );
+    }
+}
@@ -0,0 +1,74 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use compiler::Emit;
+
+    use crate::common::{generate, run};
+
+    const SHOW: &str = "trait Show { func show(self) i32 }
+impl Show for i32 { func show(self) i32 { return self } }
+impl Show for bool { func show(self) i32 { return 1 } }
+";
+
+    #[test]
+    fn static_dispatch() {
+        let input = format!("{SHOW}func main() i32 {{ var a = true.show() return 4.show() }}");
+        let mir = generate(&input, Emit::Mir);
+
+        assert!(mir.contains("func i32$Show$show(self: i32) i32"));
+        assert!(mir.contains("func bool$Show$show(self: bool) i32"));
+        assert!(mir.contains("set a: i32 = call i32 bool$Show$show(bool true)"));
+        assert!(mir.contains("return call i32 i32$Show$show(i32 4)"));
+    }
+
+    #[test]
+    fn bounds_are_monomorphized() {
+        let input = format!(
+            "{SHOW}func show<T: Show>(value T) i32 {{ return value.show() }}\nfunc main() i32 {{ var a = show(false) return show(2) }}"
+        );
+        let mir = generate(&input, Emit::Mir);
+
+        assert!(mir.contains("func Test$main$show$bool(value: bool) i32"));
+        assert!(mir.contains("return call i32 bool$Show$show(bool value)"));
+        assert!(mir.contains("return call i32 i32$Show$show(i32 value)"));
+    }
+
+    #[test]
+    fn hir_shows_trait_calls() {
+        let input = format!("{SHOW}func main() i32 {{ return 4.show() }}");
+        let hir = generate(&input, Emit::Hir);
+
+        assert!(hir.contains("return <i32 as Show>::show(4)"));
+        assert!(hir.contains("impl Show for bool {"));
+    }
+
+    #[test]
+    fn run_trait_methods() {
+        run(
+            "trait Pick { func pick(self, other Self) Self }
+impl Pick for i32 { func pick(self, other i32) i32 { return other } }
+impl Pick for bool { func pick(self, other bool) bool { return self } }
+func choose<T: Pick>(a T, b T) T { return a.pick(b) }
+func main() i32 { var ok = choose(true, false) return choose(3, 8) }",
+            8,
+        );
+    }
+
+    #[test]
+    fn run_struct_impl() {
+        let input = format!(
+            "{SHOW}struct Point {{ x i32, y i32 }}
+impl Show for Point {{ func show(self) i32 {{ return self.x * 10 + self.y }} }}
+func twice<T: Show>(value T) i32 {{ return value.show() * 2 }}
+func main() i32 {{ var p = Point {{ x: 5, y: 1 }} return twice(p) }}"
+        );
+        let hir = generate(&input, Emit::Hir);
+        let mir = generate(&input, Emit::Mir);
+
+        assert!(hir.contains("impl Show for Point {"));
+        assert!(mir.contains("func Test$main$Point$Show$show(self: %Test$main$Point) i32"));
+        assert!(mir.contains("func Test$main$twice$Test$main$Point(value: %Test$main$Point) i32"));
+        run(&input, 102);
+    }
+}
@@ -1,8 +1,7 @@
-use std::{env, path::PathBuf};
+use std::{env, path::PathBuf, process::Command};
 
 use clap::{Parser, Subcommand};
-use common::cmd::execute;
-use compiler::CompilerCtx;
+use compiler::{Arch, Backend, CompilerCtx, Emit, OptLevel, OutputKind};
 
 #[derive(Parser)]
 #[command(version, about, long_about = None, arg_required_else_help = true)]
@@ -13,11 +12,44 @@ struct Cli {
 
 #[derive(Subcommand)]
 enum Commands {
-    // Check,
-    // Build,
+    Check {
+        #[arg(long, value_name = "PATH")]
+        project: Option<PathBuf>,
+    },
+    Build {
+        #[arg(long, value_name = "PATH")]
+        project: Option<PathBuf>,
+
+        #[arg(long, value_delimiter = ',', value_name = "KINDS")]
+        emit: Vec<Emit>,
+
+        #[arg(long, value_name = "ARCH")]
+        target: Option<Arch>,
+
+        #[arg(long, value_name = "BACKEND", default_value = "llvm")]
+        backend: Backend,
+
+        #[arg(long)]
+        release: bool,
+
+        #[arg(short = 'O', value_name = "LEVEL")]
+        opt_level: Option<OptLevel>,
+
+        #[arg(long, value_name = "KIND")]
+        output: Option<OutputKind>,
+    },
     Run {
         #[arg(long, value_name = "PATH")]
         project: Option<PathBuf>,
+
+        #[arg(long, value_name = "BACKEND", default_value = "llvm")]
+        backend: Backend,
+
+        #[arg(long)]
+        release: bool,
+
+        #[arg(short = 'O', value_name = "LEVEL")]
+        opt_level: Option<OptLevel>,
     },
 }
 
@@ -30,15 +62,66 @@ fn main() {
     };
 
     match command {
-        Commands::Run { project } => {
+        Commands::Check { project } => {
+            let mut compiler = CompilerCtx::builder()
+                .project_path(project.unwrap_or_else(|| env::current_dir().unwrap()))
+                .build();
+
+            compiler::check(&mut compiler);
+            compiler.finish();
+        }
+        Commands::Build {
+            project,
+            emit,
+            target,
+            backend,
+            release,
+            opt_level,
+            output,
+        } => {
+            let mut compiler = CompilerCtx::builder()
+                .project_path(project.unwrap_or_else(|| env::current_dir().unwrap()))
+                .emit(emit)
+                .target(target)
+                .backend(backend)
+                .release(release)
+                .opt_level(opt_level)
+                .output(output)
+                .incremental(true)
+                .build();
+
+            compiler::compile(&mut compiler);
+            compiler.finish();
+        }
+        Commands::Run {
+            project,
+            backend,
+            release,
+            opt_level,
+        } => {
             let mut compiler = CompilerCtx::builder()
                 .project_path(project.unwrap_or_else(|| env::current_dir().unwrap()))
+                .backend(backend)
+                .release(release)
+                .opt_level(opt_level)
+                .output(Some(OutputKind::Executable))
+                .incremental(true)
                 .build();
 
             let executable = compiler::compile(&mut compiler);
+            compiler.finish();
 
-            execute(format!("{executable:#?}"))
+            let Some(executable) = executable else {
+                eprintln!("error: the build did not produce an executable");
+                std::process::exit(1);
+            };
+            match Command::new(&executable).status() {
+                Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+                Err(error) => {
+                    eprintln!("error: failed to run {executable:?}: {error}");
+                    std::process::exit(1);
+                }
+            }
         }
-        _ => todo!(),
     };
 }
@@ -1 +1,88 @@
-// No tests yet
+#[cfg(test)]
+mod tests {
+    use std::{
+        path::{Path, PathBuf},
+        process::{Command, Output},
+    };
+
+    struct Project(PathBuf);
+    impl Project {
+        fn new(name: &str, main: &str) -> Self {
+            Self::with_config(name, main, "")
+        }
+        fn with_config(name: &str, main: &str, config: &str) -> Self {
+            let path =
+                std::env::temp_dir().join(format!("eclipse-cli-{}-{name}", std::process::id()));
+            let _ = std::fs::remove_dir_all(&path);
+            std::fs::create_dir_all(path.join("src")).unwrap();
+            std::fs::write(path.join("src/main.ecl"), main).unwrap();
+            std::fs::write(path.join("eclipse.toml"), config).unwrap();
+            Self(path)
+        }
+        fn path(&self) -> &Path {
+            &self.0
+        }
+    }
+    impl Drop for Project {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn eclipse(arguments: &[&str], project: &Project) -> Output {
+        Command::new(env!("CARGO_BIN_EXE_eclipse"))
+            .args(arguments)
+            .arg("--project")
+            .arg(project.path())
+            .output()
+            .unwrap()
+    }
+
+    #[test]
+    fn check_fails_on_errors() {
+        let project = Project::new("check-errors", "func main() i32 { return y }");
+        let output = eclipse(&["check"], &project);
+
+        assert_eq!(output.status.code(), Some(1));
+        assert!(String::from_utf8_lossy(&output.stdout).contains("'y' not found"));
+    }
+
+    #[test]
+    fn check_succeeds() {
+        let project = Project::new("check-ok", "func main() i32 { return 0 }");
+        let output = eclipse(&["check"], &project);
+
+        assert_eq!(output.status.code(), Some(0));
+    }
+
+    #[test]
+    fn build_fails_on_link_errors() {
+        let project = Project::with_config(
+            "build-link",
+            "func main() i32 { return 0 }",
+            "[build]\nlibraries = [\"missing\"]",
+        );
+        let output = eclipse(&["build", "--backend", "c"], &project);
+
+        assert_eq!(output.status.code(), Some(1));
+        assert!(String::from_utf8_lossy(&output.stdout).contains("failed with"));
+    }
+
+    #[test]
+    fn run_fails_without_executable() {
+        let project = Project::new("run-errors", "func main() i32 { return y }");
+        let output = eclipse(&["run", "--backend", "c"], &project);
+
+        assert_eq!(output.status.code(), Some(1));
+        assert!(String::from_utf8_lossy(&output.stdout).contains("'y' not found"));
+    }
+
+    #[test]
+    fn run_forwards_exit_code_and_output() {
+        let project = Project::new("run-ok", "func main() i32 { println(\"hello\") return 3 }");
+        let output = eclipse(&["run", "--backend", "c"], &project);
+
+        assert_eq!(output.status.code(), Some(3));
+        assert!(String::from_utf8_lossy(&output.stdout).contains("hello\n"));
+    }
+}